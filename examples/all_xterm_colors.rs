@@ -1,8 +1,7 @@
 use colorz::{xterm::XtermColor, Colorize};
 
 fn main() {
-    for i in 0..=255 {
-        let color = XtermColor::from(i);
+    for color in XtermColor::variants() {
         println!("{:?}", color.fg(color));
     }
 }
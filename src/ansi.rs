@@ -116,6 +116,127 @@ macro_rules! MkAnsiColor {
                 self.to_xterm().underline_escape()
             }
 
+            #[inline]
+            /// The name of this color, as accepted by [`Color`]'s `FromStr` impl
+            ///
+            /// ```rust
+            /// use colorz::ansi::AnsiColor;
+            ///
+            /// assert_eq!(AnsiColor::BrightBlue.name(), "bright blue");
+            /// ```
+            pub const fn name(self) -> &'static str {
+                match self {
+                    Self::Black => "black",
+                    Self::Red => "red",
+                    Self::Green => "green",
+                    Self::Yellow => "yellow",
+                    Self::Blue => "blue",
+                    Self::Magenta => "magenta",
+                    Self::Cyan => "cyan",
+                    Self::White => "white",
+                    Self::BrightBlack => "bright black",
+                    Self::BrightRed => "bright red",
+                    Self::BrightGreen => "bright green",
+                    Self::BrightYellow => "bright yellow",
+                    Self::BrightBlue => "bright blue",
+                    Self::BrightMagenta => "bright magenta",
+                    Self::BrightCyan => "bright cyan",
+                    Self::BrightWhite => "bright white",
+                    Self::Default => "default",
+                }
+            }
+
+            /// All ANSI colors, in declaration order
+            const ALL: &'static [AnsiColor] = &[$(AnsiColor::$name,)*];
+
+            /// Iterate over every ANSI color, in declaration order (including [`AnsiColor::Default`])
+            ///
+            /// ```rust
+            /// use colorz::ansi::AnsiColor;
+            ///
+            /// assert_eq!(AnsiColor::variants().len(), 17);
+            /// assert_eq!(AnsiColor::variants().next(), Some(AnsiColor::Black));
+            /// ```
+            #[inline]
+            pub fn variants() -> core::iter::Copied<core::slice::Iter<'static, Self>> {
+                Self::ALL.iter().copied()
+            }
+
+            /// The color with the given index, the inverse of `self as u8`
+            pub(crate) const fn from_index(index: u8) -> Option<Self> {
+                if (index as usize) < Self::ALL.len() {
+                    Some(Self::ALL[index as usize])
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            /// The color with the given foreground or background SGR code
+            ///
+            /// ```rust
+            /// use colorz::ansi::AnsiColor;
+            ///
+            /// assert_eq!(AnsiColor::from_code(31), Some(AnsiColor::Red));
+            /// assert_eq!(AnsiColor::from_code(104), Some(AnsiColor::BrightBlue));
+            /// assert_eq!(AnsiColor::from_code(39), Some(AnsiColor::Default));
+            /// assert_eq!(AnsiColor::from_code(38), None);
+            /// ```
+            pub const fn from_code(code: u8) -> Option<Self> {
+                match Self::from_foreground_code(code) {
+                    Some(color) => Some(color),
+                    None => Self::from_background_code(code),
+                }
+            }
+
+            #[inline]
+            /// The color with the given foreground SGR code (30 to 37, 39 or 90 to 97)
+            pub const fn from_foreground_code(code: u8) -> Option<Self> {
+                match code {
+                    $($fg => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            /// The color with the given background SGR code (40 to 47, 49 or 100 to 107)
+            pub const fn from_background_code(code: u8) -> Option<Self> {
+                match code {
+                    $($bg => Some(Self::$name),)*
+                    _ => None,
+                }
+            }
+
+            #[inline]
+            /// The ANSI foreground SGR code, the numeric version of [`foreground_args`](Self::foreground_args)
+            ///
+            /// ```rust
+            /// use colorz::ansi::AnsiColor;
+            ///
+            /// assert_eq!(AnsiColor::Red.foreground_code_u8(), 31);
+            /// assert_eq!(AnsiColor::BrightRed.foreground_code_u8(), 91);
+            /// ```
+            pub const fn foreground_code_u8(self) -> u8 {
+                match self {
+                    $(Self::$name => $fg,)*
+                }
+            }
+
+            #[inline]
+            /// The ANSI background SGR code, the numeric version of [`background_args`](Self::background_args)
+            ///
+            /// ```rust
+            /// use colorz::ansi::AnsiColor;
+            ///
+            /// assert_eq!(AnsiColor::Red.background_code_u8(), 41);
+            /// assert_eq!(AnsiColor::BrightRed.background_code_u8(), 101);
+            /// ```
+            pub const fn background_code_u8(self) -> u8 {
+                match self {
+                    $(Self::$name => $bg,)*
+                }
+            }
+
             #[inline]
             /// The corresponding Xterm color
             pub const fn to_xterm(self) -> crate::xterm::XtermColor {
@@ -261,3 +382,38 @@ MkAnsiColor! {
 
     16 Default   39 49
 }
+
+impl AnsiColor {
+    /// Is this one of the bright colors (like [`AnsiColor::BrightRed`])
+    ///
+    /// ```rust
+    /// use colorz::ansi::AnsiColor;
+    ///
+    /// assert!(AnsiColor::BrightRed.is_bright());
+    /// assert!(!AnsiColor::Red.is_bright());
+    /// ```
+    #[inline]
+    pub const fn is_bright(self) -> bool {
+        matches!(self.foreground_code_u8(), 90..=97)
+    }
+
+    /// The non-bright version of this color, or the color itself if it isn't bright
+    ///
+    /// ```rust
+    /// use colorz::ansi::AnsiColor;
+    ///
+    /// assert_eq!(AnsiColor::BrightRed.to_normal(), AnsiColor::Red);
+    /// assert_eq!(AnsiColor::Red.to_normal(), AnsiColor::Red);
+    /// assert_eq!(AnsiColor::Default.to_normal(), AnsiColor::Default);
+    /// ```
+    #[inline]
+    pub const fn to_normal(self) -> Self {
+        match self.foreground_code_u8() {
+            code @ 90..=97 => match Self::from_foreground_code(code - 60) {
+                Some(color) => color,
+                None => self,
+            },
+            _ => self,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Runtime styles with precomputed escape sequences
+
+use core::fmt;
+
+use crate::{mode::Stream, value::Options, Style, StyleEscapes, StyledValue};
+
+/// A runtime style, with the escape sequences to apply and clear it rendered once up front
+///
+/// Formatting a [`StyledValue`](crate::StyledValue) builds the escape sequences each time. When the
+/// same style is used for a lot of values (like the level of a log line), a `CachedStyle` can
+/// write the cached escapes instead. The coloring mode and the global settings in [`mode`](crate::mode)
+/// and [`palette`](crate::palette) are still checked each time a value is formatted.
+///
+/// Values are otherwise formatted like a [`StyledValue`](crate::StyledValue), so they're padded outside
+/// of the escapes. The cached escapes aren't used when they can't be, like when the global settings
+/// change the style, or the value is written [`per_line`](CachedStyledValue::per_line).
+///
+/// ```rust
+/// use colorz::{style, CachedStyle, Style, mode::Stream};
+///
+/// static ERROR: CachedStyle = style!("bold red").precompute();
+///
+/// let line = format!("{}: disk full", ERROR.value("error").stream(Stream::AlwaysColor));
+///
+/// # if Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(line, "\x1b[31;1merror\x1b[39;22m: disk full");
+/// # }
+/// assert_eq!(ERROR.value("error").stream(Stream::NeverColor).to_string(), "error");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CachedStyle {
+    style: Style,
+    escapes: StyleEscapes,
+}
+
+impl Style {
+    /// Render the escape sequences of this style once, see [`CachedStyle`]
+    ///
+    /// ```rust
+    /// use colorz::{Style, ansi};
+    ///
+    /// let cached = Style::new().fg(ansi::Blue).into_runtime_style().precompute();
+    ///
+    /// assert_eq!(cached.prefix(), "\x1b[34m");
+    /// ```
+    #[inline]
+    pub const fn precompute(self) -> CachedStyle {
+        CachedStyle::new(self)
+    }
+}
+
+impl CachedStyle {
+    /// Render the escape sequences of `style`
+    ///
+    /// ```rust
+    /// use colorz::{CachedStyle, Style};
+    ///
+    /// let cached = CachedStyle::new(Style::new().bold().into_runtime_style());
+    ///
+    /// assert_eq!(cached.prefix(), "\x1b[1m");
+    /// assert_eq!(cached.suffix(), "\x1b[22m");
+    /// ```
+    #[inline]
+    pub const fn new(style: Style) -> Self {
+        Self {
+            style,
+            escapes: style.escapes(),
+        }
+    }
+
+    /// The style which was rendered
+    #[inline]
+    pub const fn style(&self) -> Style {
+        self.style
+    }
+
+    /// The escape sequence which applies the style, see [`StyleEscapes::prefix`]
+    #[inline]
+    pub const fn prefix(&self) -> &str {
+        self.escapes.prefix()
+    }
+
+    /// The escape sequence which clears the style, see [`StyleEscapes::suffix`]
+    #[inline]
+    pub const fn suffix(&self) -> &str {
+        self.escapes.suffix()
+    }
+
+    /// Style a value with this style
+    ///
+    /// ```rust
+    /// use colorz::{style, CachedStyle};
+    ///
+    /// let warning = style!("yellow").precompute();
+    ///
+    /// for i in 0..3 {
+    ///     println!("{} {i}", warning.value("warning:"));
+    /// }
+    /// ```
+    #[inline]
+    pub const fn value<T>(&self, value: T) -> CachedStyledValue<'_, T> {
+        CachedStyledValue {
+            value,
+            stream: None,
+            style: self,
+            options: Options::DEFAULT,
+        }
+    }
+}
+
+/// A value styled with a [`CachedStyle`], created from [`CachedStyle::value`]
+#[derive(Debug, Clone, Copy)]
+pub struct CachedStyledValue<'a, T> {
+    /// The value to style
+    pub value: T,
+    /// The stream to use
+    pub stream: Option<Stream>,
+    style: &'a CachedStyle,
+    options: Options,
+}
+
+impl<T> CachedStyledValue<'_, T> {
+    /// Set the stream this value will be written to, see [`StyledValue::stream`](crate::StyledValue::stream)
+    #[inline]
+    pub const fn stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Set the stream this value will be written to, or use the default stream if `None`
+    #[inline]
+    pub const fn stream_opt(mut self, stream: Option<Stream>) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Clear the style before each newline, see [`StyledValue::per_line`](crate::StyledValue::per_line)
+    #[inline]
+    pub const fn per_line(mut self) -> Self {
+        self.options.per_line = true;
+        self
+    }
+
+    /// Restore the style of the enclosing styled value after this value,
+    /// see [`StyledValue::nested`](crate::StyledValue::nested)
+    #[inline]
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub const fn nested(mut self) -> Self {
+        self.options.nested = true;
+        self
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for CachedStyledValue<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = StyledValue {
+            value: &self.value,
+            style: self.style.style,
+            stream: self.stream,
+            options: self.options,
+        };
+
+        value.fmt_padded(f, Some(&self.style.escapes), |value, f| value.fmt(f))
+    }
+}
@@ -0,0 +1,106 @@
+//! Support for the `max-colors-256` and `max-colors-16` features, which downgrade
+//! higher-fidelity colors when they are written
+//!
+//! Compile time colors are downgraded at compile time, so they don't have any runtime cost
+
+use crate::{
+    ansi::AnsiColor,
+    mode::{ColorKind, MAX_COLOR_KIND},
+    xterm::XtermColor,
+    Color,
+};
+
+/// The kind of color which is written in place of a color of the given kind
+pub(crate) const fn kind(kind: ColorKind) -> ColorKind {
+    kind.min(MAX_COLOR_KIND)
+}
+
+/// A color which is written in place of a higher-fidelity color
+#[derive(Clone, Copy)]
+pub(crate) enum Capped {
+    Ansi(AnsiColor),
+    Xterm(XtermColor),
+}
+
+/// The color which is written in place of `color`, or `None` if `color` is written as is
+pub(crate) const fn cap(color: Color) -> Option<Capped> {
+    match (color, color.downgrade(MAX_COLOR_KIND)) {
+        (Color::Ansi(_), _) => None,
+        (_, Some(Color::Ansi(capped))) => Some(Capped::Ansi(capped)),
+        (Color::Xterm(_), _) => None,
+        (_, Some(Color::Xterm(capped))) => Some(Capped::Xterm(capped)),
+        _ => None,
+    }
+}
+
+macro_rules! capped_str {
+    ($($name:ident)*) => {
+        impl Capped {$(
+            pub(crate) const fn $name(self) -> &'static str {
+                match self {
+                    Self::Ansi(color) => color.$name(),
+                    Self::Xterm(color) => color.$name(),
+                }
+            }
+        )*}
+    };
+}
+
+capped_str! {
+    foreground_args
+    background_args
+    underline_args
+    foreground_escape
+    background_escape
+    underline_escape
+}
+
+/// Implement the `ColorSpec` methods for a compile time color, which is downgraded at compile time
+macro_rules! comptime_capped_methods {
+    ($color:expr) => {
+        comptime_capped_methods! {
+            $color;
+            foreground_args FOREGROUND_ARGS
+            background_args BACKGROUND_ARGS
+            underline_args UNDERLINE_ARGS
+            foreground_escape FOREGROUND_ESCAPE
+            background_escape BACKGROUND_ESCAPE
+            underline_escape UNDERLINE_ESCAPE
+        }
+    };
+    ($color:expr; $($name:ident $value:ident)*) => {$(
+        #[inline]
+        fn $name(self) -> &'static str {
+            const {
+                match crate::cap::cap($color) {
+                    Some(capped) => capped.$name(),
+                    None => Self::$value,
+                }
+            }
+        }
+    )*};
+}
+
+/// Implement the `ColorSpec` methods for a runtime color, by downgrading it if needed
+macro_rules! runtime_capped_methods {
+    ($self:ident => $color:expr) => {
+        runtime_capped_methods! {
+            $self => $color;
+            foreground_args
+            background_args
+            underline_args
+            foreground_escape
+            background_escape
+            underline_escape
+        }
+    };
+    ($self:ident => $color:expr; $($name:ident)*) => {$(
+        #[inline]
+        fn $name($self) -> &'static str {
+            match crate::cap::cap($color) {
+                Some(capped) => capped.$name(),
+                None => $self.$name(),
+            }
+        }
+    )*};
+}
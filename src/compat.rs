@@ -0,0 +1,6 @@
+//! Compatibility layers for other terminal coloring crates
+//!
+//! These modules mirror the API of other crates on top of colorz's types, so code written for those
+//! crates can switch to colorz by only changing imports.
+
+pub mod colored;
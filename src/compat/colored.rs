@@ -0,0 +1,545 @@
+//! A compatibility layer for the [`colored`](https://docs.rs/colored) crate
+//!
+//! This mirrors the `colored` API (the [`Colorize`] trait, [`ColoredString`], [`Color`], [`Styles`]
+//! and [`control`]), implemented on top of colorz. So code using `colored` can switch to colorz by
+//! replacing `use colored::*` with `use colorz::compat::colored::*`.
+//!
+//! The colors are written via [`StyledValue`](crate::StyledValue), so they respect colorz's
+//! [coloring mode](crate::mode) instead of `colored`'s environment variable handling.
+//! Use [`set_coloring_mode_from_env`](crate::mode::set_coloring_mode_from_env) to follow the
+//! `NO_COLOR` and `CLICOLOR` conventions like `colored` does.
+//!
+//! Don't import this [`Colorize`] and [`colorz::Colorize`](crate::Colorize) in the same scope,
+//! since they have methods with the same names.
+//!
+//! ```rust
+//! use colorz::compat::colored::*;
+//!
+//! let warning = "warning".yellow().bold();
+//! println!("{}: {}", warning, "something happened".italic());
+//!
+//! assert_eq!(warning.fgcolor(), Some(Color::Yellow));
+//! assert_eq!(&*warning, "warning");
+//!
+//! control::set_override(false);
+//! assert_eq!(warning.to_string(), "warning");
+//! ```
+
+use alloc::string::String;
+use core::{fmt, ops::Deref, str::FromStr};
+
+use crate::{ansi::AnsiColor, rgb::RgbColor, Effect, EffectFlags, Style};
+
+/// A color, like `colored::Color`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The ANSI black color
+    Black,
+    /// The ANSI red color
+    Red,
+    /// The ANSI green color
+    Green,
+    /// The ANSI yellow color
+    Yellow,
+    /// The ANSI blue color
+    Blue,
+    /// The ANSI magenta color
+    Magenta,
+    /// The ANSI cyan color
+    Cyan,
+    /// The ANSI white color
+    White,
+    /// The ANSI bright black color
+    BrightBlack,
+    /// The ANSI bright red color
+    BrightRed,
+    /// The ANSI bright green color
+    BrightGreen,
+    /// The ANSI bright yellow color
+    BrightYellow,
+    /// The ANSI bright blue color
+    BrightBlue,
+    /// The ANSI bright magenta color
+    BrightMagenta,
+    /// The ANSI bright cyan color
+    BrightCyan,
+    /// The ANSI bright white color
+    BrightWhite,
+    /// An RGB color
+    TrueColor {
+        /// The red component
+        r: u8,
+        /// The green component
+        g: u8,
+        /// The blue component
+        b: u8,
+    },
+}
+
+impl From<Color> for crate::Color {
+    #[inline]
+    fn from(color: Color) -> Self {
+        crate::Color::Ansi(match color {
+            Color::Black => AnsiColor::Black,
+            Color::Red => AnsiColor::Red,
+            Color::Green => AnsiColor::Green,
+            Color::Yellow => AnsiColor::Yellow,
+            Color::Blue => AnsiColor::Blue,
+            Color::Magenta => AnsiColor::Magenta,
+            Color::Cyan => AnsiColor::Cyan,
+            Color::White => AnsiColor::White,
+            Color::BrightBlack => AnsiColor::BrightBlack,
+            Color::BrightRed => AnsiColor::BrightRed,
+            Color::BrightGreen => AnsiColor::BrightGreen,
+            Color::BrightYellow => AnsiColor::BrightYellow,
+            Color::BrightBlue => AnsiColor::BrightBlue,
+            Color::BrightMagenta => AnsiColor::BrightMagenta,
+            Color::BrightCyan => AnsiColor::BrightCyan,
+            Color::BrightWhite => AnsiColor::BrightWhite,
+            Color::TrueColor { r, g, b } => {
+                return crate::Color::Rgb(RgbColor {
+                    red: r,
+                    green: g,
+                    blue: b,
+                })
+            }
+        })
+    }
+}
+
+/// Parses the color names accepted by `colored`, like `"red"`, `"bright red"` or `"bright_red"`
+///
+/// ```rust
+/// use colorz::compat::colored::Color;
+///
+/// assert_eq!("Bright Blue".parse(), Ok(Color::BrightBlue));
+/// assert_eq!("purple".parse(), Ok(Color::Magenta));
+/// assert_eq!("orange".parse::<Color>(), Err(()));
+/// ```
+impl FromStr for Color {
+    type Err = ();
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (bright, name) = match s.get(..7) {
+            Some(prefix)
+                if prefix.eq_ignore_ascii_case("bright ")
+                    || prefix.eq_ignore_ascii_case("bright_") =>
+            {
+                (true, &s[7..])
+            }
+            _ => (false, s),
+        };
+
+        const NAMES: [(&str, Color, Color); 8] = [
+            ("black", Color::Black, Color::BrightBlack),
+            ("red", Color::Red, Color::BrightRed),
+            ("green", Color::Green, Color::BrightGreen),
+            ("yellow", Color::Yellow, Color::BrightYellow),
+            ("blue", Color::Blue, Color::BrightBlue),
+            ("magenta", Color::Magenta, Color::BrightMagenta),
+            ("cyan", Color::Cyan, Color::BrightCyan),
+            ("white", Color::White, Color::BrightWhite),
+        ];
+
+        let name = if name.eq_ignore_ascii_case("purple") {
+            "magenta"
+        } else {
+            name
+        };
+
+        NAMES
+            .iter()
+            .find(|(n, _, _)| n.eq_ignore_ascii_case(name))
+            .map(|&(_, normal, bright_color)| if bright { bright_color } else { normal })
+            .ok_or(())
+    }
+}
+
+/// Parses the color name, using [`Color::White`] for unknown names like `colored` does
+impl From<&str> for Color {
+    #[inline]
+    fn from(s: &str) -> Self {
+        s.parse().unwrap_or(Color::White)
+    }
+}
+
+/// Parses the color name, using [`Color::White`] for unknown names like `colored` does
+impl From<String> for Color {
+    #[inline]
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+/// An effect, like `colored::Styles`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Styles {
+    /// No colors or effects
+    Clear,
+    /// [`Effect::Bold`]
+    Bold,
+    /// [`Effect::Dimmed`]
+    Dimmed,
+    /// [`Effect::Underline`]
+    Underline,
+    /// [`Effect::Reversed`]
+    Reversed,
+    /// [`Effect::Italic`]
+    Italic,
+    /// [`Effect::Blink`]
+    Blink,
+    /// [`Effect::Hidden`]
+    Hidden,
+    /// [`Effect::Strikethrough`]
+    Strikethrough,
+}
+
+impl Styles {
+    /// The colorz effect for this style, [`Styles::Clear`] doesn't have an effect
+    #[inline]
+    pub const fn to_effect(self) -> Option<Effect> {
+        Some(match self {
+            Styles::Clear => return None,
+            Styles::Bold => Effect::Bold,
+            Styles::Dimmed => Effect::Dimmed,
+            Styles::Underline => Effect::Underline,
+            Styles::Reversed => Effect::Reversed,
+            Styles::Italic => Effect::Italic,
+            Styles::Blink => Effect::Blink,
+            Styles::Hidden => Effect::Hidden,
+            Styles::Strikethrough => Effect::Strikethrough,
+        })
+    }
+}
+
+/// A string with colors and effects, like `colored::ColoredString`
+///
+/// This dereferences to the uncolored string, and the colors are only written when it's formatted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ColoredString {
+    input: String,
+    fgcolor: Option<Color>,
+    bgcolor: Option<Color>,
+    effects: EffectFlags,
+}
+
+impl ColoredString {
+    /// The foreground color, if any
+    #[inline]
+    pub const fn fgcolor(&self) -> Option<Color> {
+        self.fgcolor
+    }
+
+    /// The background color, if any
+    #[inline]
+    pub const fn bgcolor(&self) -> Option<Color> {
+        self.bgcolor
+    }
+
+    /// The effects applied to the string
+    #[inline]
+    pub const fn style(&self) -> EffectFlags {
+        self.effects
+    }
+
+    /// Are there no colors or effects
+    #[inline]
+    pub const fn is_plain(&self) -> bool {
+        self.fgcolor.is_none() && self.bgcolor.is_none() && self.effects.is_plain()
+    }
+
+    /// Remove the foreground color
+    #[inline]
+    pub const fn clear_fgcolor(&mut self) {
+        self.fgcolor = None;
+    }
+
+    /// Remove the background color
+    #[inline]
+    pub const fn clear_bgcolor(&mut self) {
+        self.bgcolor = None;
+    }
+
+    /// Remove all effects
+    #[inline]
+    pub const fn clear_style(&mut self) {
+        self.effects = EffectFlags::new();
+    }
+
+    /// The colorz style used to write this string
+    #[inline]
+    pub fn to_style(&self) -> Style {
+        Style {
+            foreground: self.fgcolor.map(Into::into),
+            background: self.bgcolor.map(Into::into),
+            underline_color: None,
+            effects: self.effects,
+        }
+    }
+
+    /// Convert to the uncolored string
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.input
+    }
+}
+
+impl Deref for ColoredString {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.input
+    }
+}
+
+impl From<&str> for ColoredString {
+    #[inline]
+    fn from(input: &str) -> Self {
+        String::from(input).into()
+    }
+}
+
+impl From<String> for ColoredString {
+    #[inline]
+    fn from(input: String) -> Self {
+        Self {
+            input,
+            ..Self::default()
+        }
+    }
+}
+
+impl fmt::Display for ColoredString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::Colorize::style_with(self.input.as_str(), self.to_style()).fmt(f)
+    }
+}
+
+macro_rules! colored_methods {
+    (
+        colors { $($color:ident $fg:ident $bg:ident)* }
+        styles { $($style:ident $style_fn:ident)* }
+    ) => {
+        $(
+            #[doc = concat!("Change the foreground to [`Color::", stringify!($color), "`]")]
+            #[inline]
+            fn $fg(self) -> ColoredString
+            where
+                Self: Sized,
+            {
+                self.color(Color::$color)
+            }
+
+            #[doc = concat!("Change the background to [`Color::", stringify!($color), "`]")]
+            #[inline]
+            fn $bg(self) -> ColoredString
+            where
+                Self: Sized,
+            {
+                self.on_color(Color::$color)
+            }
+        )*
+
+        $(
+            #[doc = concat!("Add [`Effect::", stringify!($style), "`]")]
+            #[inline]
+            fn $style_fn(self) -> ColoredString
+            where
+                Self: Sized,
+            {
+                self.with_style(Styles::$style)
+            }
+        )*
+    };
+}
+
+/// Methods to color strings, like `colored::Colorize`
+///
+/// Unlike [`colorz::Colorize`](crate::Colorize), these methods always produce a [`ColoredString`]
+pub trait Colorize {
+    /// Change the foreground color
+    fn color<S: Into<Color>>(self, color: S) -> ColoredString;
+    /// Change the background color
+    fn on_color<S: Into<Color>>(self, color: S) -> ColoredString;
+    /// Remove all colors and effects
+    fn clear(self) -> ColoredString;
+    /// Add the effect of `style`, or remove all colors and effects for [`Styles::Clear`]
+    fn with_style(self, style: Styles) -> ColoredString;
+
+    /// Remove all colors and effects, the same as [`clear`](Colorize::clear)
+    #[inline]
+    fn normal(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.clear()
+    }
+
+    /// Change the foreground to an RGB color
+    #[inline]
+    fn truecolor(self, r: u8, g: u8, b: u8) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.color(Color::TrueColor { r, g, b })
+    }
+
+    /// Change the background to an RGB color
+    #[inline]
+    fn on_truecolor(self, r: u8, g: u8, b: u8) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.on_color(Color::TrueColor { r, g, b })
+    }
+
+    /// Swap the foreground and background colors, the same as [`reversed`](Colorize::reversed)
+    #[inline]
+    fn reverse(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.with_style(Styles::Reversed)
+    }
+
+    /// Change the foreground to magenta
+    #[inline]
+    fn purple(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.color(Color::Magenta)
+    }
+
+    /// Change the foreground to bright magenta
+    #[inline]
+    fn bright_purple(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.color(Color::BrightMagenta)
+    }
+
+    /// Change the background to magenta
+    #[inline]
+    fn on_purple(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.on_color(Color::Magenta)
+    }
+
+    /// Change the background to bright magenta
+    #[inline]
+    fn on_bright_purple(self) -> ColoredString
+    where
+        Self: Sized,
+    {
+        self.on_color(Color::BrightMagenta)
+    }
+
+    colored_methods! {
+        colors {
+            Black black on_black
+            Red red on_red
+            Green green on_green
+            Yellow yellow on_yellow
+            Blue blue on_blue
+            Magenta magenta on_magenta
+            Cyan cyan on_cyan
+            White white on_white
+            BrightBlack bright_black on_bright_black
+            BrightRed bright_red on_bright_red
+            BrightGreen bright_green on_bright_green
+            BrightYellow bright_yellow on_bright_yellow
+            BrightBlue bright_blue on_bright_blue
+            BrightMagenta bright_magenta on_bright_magenta
+            BrightCyan bright_cyan on_bright_cyan
+            BrightWhite bright_white on_bright_white
+        }
+        styles {
+            Bold bold
+            Dimmed dimmed
+            Italic italic
+            Underline underline
+            Blink blink
+            Reversed reversed
+            Hidden hidden
+            Strikethrough strikethrough
+        }
+    }
+}
+
+impl Colorize for ColoredString {
+    #[inline]
+    fn color<S: Into<Color>>(mut self, color: S) -> ColoredString {
+        self.fgcolor = Some(color.into());
+        self
+    }
+
+    #[inline]
+    fn on_color<S: Into<Color>>(mut self, color: S) -> ColoredString {
+        self.bgcolor = Some(color.into());
+        self
+    }
+
+    #[inline]
+    fn clear(self) -> ColoredString {
+        self.input.into()
+    }
+
+    #[inline]
+    fn with_style(mut self, style: Styles) -> ColoredString {
+        match style.to_effect() {
+            Some(effect) => self.effects.set(effect),
+            None => return self.clear(),
+        }
+        self
+    }
+}
+
+impl Colorize for &str {
+    #[inline]
+    fn color<S: Into<Color>>(self, color: S) -> ColoredString {
+        ColoredString::from(self).color(color)
+    }
+
+    #[inline]
+    fn on_color<S: Into<Color>>(self, color: S) -> ColoredString {
+        ColoredString::from(self).on_color(color)
+    }
+
+    #[inline]
+    fn clear(self) -> ColoredString {
+        self.into()
+    }
+
+    #[inline]
+    fn with_style(self, style: Styles) -> ColoredString {
+        ColoredString::from(self).with_style(style)
+    }
+}
+
+/// Controlling when colors are written, like `colored::control`
+pub mod control {
+    use crate::mode::{self, Mode};
+
+    /// Always color (`true`) or never color (`false`), regardless of the environment
+    ///
+    /// This sets colorz's global [coloring mode](crate::mode::set_coloring_mode)
+    #[inline]
+    pub fn set_override(override_colorize: bool) {
+        mode::set_coloring_mode(Mode::from(override_colorize));
+    }
+
+    /// Go back to detecting if colors should be written
+    #[inline]
+    pub fn unset_override() {
+        mode::set_coloring_mode(Mode::Detect);
+    }
+}
@@ -0,0 +1,153 @@
+//! Styling messages for the browser's devtools console
+//!
+//! The browser console doesn't understand ANSI escape sequences, instead `console.log` takes a
+//! format string where each `%c` marker applies the CSS in the corresponding argument to the following text.
+//! A [`ConsoleMessage`] builds up the format string and the CSS arguments, which can then be passed
+//! to `console.log` (for example with `web_sys::console::log`).
+
+use core::fmt::{self, Write};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{OptionalColor, Style, StyledText, StyledValue};
+
+/// A message for `console.log`, made of a format string containing `%c` markers and the CSS for each marker
+///
+/// ```rust
+/// use colorz::{Colorize, console::ConsoleMessage};
+///
+/// let mut message = ConsoleMessage::new();
+/// message.push_value(&"error".red().bold());
+/// message.push_plain(": 100% broken");
+///
+/// assert_eq!(message.format, "%cerror%c: 100%% broken");
+/// assert_eq!(message.styles, ["color: rgb(205, 0, 0); font-weight: bold", ""]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ConsoleMessage {
+    /// The format string, this should be the first argument to `console.log`
+    pub format: String,
+    /// The CSS for each `%c` marker in the format string, these should be the rest of the arguments to `console.log`
+    pub styles: Vec<String>,
+}
+
+/// A writer which escapes `%` for console format strings
+struct Escape<'a>(&'a mut String);
+
+impl Write for Escape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut parts = s.split('%');
+
+        if let Some(part) = parts.next() {
+            self.0.push_str(part);
+        }
+
+        for part in parts {
+            self.0.push_str("%%");
+            self.0.push_str(part);
+        }
+
+        Ok(())
+    }
+}
+
+impl ConsoleMessage {
+    /// Create an empty message
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            format: String::new(),
+            styles: Vec::new(),
+        }
+    }
+
+    /// Start a new piece of text with the given css, if it's different from the current css
+    fn set_css(&mut self, css: String) {
+        let current = self.styles.last().map_or("", String::as_str);
+
+        if current != css {
+            self.format.push_str("%c");
+            self.styles.push(css);
+        }
+    }
+
+    /// Append some text with the given style
+    ///
+    /// The style is always used, regardless of the [coloring mode](crate::mode)
+    #[inline]
+    pub fn push_styled<F, B, U>(&mut self, style: Style<F, B, U>, text: &str)
+    where
+        F: OptionalColor,
+        B: OptionalColor,
+        U: OptionalColor,
+    {
+        self.push_display(style.to_runtime(), &text)
+    }
+
+    /// Append some text without any style
+    #[inline]
+    pub fn push_plain(&mut self, text: &str) {
+        self.push_styled(Style::new(), text)
+    }
+
+    /// Append a styled value, using its style
+    ///
+    /// The style is always used, regardless of the [coloring mode](crate::mode).
+    /// Any ANSI escape sequences written by the value itself are removed.
+    #[inline]
+    pub fn push_value<T, F, B, U>(&mut self, value: &StyledValue<T, F, B, U>)
+    where
+        T: fmt::Display,
+        F: OptionalColor,
+        B: OptionalColor,
+        U: OptionalColor,
+    {
+        self.push_display(value.resolved_style(), &value.value)
+    }
+
+    fn push_display(&mut self, style: Style, value: &dyn fmt::Display) {
+        let mut css = String::new();
+        // writing to a `String` can't fail
+        let _ = write!(css, "{}", style.to_css());
+        self.set_css(css);
+
+        let _ = write!(
+            Escape(&mut self.format),
+            "{}",
+            crate::strip::StripAnsi(value)
+        );
+    }
+}
+
+impl<F: OptionalColor, B: OptionalColor, U: OptionalColor, T: fmt::Display>
+    StyledValue<T, F, B, U>
+{
+    /// Convert the styled value to a message for the browser's devtools console, see [`ConsoleMessage`]
+    ///
+    /// ```rust
+    /// use colorz::Colorize;
+    ///
+    /// let message = "hello".italics().to_console();
+    ///
+    /// assert_eq!(message.format, "%chello");
+    /// assert_eq!(message.styles, ["font-style: italic"]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn to_console(&self) -> ConsoleMessage {
+        let mut message = ConsoleMessage::new();
+        message.push_value(self);
+        message
+    }
+}
+
+impl From<&StyledText> for ConsoleMessage {
+    #[inline]
+    fn from(text: &StyledText) -> Self {
+        let mut message = ConsoleMessage::new();
+        for (style, text) in text.spans() {
+            message.push_styled(style, text);
+        }
+        message
+    }
+}
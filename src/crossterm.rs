@@ -0,0 +1,161 @@
+//! Conversions between colorz and [`crossterm`](::crossterm) styles, enabled by the `crossterm` feature
+//!
+//! ```rust
+//! use colorz::{Style, ansi, xterm, rgb::RgbColor};
+//! use crossterm::style::{Attribute, Color, ContentStyle};
+//!
+//! let style = Style::new().fg(ansi::BrightRed).bg(xterm::Gray0).bold().into_runtime_style();
+//! let content: ContentStyle = style.into();
+//!
+//! assert_eq!(content.foreground_color, Some(Color::Red));
+//! assert_eq!(content.background_color, Some(Color::AnsiValue(16)));
+//! assert!(content.attributes.has(Attribute::Bold));
+//! assert_eq!(Style::from(content), style);
+//! ```
+
+use ::crossterm::style::{Attribute, Attributes, Color as CrosstermColor, ContentStyle};
+
+use crate::{ansi::AnsiColor, rgb::RgbColor, xterm::XtermColor, Color, Effect, EffectFlags, Style};
+
+/// The effects which have a crossterm attribute, [`Effect::SuperScript`] and [`Effect::SubScript`] don't have one
+const ATTRIBUTES: [(Effect, Attribute); 13] = [
+    (Effect::Bold, Attribute::Bold),
+    (Effect::Dimmed, Attribute::Dim),
+    (Effect::Italic, Attribute::Italic),
+    (Effect::Underline, Attribute::Underlined),
+    (Effect::DoubleUnderline, Attribute::DoubleUnderlined),
+    (Effect::Blink, Attribute::SlowBlink),
+    (Effect::BlinkFast, Attribute::RapidBlink),
+    (Effect::Reversed, Attribute::Reverse),
+    (Effect::Hidden, Attribute::Hidden),
+    (Effect::Strikethrough, Attribute::CrossedOut),
+    (Effect::Overline, Attribute::OverLined),
+    (Effect::Framed, Attribute::Framed),
+    (Effect::Encircled, Attribute::Encircled),
+];
+
+/// Crossterm calls the normal ANSI colors "dark" and the bright ANSI colors by their plain names
+impl From<Color> for CrosstermColor {
+    #[inline]
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Ansi(color) => match color {
+                AnsiColor::Black => CrosstermColor::Black,
+                AnsiColor::Red => CrosstermColor::DarkRed,
+                AnsiColor::Green => CrosstermColor::DarkGreen,
+                AnsiColor::Yellow => CrosstermColor::DarkYellow,
+                AnsiColor::Blue => CrosstermColor::DarkBlue,
+                AnsiColor::Magenta => CrosstermColor::DarkMagenta,
+                AnsiColor::Cyan => CrosstermColor::DarkCyan,
+                AnsiColor::White => CrosstermColor::Grey,
+                AnsiColor::BrightBlack => CrosstermColor::DarkGrey,
+                AnsiColor::BrightRed => CrosstermColor::Red,
+                AnsiColor::BrightGreen => CrosstermColor::Green,
+                AnsiColor::BrightYellow => CrosstermColor::Yellow,
+                AnsiColor::BrightBlue => CrosstermColor::Blue,
+                AnsiColor::BrightMagenta => CrosstermColor::Magenta,
+                AnsiColor::BrightCyan => CrosstermColor::Cyan,
+                AnsiColor::BrightWhite => CrosstermColor::White,
+                AnsiColor::Default => CrosstermColor::Reset,
+            },
+            Color::Xterm(color) => CrosstermColor::AnsiValue(color as u8),
+            Color::Css(color) => color.rgb().into(),
+            Color::Rgb(color) => color.into(),
+            Color::Default => CrosstermColor::Reset,
+        }
+    }
+}
+
+impl From<RgbColor> for CrosstermColor {
+    #[inline]
+    fn from(RgbColor { red, green, blue }: RgbColor) -> Self {
+        CrosstermColor::Rgb {
+            r: red,
+            g: green,
+            b: blue,
+        }
+    }
+}
+
+impl From<CrosstermColor> for Color {
+    #[inline]
+    fn from(color: CrosstermColor) -> Self {
+        Color::Ansi(match color {
+            CrosstermColor::Reset => AnsiColor::Default,
+            CrosstermColor::Black => AnsiColor::Black,
+            CrosstermColor::DarkGrey => AnsiColor::BrightBlack,
+            CrosstermColor::Red => AnsiColor::BrightRed,
+            CrosstermColor::DarkRed => AnsiColor::Red,
+            CrosstermColor::Green => AnsiColor::BrightGreen,
+            CrosstermColor::DarkGreen => AnsiColor::Green,
+            CrosstermColor::Yellow => AnsiColor::BrightYellow,
+            CrosstermColor::DarkYellow => AnsiColor::Yellow,
+            CrosstermColor::Blue => AnsiColor::BrightBlue,
+            CrosstermColor::DarkBlue => AnsiColor::Blue,
+            CrosstermColor::Magenta => AnsiColor::BrightMagenta,
+            CrosstermColor::DarkMagenta => AnsiColor::Magenta,
+            CrosstermColor::Cyan => AnsiColor::BrightCyan,
+            CrosstermColor::DarkCyan => AnsiColor::Cyan,
+            CrosstermColor::White => AnsiColor::BrightWhite,
+            CrosstermColor::Grey => AnsiColor::White,
+            CrosstermColor::Rgb { r, g, b } => {
+                return Color::Rgb(RgbColor {
+                    red: r,
+                    green: g,
+                    blue: b,
+                })
+            }
+            CrosstermColor::AnsiValue(code) => return Color::Xterm(XtermColor::from_code(code)),
+        })
+    }
+}
+
+/// [`Effect::SuperScript`] and [`Effect::SubScript`] don't have a crossterm attribute, so they are ignored
+impl From<EffectFlags> for Attributes {
+    #[inline]
+    fn from(effects: EffectFlags) -> Self {
+        ATTRIBUTES
+            .iter()
+            .filter(|&&(effect, _)| effects.is(effect))
+            .fold(Attributes::none(), |attributes, &(_, attribute)| {
+                attributes.with(attribute)
+            })
+    }
+}
+
+/// Attributes which don't have a colorz effect (like [`Attribute::Undercurled`]) are ignored
+impl From<Attributes> for EffectFlags {
+    #[inline]
+    fn from(attributes: Attributes) -> Self {
+        ATTRIBUTES
+            .iter()
+            .filter(|&&(_, attribute)| attributes.has(attribute))
+            .fold(EffectFlags::new(), |effects, &(effect, _)| {
+                effects.with(effect)
+            })
+    }
+}
+
+impl From<Style> for ContentStyle {
+    #[inline]
+    fn from(style: Style) -> Self {
+        ContentStyle {
+            foreground_color: style.foreground.map(Into::into),
+            background_color: style.background.map(Into::into),
+            underline_color: style.underline_color.map(Into::into),
+            attributes: style.effects.into(),
+        }
+    }
+}
+
+impl From<ContentStyle> for Style {
+    #[inline]
+    fn from(style: ContentStyle) -> Self {
+        Style {
+            foreground: style.foreground_color.map(Into::into),
+            background: style.background_color.map(Into::into),
+            underline_color: style.underline_color.map(Into::into),
+            effects: style.attributes.into(),
+        }
+    }
+}
@@ -3,14 +3,13 @@
 //! Reference: <https://www.w3schools.com/cssref/css_colors.asp>
 //! Reference: <https://developer.mozilla.org/en-US/docs/Web/CSS/color_value>
 
-use crate::rgb::Rgb;
-use crate::ColorSpec;
+use core::fmt::{self, Write};
 
-#[cfg(doc)]
-use crate::Color;
+use crate::rgb::Rgb;
+use crate::{ansi::AnsiColor, Color, ColorSpec, Effect, Style};
 
 macro_rules! Css {
-    ($($name:ident ($r:literal, $g:literal, $b:literal))*) => {
+    ($d:tt $($name:ident ($r:literal, $g:literal, $b:literal))*) => {
         /// A runtime Css color type. Not as widely supported as standard ANSI as it relies on 48-bit color support.
         ///
         /// This type can be converted to an [`RgbColor`](crate::rgb::RgbColor)
@@ -25,6 +24,26 @@ macro_rules! Css {
 
         const _: [(); core::mem::size_of::<CssColor>()] = [(); 1];
 
+        /// Convert a color name to the compile time CSS color type
+        ///
+        /// This is useful in macros that generate styles, the name must be written in `CamelCase`
+        ///
+        /// ```rust
+        /// use colorz::{css_color, css, Colorize};
+        ///
+        /// let blue: css_color!(DodgerBlue) = css_color!(DodgerBlue);
+        /// assert_eq!(css::CssColor::from(blue), css::CssColor::DodgerBlue);
+        ///
+        /// println!("{}", "hello".fg(css_color!(DodgerBlue)));
+        /// ```
+        #[macro_export]
+        macro_rules! css_color {
+            $(($name) => { $crate::css::$name };)*
+            ($d t:tt) => {{
+                compile_error! { concat!("Unknown CSS color name: ", stringify!($d t)) }
+            }};
+        }
+
         $(
             /// A compile time css color type
             #[doc = concat!(" representing the rgb color value (", stringify!($r), ", ", stringify!($g), ",", stringify!($b), ")")]
@@ -84,6 +103,76 @@ macro_rules! Css {
         )*
 
         impl CssColor {
+            /// The name of this color, in `CamelCase`
+            ///
+            /// ```rust
+            /// use colorz::css::CssColor;
+            ///
+            /// assert_eq!(CssColor::DodgerBlue.name(), "DodgerBlue");
+            /// ```
+            #[inline]
+            pub const fn name(self) -> &'static str {
+                const NAMES: &[&'static str; 147] = &[
+                    $(stringify!($name),)*
+                ];
+
+                NAMES[self as usize]
+            }
+
+            /// Look up a color by it's name, ignoring ascii case, spaces, hyphens, and underscores
+            ///
+            /// ```rust
+            /// use colorz::css::CssColor;
+            ///
+            /// assert_eq!(CssColor::from_name("dodger-blue"), Some(CssColor::DodgerBlue));
+            /// assert_eq!(CssColor::from_name("Light Sea Green"), Some(CssColor::LightSeaGreen));
+            /// assert_eq!(CssColor::from_name("not a color"), None);
+            /// ```
+            #[inline]
+            pub const fn from_name(name: &str) -> Option<Self> {
+                Self::from_name_bytes(name.as_bytes())
+            }
+
+            /// All CSS colors, in alphabetical order
+            const ALL: &'static [CssColor; 147] = &[
+                $(CssColor::$name,)*
+            ];
+
+            /// Iterate over every CSS color, in alphabetical order
+            ///
+            /// ```rust
+            /// use colorz::css::CssColor;
+            ///
+            /// assert_eq!(CssColor::variants().len(), 147);
+            /// assert_eq!(CssColor::variants().next(), Some(CssColor::AliceBlue));
+            /// ```
+            #[inline]
+            pub fn variants() -> core::iter::Copied<core::slice::Iter<'static, Self>> {
+                Self::ALL.iter().copied()
+            }
+
+            /// Look up a color by it's name, see [`CssColor::from_name`]
+            pub(crate) const fn from_name_bytes(name: &[u8]) -> Option<Self> {
+                let mut i = 0;
+                while i < Self::ALL.len() {
+                    if crate::from_str::eq_ignore_case_and_separators(name, Self::ALL[i].name().as_bytes()) {
+                        return Some(Self::ALL[i]);
+                    }
+                    i += 1;
+                }
+
+                None
+            }
+
+            /// The color with the given index, the inverse of `self as u8`
+            pub(crate) const fn from_index(index: u8) -> Option<Self> {
+                if (index as usize) < Self::ALL.len() {
+                    Some(Self::ALL[index as usize])
+                } else {
+                    None
+                }
+            }
+
             /// The ANSI color args
             #[inline]
             pub const fn args(self) -> &'static str {
@@ -93,6 +182,12 @@ macro_rules! Css {
             }
 
             /// The equivalent rgb color
+            ///
+            /// ```rust
+            /// use colorz::{css::CssColor, rgb::RgbColor};
+            ///
+            /// assert_eq!(CssColor::Tomato.rgb(), RgbColor { red: 255, green: 99, blue: 71 });
+            /// ```
             #[inline]
             pub const fn rgb(self) -> crate::rgb::RgbColor {
                 const RGB: &[crate::rgb::RgbColor; 147] = &[
@@ -167,42 +262,14 @@ macro_rules! Css {
         impl ColorSpec for CssColor {
             type Dynamic = Self;
 
-            const KIND: crate::mode::ColorKind = crate::mode::ColorKind::Rgb;
+            const KIND: crate::mode::ColorKind = crate::cap::kind(crate::mode::ColorKind::Rgb);
 
             #[inline]
             fn into_dynamic(self) -> Self::Dynamic {
                 self
             }
 
-            #[inline]
-            fn foreground_args(self) -> &'static str {
-                self.foreground_args()
-            }
-
-            #[inline]
-            fn background_args(self) -> &'static str {
-                self.background_args()
-            }
-
-            #[inline]
-            fn underline_args(self) -> &'static str {
-                self.underline_args()
-            }
-
-            #[inline]
-            fn foreground_escape(self) -> &'static str {
-                self.foreground_escape()
-            }
-
-            #[inline]
-            fn background_escape(self) -> &'static str {
-                self.background_escape()
-            }
-
-            #[inline]
-            fn underline_escape(self) -> &'static str {
-                self.underline_escape()
-            }
+            runtime_capped_methods!(self => crate::Color::Css(self));
         }
 
         $(
@@ -234,42 +301,14 @@ macro_rules! Css {
             impl ColorSpec for $name {
                 type Dynamic = CssColor;
 
-                const KIND: crate::mode::ColorKind = crate::mode::ColorKind::Rgb;
+                const KIND: crate::mode::ColorKind = crate::cap::kind(crate::mode::ColorKind::Rgb);
 
                 #[inline]
                 fn into_dynamic(self) -> Self::Dynamic {
                     Self::DYNAMIC
                 }
 
-                #[inline]
-                fn foreground_args(self) -> &'static str {
-                    Self::FOREGROUND_ARGS
-                }
-
-                #[inline]
-                fn background_args(self) -> &'static str {
-                    Self::BACKGROUND_ARGS
-                }
-
-                #[inline]
-                fn underline_args(self) -> &'static str {
-                    Self::UNDERLINE_ARGS
-                }
-
-                #[inline]
-                fn foreground_escape(self) -> &'static str {
-                    Self::FOREGROUND_ESCAPE
-                }
-
-                #[inline]
-                fn background_escape(self) -> &'static str {
-                    Self::BACKGROUND_ESCAPE
-                }
-
-                #[inline]
-                fn underline_escape(self) -> &'static str {
-                    Self::UNDERLINE_ESCAPE
-                }
+                comptime_capped_methods!(crate::Color::Css(Self::DYNAMIC));
             }
         )*
 
@@ -277,6 +316,7 @@ macro_rules! Css {
 }
 
 Css! {
+    $
     AliceBlue (240, 248, 255)
     AntiqueWhite (250, 235, 215)
     Aqua (0, 255, 255)
@@ -425,3 +465,142 @@ Css! {
     Yellow (255, 255, 0)
     YellowGreen (154, 205, 50)
 }
+
+fn write_color(f: &mut dyn Write, color: Color) -> fmt::Result {
+    let rgb = color.to_rgb();
+
+    write!(f, "rgb({}, {}, {})", rgb.red, rgb.green, rgb.blue)
+}
+
+/// Filter out the terminal's default color, since it's value isn't known
+pub(crate) fn is_default(color: Option<Color>) -> Option<Color> {
+    color.filter(|&color| !matches!(color, Color::Ansi(AnsiColor::Default) | Color::Default))
+}
+
+/// Write the CSS declarations for the style, like `color: #cd0000; font-weight: bold`
+pub(crate) fn write_declarations(f: &mut dyn Write, style: &Style) -> fmt::Result {
+    let mut first = true;
+    let mut declaration = |f: &mut dyn Write, name: &str| -> fmt::Result {
+        if !first {
+            f.write_str("; ")?;
+        }
+        first = false;
+        f.write_str(name)?;
+        f.write_str(": ")
+    };
+
+    let mut foreground = is_default(style.foreground);
+    let mut background = is_default(style.background);
+
+    if style.effects.is(Effect::Reversed) {
+        core::mem::swap(&mut foreground, &mut background);
+
+        // the default colors aren't known, so use the browser's default colors instead
+        declaration(f, "color")?;
+        match foreground {
+            Some(color) => write_color(f, color)?,
+            None => f.write_str("Canvas")?,
+        }
+
+        declaration(f, "background-color")?;
+        match background {
+            Some(color) => write_color(f, color)?,
+            None => f.write_str("CanvasText")?,
+        }
+    } else {
+        if let Some(color) = foreground {
+            declaration(f, "color")?;
+            write_color(f, color)?;
+        }
+
+        if let Some(color) = background {
+            declaration(f, "background-color")?;
+            write_color(f, color)?;
+        }
+    }
+
+    if style.effects.is(Effect::Bold) {
+        declaration(f, "font-weight")?;
+        f.write_str("bold")?;
+    }
+
+    if style.effects.is(Effect::Dimmed) {
+        declaration(f, "opacity")?;
+        f.write_str("0.5")?;
+    }
+
+    if style.effects.is(Effect::Italic) {
+        declaration(f, "font-style")?;
+        f.write_str("italic")?;
+    }
+
+    const LINES: [(Effect, &str); 5] = [
+        (Effect::Underline, "underline"),
+        (Effect::DoubleUnderline, "underline"),
+        (Effect::Overline, "overline"),
+        (Effect::Strikethrough, "line-through"),
+        (Effect::Blink, "blink"),
+    ];
+
+    let mut lines = LINES
+        .iter()
+        .filter(|(effect, _)| style.effects.is(*effect))
+        .map(|&(_, line)| line)
+        .peekable();
+
+    if lines.peek().is_some() {
+        declaration(f, "text-decoration-line")?;
+        let mut prev = "";
+        for (i, line) in lines.enumerate() {
+            // underline and double underline both use `underline`
+            if line == prev {
+                continue;
+            }
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(line)?;
+            prev = line;
+        }
+
+        if style.effects.is(Effect::DoubleUnderline) {
+            declaration(f, "text-decoration-style")?;
+            f.write_str("double")?;
+        }
+
+        if let Some(color) = is_default(style.underline_color) {
+            if style.effects.is(Effect::Underline) || style.effects.is(Effect::DoubleUnderline) {
+                declaration(f, "text-decoration-color")?;
+                write_color(f, color)?;
+            }
+        }
+    }
+
+    if style.effects.is(Effect::Hidden) {
+        declaration(f, "visibility")?;
+        f.write_str("hidden")?;
+    }
+
+    if style.effects.is(Effect::SuperScript) || style.effects.is(Effect::SubScript) {
+        declaration(f, "vertical-align")?;
+        f.write_str(if style.effects.is(Effect::SuperScript) {
+            "super"
+        } else {
+            "sub"
+        })?;
+        declaration(f, "font-size")?;
+        f.write_str("smaller")?;
+    }
+
+    if style.effects.is(Effect::Framed) || style.effects.is(Effect::Encircled) {
+        declaration(f, "border")?;
+        f.write_str("1px solid")?;
+
+        if style.effects.is(Effect::Encircled) {
+            declaration(f, "border-radius")?;
+            f.write_str("50%")?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! [`defmt`](::defmt) support, so colors, styles and styled values can be logged from firmware
+//!
+//! Colors, effects and styles are written by name, and [`StyledValue`]s are written with their
+//! escape sequences around the value (following the [coloring mode](crate::mode)), so RTT viewers
+//! which understand ANSI escapes show the styled text.
+
+use ::defmt::{Display2Format, Format, Formatter};
+
+use crate::{
+    ansi::AnsiColor, css::CssColor, render::Render, rgb::RgbColor, xterm::XtermColor, Color,
+    Effect, EffectFlags, OptionalColor, Style, StyledValue,
+};
+
+impl Format for AnsiColor {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "{=str}", self.name())
+    }
+}
+
+impl Format for XtermColor {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "Xterm({=u8})", *self as u8)
+    }
+}
+
+impl Format for CssColor {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "{=str}", self.name())
+    }
+}
+
+impl Format for RgbColor {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(
+            f,
+            "#{=u8:02x}{=u8:02x}{=u8:02x}",
+            self.red,
+            self.green,
+            self.blue
+        )
+    }
+}
+
+impl Format for Color {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        match self {
+            Color::Ansi(color) => color.format(f),
+            Color::Xterm(color) => color.format(f),
+            Color::Css(color) => color.format(f),
+            Color::Rgb(color) => color.format(f),
+            Color::Default => ::defmt::write!(f, "default"),
+        }
+    }
+}
+
+impl Format for Effect {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "{=str}", self.name())
+    }
+}
+
+impl Format for EffectFlags {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(f, "{{");
+        for (i, effect) in self.iter().enumerate() {
+            if i != 0 {
+                ::defmt::write!(f, ", ");
+            }
+            effect.format(f);
+        }
+        ::defmt::write!(f, "}}");
+    }
+}
+
+impl Format for Style {
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        ::defmt::write!(
+            f,
+            "Style {{ foreground: {}, background: {}, underline_color: {}, effects: {} }}",
+            self.foreground,
+            self.background,
+            self.underline_color,
+            self.effects
+        )
+    }
+}
+
+/// Writes the value with the style's escape sequences around it, if the value should be colored
+impl<T: Format, F: OptionalColor, B: OptionalColor, U: OptionalColor> Format
+    for StyledValue<T, F, B, U>
+{
+    #[inline]
+    fn format(&self, f: Formatter<'_>) {
+        let (render, style) = match self.resolve_theme() {
+            Some(themed) => (
+                crate::render::resolve(&themed.style, self.stream, self.options.max_color),
+                themed.style,
+            ),
+            None => (
+                crate::render::resolve(&self.style, self.stream, self.options.max_color),
+                self.style.to_runtime(),
+            ),
+        };
+
+        let style = match render {
+            Render::Plain => return self.value.format(f),
+            Render::AsIs => style,
+            Render::Runtime(style) => style,
+        };
+
+        ::defmt::write!(
+            f,
+            "{}{}{}",
+            Display2Format(&style.apply()),
+            self.value,
+            Display2Format(&style.clear())
+        )
+    }
+}
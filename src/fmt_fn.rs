@@ -0,0 +1,47 @@
+//! Formatting with a closure
+
+use core::fmt;
+
+/// A value which is formatted by calling a closure, created from [`fmt_fn`]
+#[derive(Clone, Copy)]
+pub struct FmtFn<F> {
+    f: F,
+}
+
+/// Create a value which is formatted by calling `f`, so ad-hoc formatting logic can be styled
+/// with [`Colorize`](crate::Colorize) without defining a new type
+///
+/// `f` is called each time the value is formatted, and the formatter's options (like the width)
+/// are passed on to `f` as is.
+///
+/// ```rust
+/// use colorz::{fmt_fn, Colorize, mode::Stream};
+///
+/// let secs = 125;
+/// let duration = fmt_fn(|f| write!(f, "{}m {:02}s", secs / 60, secs % 60));
+///
+/// println!("took {}", duration.green());
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(duration.green().stream(Stream::AlwaysColor).to_string(), "\x1b[32m2m 05s\x1b[39m");
+/// # }
+/// assert_eq!(duration.to_string(), "2m 05s");
+/// ```
+#[inline]
+pub const fn fmt_fn<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result>(f: F) -> FmtFn<F> {
+    FmtFn { f }
+}
+
+impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> fmt::Display for FmtFn<F> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.f)(f)
+    }
+}
+
+impl<F: Fn(&mut fmt::Formatter<'_>) -> fmt::Result> fmt::Debug for FmtFn<F> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.f)(f)
+    }
+}
@@ -1,6 +1,9 @@
-use core::str::FromStr;
+use core::{fmt, str::FromStr};
 
-use crate::{ansi::AnsiColor, Color};
+use crate::{
+    ansi::AnsiColor, css::CssColor, rgb::RgbColor, xterm::XtermColor, Color, Effect, EffectFlags,
+    Style,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// An error type for parsing colors
@@ -11,6 +14,480 @@ pub enum ParseColorError {
     U8Overflow,
     /// An unknown color format
     UnknownColor,
+    /// The arguments to `rgb(...)` or `hsl(...)` were invalid
+    InvalidArguments,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseColorError {}
+
+impl ParseColorError {
+    /// The error message, the same as the [`Display`](fmt::Display) impl
+    ///
+    /// ```
+    /// use colorz::ParseColorError;
+    ///
+    /// assert_eq!(ParseColorError::InvalidHexDigit.as_str(), "Invalid color: invalid hex digit");
+    /// ```
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ParseColorError::InvalidHexDigit => "Invalid color: invalid hex digit",
+            ParseColorError::U8Overflow => "Invalid color: color code must be in the range 0..=255",
+            ParseColorError::UnknownColor => "Invalid color: unknown color name or format",
+            ParseColorError::InvalidArguments => {
+                "Invalid color: expected `rgb(red, green, blue)` or `hsl(hue, saturation%, lightness%)`"
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseColorError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An error type for parsing effects
+pub struct ParseEffectError;
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseEffectError {}
+
+impl fmt::Display for ParseEffectError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Invalid effect: unknown effect name")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// An error type for parsing styles
+pub enum ParseStyleError {
+    /// A color could not be parsed
+    InvalidColor(ParseColorError),
+    /// `on` or `underline:` wasn't followed by a color
+    MissingColor,
+    /// A word that isn't a color, effect, or keyword
+    UnknownWord,
+    /// The same color (foreground, background, or underline) was specified more than once
+    DuplicateColor,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseStyleError {}
+
+impl ParseStyleError {
+    /// The error message, the same as the [`Display`](fmt::Display) impl
+    ///
+    /// ```
+    /// use colorz::ParseStyleError;
+    ///
+    /// assert_eq!(
+    ///     ParseStyleError::MissingColor.as_str(),
+    ///     "Invalid style: expected a color after `on` or `underline:`"
+    /// );
+    /// ```
+    #[inline]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ParseStyleError::InvalidColor(err) => err.as_str(),
+            ParseStyleError::MissingColor => {
+                "Invalid style: expected a color after `on` or `underline:`"
+            }
+            ParseStyleError::UnknownWord => {
+                "Invalid style: expected a color, an effect, `on <color>` or `underline:<color>`"
+            }
+            ParseStyleError::DuplicateColor => {
+                "Invalid style: the same color was specified more than once"
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseStyleError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<ParseColorError> for ParseStyleError {
+    #[inline]
+    fn from(err: ParseColorError) -> Self {
+        Self::InvalidColor(err)
+    }
+}
+
+/// Return early with the error, like `?` but usable in const fns
+macro_rules! tri {
+    ($result:expr) => {
+        match $result {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        }
+    };
+}
+
+/// Compare two byte strings, ignoring ascii case
+const fn eq_ignore_ascii_case(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if !a[i].eq_ignore_ascii_case(&b[i]) {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Compare `input` against a `name` which doesn't contain any separators,
+/// ignoring ascii case and any `-`, `_` or ` ` in the input
+pub(crate) const fn eq_ignore_case_and_separators(input: &[u8], name: &[u8]) -> bool {
+    let mut i = 0;
+    let mut j = 0;
+
+    loop {
+        while i < input.len() && matches!(input[i], b'-' | b'_' | b' ') {
+            i += 1;
+        }
+
+        match (i < input.len(), j < name.len()) {
+            (true, true) if input[i].eq_ignore_ascii_case(&name[j]) => {
+                i += 1;
+                j += 1;
+            }
+            (false, false) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Is `b` ascii whitespace, or a comma if `commas` is true
+const fn is_separator(b: u8, commas: bool) -> bool {
+    b.is_ascii_whitespace() || (commas && b == b',')
+}
+
+/// Split off the first run of bytes from `s` which aren't separators (see [`is_separator`]),
+/// after skipping any leading separators. Returns `None` if there are only separators left
+///
+/// Separators inside of parentheses don't end the word, so `rgb(1, 2, 3)` is a single word
+const fn split_word(mut s: &[u8], commas: bool) -> Option<(&[u8], &[u8])> {
+    while let [first, rest @ ..] = s {
+        if !is_separator(*first, commas) {
+            break;
+        }
+        s = rest;
+    }
+
+    if s.is_empty() {
+        return None;
+    }
+
+    let mut len = 0;
+    let mut depth = 0_usize;
+    while len < s.len() && (depth != 0 || !is_separator(s[len], commas)) {
+        match s[len] {
+            b'(' => depth += 1,
+            b')' if depth != 0 => depth -= 1,
+            _ => {}
+        }
+        len += 1;
+    }
+
+    Some(s.split_at(len))
+}
+
+/// The words of a style, separated by ascii whitespace outside of parentheses
+struct Words<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Words<'a> {
+    const fn next(&mut self) -> Option<&'a [u8]> {
+        match split_word(self.rest, false) {
+            Some((word, rest)) => {
+                self.rest = rest;
+                Some(word)
+            }
+            None => None,
+        }
+    }
+}
+
+pub(crate) const fn parse_effect(s: &[u8]) -> Option<Effect> {
+    const NAMES: &[(&str, Effect)] = &[
+        ("bold", Effect::Bold),
+        ("dim", Effect::Dimmed),
+        ("dimmed", Effect::Dimmed),
+        ("italic", Effect::Italic),
+        ("italics", Effect::Italic),
+        ("underline", Effect::Underline),
+        ("underlined", Effect::Underline),
+        ("doubleunderline", Effect::DoubleUnderline),
+        ("blink", Effect::Blink),
+        ("blinkfast", Effect::BlinkFast),
+        ("reverse", Effect::Reversed),
+        ("reversed", Effect::Reversed),
+        ("hide", Effect::Hidden),
+        ("hidden", Effect::Hidden),
+        ("strikethrough", Effect::Strikethrough),
+        ("overline", Effect::Overline),
+        ("superscript", Effect::SuperScript),
+        ("subscript", Effect::SubScript),
+        ("framed", Effect::Framed),
+        ("encircled", Effect::Encircled),
+    ];
+
+    let mut i = 0;
+    while i < NAMES.len() {
+        let (name, effect) = NAMES[i];
+        if eq_ignore_case_and_separators(s, name.as_bytes()) {
+            return Some(effect);
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Parse an effect from it's name, ignoring case and any `-`, `_` or ` `
+///
+/// This accepts the names from [`Effect::name`] and a few common aliases (like `italic` or `reversed`)
+///
+/// ```
+/// use colorz::Effect;
+///
+/// assert_eq!("bold".parse(), Ok(Effect::Bold));
+/// assert_eq!("Double-Underline".parse(), Ok(Effect::DoubleUnderline));
+/// assert_eq!("italic".parse(), Ok(Effect::Italic));
+///
+/// for effect in colorz::EffectFlags::all() {
+///     assert_eq!(effect.to_string().parse(), Ok(effect));
+/// }
+/// ```
+impl FromStr for Effect {
+    type Err = ParseEffectError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_effect(s.as_bytes()).ok_or(ParseEffectError)
+    }
+}
+
+/// Parse a CSS color from it's name, see [`CssColor::from_name`]
+///
+/// ```
+/// use colorz::{css::CssColor, ParseColorError};
+///
+/// assert_eq!("rebeccapurple".parse(), Ok(CssColor::RebeccaPurple));
+/// assert_eq!("DarkSlateGray".parse(), Ok(CssColor::DarkSlateGray));
+/// assert_eq!("#ff0000".parse::<CssColor>(), Err(ParseColorError::UnknownColor));
+/// ```
+impl FromStr for CssColor {
+    type Err = ParseColorError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CssColor::from_name(s).ok_or(ParseColorError::UnknownColor)
+    }
+}
+
+/// Parse a comma separated list of effects, see [`Effect`]'s `FromStr` impl for the supported names
+///
+/// ```
+/// use colorz::{Effect, EffectFlags};
+///
+/// let effects: EffectFlags = "bold, underline".parse().unwrap();
+/// assert_eq!(effects, EffectFlags::from_array([Effect::Bold, Effect::Underline]));
+/// assert_eq!(effects.to_string(), "bold,underline");
+///
+/// assert_eq!("".parse(), Ok(EffectFlags::new()));
+/// ```
+impl FromStr for EffectFlags {
+    type Err = ParseEffectError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Ok(EffectFlags::new());
+        }
+
+        s.split(',')
+            .map(|effect| effect.trim().parse::<Effect>())
+            .collect()
+    }
+}
+
+/// Parse the name of an ansi color ignoring ascii case, the bright colors are named without
+/// their `bright` prefix
+pub(crate) const fn parse_ansi_name(s: &[u8], bright: bool) -> Option<AnsiColor> {
+    const NAMES: &[(&str, AnsiColor, AnsiColor)] = &[
+        ("black", AnsiColor::Black, AnsiColor::BrightBlack),
+        ("red", AnsiColor::Red, AnsiColor::BrightRed),
+        ("green", AnsiColor::Green, AnsiColor::BrightGreen),
+        ("yellow", AnsiColor::Yellow, AnsiColor::BrightYellow),
+        ("blue", AnsiColor::Blue, AnsiColor::BrightBlue),
+        ("magenta", AnsiColor::Magenta, AnsiColor::BrightMagenta),
+        ("cyan", AnsiColor::Cyan, AnsiColor::BrightCyan),
+        ("white", AnsiColor::White, AnsiColor::BrightWhite),
+    ];
+
+    if !bright && eq_ignore_ascii_case(s, b"purple") {
+        return Some(AnsiColor::Magenta);
+    }
+
+    let mut i = 0;
+    while i < NAMES.len() {
+        let (name, color, bright_color) = NAMES[i];
+        if eq_ignore_ascii_case(s, name.as_bytes()) {
+            return Some(if bright { bright_color } else { color });
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Parse a color which may be written as two words (`bright red`)
+const fn parse_color_words(first: &[u8], words: &mut Words<'_>) -> Result<Color, ParseColorError> {
+    if !eq_ignore_ascii_case(first, b"bright") {
+        return parse_color(first);
+    }
+
+    let second = match words.next() {
+        Some(second) => second,
+        None => return Err(ParseColorError::UnknownColor),
+    };
+
+    match parse_ansi_name(second, true) {
+        Some(color) => Ok(Color::Ansi(color)),
+        None => Err(ParseColorError::UnknownColor),
+    }
+}
+
+const fn set_color(slot: &mut Option<Color>, color: Color) -> Result<(), ParseStyleError> {
+    match slot.replace(color) {
+        Some(_) => Err(ParseStyleError::DuplicateColor),
+        None => Ok(()),
+    }
+}
+
+const fn parse_style(s: &[u8]) -> Result<Style, ParseStyleError> {
+    const UNDERLINE: &[u8] = b"underline:";
+
+    let mut style = Style::new().const_into_runtime_style();
+    let mut words = Words { rest: s };
+
+    while let Some(word) = words.next() {
+        if eq_ignore_ascii_case(word, b"on") {
+            let color = match words.next() {
+                Some(color) => color,
+                None => return Err(ParseStyleError::MissingColor),
+            };
+
+            let color = match parse_color_words(color, &mut words) {
+                Ok(color) => color,
+                Err(err) => return Err(ParseStyleError::InvalidColor(err)),
+            };
+
+            tri!(set_color(&mut style.background, color));
+        } else if word.len() >= UNDERLINE.len()
+            && eq_ignore_ascii_case(word.split_at(UNDERLINE.len()).0, UNDERLINE)
+        {
+            let color = word.split_at(UNDERLINE.len()).1;
+
+            if color.is_empty() {
+                return Err(ParseStyleError::MissingColor);
+            }
+
+            let color = match parse_color_words(color, &mut words) {
+                Ok(color) => color,
+                Err(err) => return Err(ParseStyleError::InvalidColor(err)),
+            };
+
+            tri!(set_color(&mut style.underline_color, color));
+        } else if let Some(effect) = parse_effect(word) {
+            style.effects.set(effect);
+        } else {
+            let color = match parse_color_words(word, &mut words) {
+                Ok(color) => color,
+                Err(ParseColorError::UnknownColor) => return Err(ParseStyleError::UnknownWord),
+                Err(err) => return Err(ParseStyleError::InvalidColor(err)),
+            };
+
+            tri!(set_color(&mut style.foreground, color));
+        }
+    }
+
+    if style.underline_color.is_some() && !style.effects.is(Effect::DoubleUnderline) {
+        style.effects.set(Effect::Underline);
+    }
+
+    Ok(style)
+}
+
+impl Style {
+    /// Parse a runtime style from a space separated list of words, in a const context
+    ///
+    /// This accepts the same format as [`Style`]'s `FromStr` impl.
+    /// See [`style!`](crate::style!) to parse a style at compile time.
+    ///
+    /// ```
+    /// use colorz::{Style, ansi, ParseStyleError};
+    ///
+    /// const STYLE: Style = match Style::parse("bold red") {
+    ///     Ok(style) => style,
+    ///     Err(_) => panic!("invalid style"),
+    /// };
+    ///
+    /// assert_eq!(STYLE, Style::new().fg(ansi::Red).bold().into_runtime_style());
+    /// assert_eq!(Style::parse("red blue"), Err(ParseStyleError::DuplicateColor));
+    /// ```
+    #[inline]
+    pub const fn parse(s: &str) -> Result<Self, ParseStyleError> {
+        parse_style(s.as_bytes())
+    }
+}
+
+/// Parse a runtime style from a space separated list of words
+///
+/// * `<color>` sets the foreground color, see [`Color`] for the supported color formats,
+///   the arguments of color functions like `rgb(1, 2, 3)` may be separated by spaces
+/// * `on <color>` sets the background color
+/// * `underline:<color>` sets the underline color, and underlines the text
+/// * any [`Effect`] name, like `bold` or `italic`
+///
+/// ```
+/// use colorz::{Style, ansi, xterm, Color};
+///
+/// let style: Style = "bold red on bright blue underline:#ff".parse().unwrap();
+///
+/// assert_eq!(
+///     style,
+///     Style::new()
+///         .fg(ansi::Red)
+///         .bg(ansi::BrightBlue)
+///         .underline_color(Color::Xterm(xterm::XtermColor::from_code(0xff)))
+///         .bold()
+///         .underline()
+///         .into_runtime_style()
+/// );
+/// ```
+impl FromStr for Style {
+    type Err = ParseStyleError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Style::parse(s)
+    }
 }
 
 #[inline(always)]
@@ -27,53 +504,262 @@ const fn merge(a: u8, b: u8) -> u8 {
     a << 4 | b
 }
 
+/// Parse a decimal number, with an optional leading `+`
+const fn parse_u16(s: &[u8]) -> Option<u16> {
+    let digits = match s {
+        [b'+', digits @ ..] => digits,
+        _ => s,
+    };
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        if !digits[i].is_ascii_digit() {
+            return None;
+        }
+
+        value = match value.checked_mul(10) {
+            Some(value) => value,
+            None => return None,
+        };
+        value = match value.checked_add((digits[i] - b'0') as u16) {
+            Some(value) => value,
+            None => return None,
+        };
+        i += 1;
+    }
+
+    Some(value)
+}
+
+/// Parse the arguments to a function like `rgb(...)` and `hsl(...)`
+///
+/// The arguments may be separated by commas, whitespace or both.
+/// Returns `None` if `s` isn't a call to the function `name`
+const fn parse_function_args<'a>(
+    s: &'a [u8],
+    name: &[u8],
+) -> Option<Result<[&'a [u8]; 3], ParseColorError>> {
+    if s.len() < name.len() {
+        return None;
+    }
+
+    let (prefix, rest) = s.split_at(name.len());
+
+    if !eq_ignore_ascii_case(prefix, name) {
+        return None;
+    }
+
+    let mut rest = match rest.trim_ascii_start() {
+        [b'(', args @ .., b')'] => args,
+        _ => return None,
+    };
+
+    let mut args: [&[u8]; 3] = [&[]; 3];
+    let mut count = 0;
+
+    while let Some((arg, tail)) = split_word(rest, true) {
+        if count == args.len() {
+            return Some(Err(ParseColorError::InvalidArguments));
+        }
+
+        args[count] = arg;
+        count += 1;
+        rest = tail;
+    }
+
+    Some(if count == args.len() {
+        Ok(args)
+    } else {
+        Err(ParseColorError::InvalidArguments)
+    })
+}
+
+const fn parse_rgb_function(s: &[u8]) -> Option<Result<Color, ParseColorError>> {
+    const fn component(arg: &[u8]) -> Result<u8, ParseColorError> {
+        match parse_u16(arg) {
+            Some(value @ 0..=255) => Ok(value as u8),
+            Some(_) => Err(ParseColorError::U8Overflow),
+            None => Err(ParseColorError::InvalidArguments),
+        }
+    }
+
+    let [red, green, blue] = match parse_function_args(s, b"rgb") {
+        Some(Ok(args)) => args,
+        Some(Err(err)) => return Some(Err(err)),
+        None => return None,
+    };
+
+    Some(match (component(red), component(green), component(blue)) {
+        (Ok(red), Ok(green), Ok(blue)) => Ok(Color::Rgb(RgbColor { red, green, blue })),
+        (Err(err), _, _) | (_, Err(err), _) | (_, _, Err(err)) => Err(err),
+    })
+}
+
+const fn parse_hsl_function(s: &[u8]) -> Option<Result<Color, ParseColorError>> {
+    const fn percent(arg: &[u8]) -> Result<u8, ParseColorError> {
+        let arg = match arg {
+            [arg @ .., b'%'] => arg,
+            _ => arg,
+        };
+
+        match parse_u16(arg) {
+            Some(value @ 0..=100) => Ok(value as u8),
+            _ => Err(ParseColorError::InvalidArguments),
+        }
+    }
+
+    let [hue, saturation, lightness] = match parse_function_args(s, b"hsl") {
+        Some(Ok(args)) => args,
+        Some(Err(err)) => return Some(Err(err)),
+        None => return None,
+    };
+
+    let hue = match hue {
+        [hue @ .., b'd', b'e', b'g'] => hue,
+        _ => hue,
+    };
+
+    let hue = match parse_u16(hue) {
+        Some(hue) => hue,
+        None => return Some(Err(ParseColorError::InvalidArguments)),
+    };
+
+    Some(match (percent(saturation), percent(lightness)) {
+        (Ok(saturation), Ok(lightness)) => {
+            Ok(Color::Rgb(RgbColor::from_hsl(hue, saturation, lightness)))
+        }
+        (Err(err), _) | (_, Err(err)) => Err(err),
+    })
+}
+
+/// Parse a `#rrggbb` or `#rgb` color, the `#` is optional
+pub(crate) const fn parse_hex_rgb(s: &[u8]) -> Result<RgbColor, ParseColorError> {
+    let s = match s {
+        [b'#', s @ ..] => s,
+        _ => s,
+    };
+
+    Ok(match *s {
+        [a, b, c, d, e, f] => {
+            let a = tri!(parse_hex_digit(a));
+            let b = tri!(parse_hex_digit(b));
+            let c = tri!(parse_hex_digit(c));
+            let d = tri!(parse_hex_digit(d));
+            let e = tri!(parse_hex_digit(e));
+            let f = tri!(parse_hex_digit(f));
+
+            RgbColor {
+                red: merge(a, b),
+                green: merge(c, d),
+                blue: merge(e, f),
+            }
+        }
+        [r, g, b] => {
+            let r = tri!(parse_hex_digit(r));
+            let g = tri!(parse_hex_digit(g));
+            let b = tri!(parse_hex_digit(b));
+
+            RgbColor {
+                red: merge(r, r),
+                green: merge(g, g),
+                blue: merge(b, b),
+            }
+        }
+        _ => return Err(ParseColorError::UnknownColor),
+    })
+}
+
+const fn parse_color(s: &[u8]) -> Result<Color, ParseColorError> {
+    Ok(match *s {
+        [b'#', _, _, _, _, _, _] | [b'#', _, _, _] => Color::Rgb(tri!(parse_hex_rgb(s))),
+        [a @ b'0'..=b'9'] => Color::Xterm(XtermColor::from_code(a - b'0')),
+        [a @ b'0'..=b'9', b @ b'0'..=b'9'] => {
+            Color::Xterm(XtermColor::from_code((a - b'0') * 10 + (b - b'0')))
+        }
+        [a @ b'0'..=b'1', b @ b'0'..=b'9', c @ b'0'..=b'9']
+        | [a @ b'2', b @ b'0'..=b'4', c @ b'0'..=b'9']
+        | [a @ b'2', b @ b'5', c @ b'0'..=b'5'] => Color::Xterm(XtermColor::from_code(
+            (a - b'0') * 100 + (b - b'0') * 10 + (c - b'0'),
+        )),
+        [b'0'..=b'9', b'0'..=b'9', b'0'..=b'9'] => return Err(ParseColorError::U8Overflow),
+        [b'#', a] => Color::Xterm(XtermColor::from_code(tri!(parse_hex_digit(a)))),
+        [b'#', a, b] => Color::Xterm(XtermColor::from_code(merge(
+            tri!(parse_hex_digit(a)),
+            tri!(parse_hex_digit(b)),
+        ))),
+        _ => {
+            const BRIGHT: &[u8] = b"bright ";
+            const CSS: &[u8] = b"css:";
+
+            if s.len() > CSS.len() && eq_ignore_ascii_case(s.split_at(CSS.len()).0, CSS) {
+                return match CssColor::from_name_bytes(s.split_at(CSS.len()).1) {
+                    Some(color) => Ok(Color::Css(color)),
+                    None => Err(ParseColorError::UnknownColor),
+                };
+            }
+
+            if let Some(color) = parse_ansi_name(s, false) {
+                return Ok(Color::Ansi(color));
+            }
+
+            if s.len() > BRIGHT.len() && eq_ignore_ascii_case(s.split_at(BRIGHT.len()).0, BRIGHT) {
+                if let Some(color) = parse_ansi_name(s.split_at(BRIGHT.len()).1, true) {
+                    return Ok(Color::Ansi(color));
+                }
+            }
+
+            if eq_ignore_ascii_case(s, b"default") {
+                return Ok(Color::Default);
+            }
+
+            if let Some(color) = parse_rgb_function(s) {
+                return color;
+            }
+
+            if let Some(color) = parse_hsl_function(s) {
+                return color;
+            }
+
+            match CssColor::from_name_bytes(s) {
+                Some(color) => Color::Css(color),
+                None => return Err(ParseColorError::UnknownColor),
+            }
+        }
+    })
+}
+
+impl Color {
+    /// Parse a color in a const context
+    ///
+    /// This accepts the same formats as [`Color`]'s `FromStr` impl
+    ///
+    /// ```
+    /// use colorz::{Color, rgb::RgbColor, ParseColorError};
+    ///
+    /// const DODGER_BLUE: Color = match Color::parse("#1e90ff") {
+    ///     Ok(color) => color,
+    ///     Err(_) => panic!("invalid color"),
+    /// };
+    ///
+    /// assert_eq!(DODGER_BLUE, Color::Rgb(RgbColor { red: 30, green: 144, blue: 255 }));
+    /// assert_eq!(Color::parse("#1e90fg"), Err(ParseColorError::InvalidHexDigit));
+    /// ```
+    #[inline]
+    pub const fn parse(s: &str) -> Result<Self, ParseColorError> {
+        parse_color(s.as_bytes())
+    }
+}
+
 impl FromStr for Color {
     type Err = ParseColorError;
 
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s.as_bytes() {
-            &[b'#', a, b, c, d, e, f] => {
-                let a = parse_hex_digit(a)?;
-                let b = parse_hex_digit(b)?;
-                let c = parse_hex_digit(c)?;
-                let d = parse_hex_digit(d)?;
-                let e = parse_hex_digit(e)?;
-                let f = parse_hex_digit(f)?;
-
-                Self::Rgb(crate::rgb::RgbColor {
-                    red: merge(a, b),
-                    green: merge(c, d),
-                    blue: merge(e, f),
-                })
-            }
-            &[a @ b'0'..=b'9'] => Self::Xterm((a - b'0').into()),
-            &[a @ b'0'..=b'9', b @ b'0'..=b'9'] => Self::Xterm(((a - b'0') * 10 + b).into()),
-            &[a @ b'0'..=b'1', b @ b'0'..=b'9', c @ b'0'..=b'9']
-            | &[a @ b'2', b @ b'0'..=b'4', c @ b'0'..=b'9']
-            | &[a @ b'2', b @ b'5', c @ b'0'..=b'5'] => {
-                Self::Xterm(((a - b'0') * 100 + (b - b'0') * 10 + (c - b'0')).into())
-            }
-            &[b'0'..=b'9', b'0'..=b'9', b'0'..=b'9'] => return Err(ParseColorError::U8Overflow),
-            &[b'#', a] => Self::Xterm(parse_hex_digit(a)?.into()),
-            &[b'#', a, b] => Self::Xterm(merge(parse_hex_digit(a)?, parse_hex_digit(b)?).into()),
-            b"black" => Self::Ansi(AnsiColor::Black),
-            b"red" => Self::Ansi(AnsiColor::Red),
-            b"green" => Self::Ansi(AnsiColor::Green),
-            b"yellow" => Self::Ansi(AnsiColor::Yellow),
-            b"blue" => Self::Ansi(AnsiColor::Blue),
-            b"magenta" | b"purple" => Self::Ansi(AnsiColor::Magenta),
-            b"cyan" => Self::Ansi(AnsiColor::Cyan),
-            b"white" => Self::Ansi(AnsiColor::White),
-            b"bright black" => Self::Ansi(AnsiColor::BrightBlack),
-            b"bright red" => Self::Ansi(AnsiColor::BrightRed),
-            b"bright green" => Self::Ansi(AnsiColor::BrightGreen),
-            b"bright yellow" => Self::Ansi(AnsiColor::BrightYellow),
-            b"bright blue" => Self::Ansi(AnsiColor::BrightBlue),
-            b"bright magenta" => Self::Ansi(AnsiColor::BrightMagenta),
-            b"bright cyan" => Self::Ansi(AnsiColor::BrightCyan),
-            b"bright white" => Self::Ansi(AnsiColor::BrightWhite),
-            _ => return Err(ParseColorError::UnknownColor),
-        })
+        Color::parse(s)
     }
 }
@@ -0,0 +1,132 @@
+//! Text which is colored character by character, interpolating between multiple colors
+//!
+//! ```rust
+//! use colorz::{gradient::Gradient, mode::Stream, rgb::RgbColor};
+//!
+//! let red = RgbColor { red: 255, green: 0, blue: 0 };
+//! let blue = RgbColor { red: 0, green: 0, blue: 255 };
+//!
+//! println!("{}", Gradient::new("Hello, world!", &[red, blue]));
+//!
+//! # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+//! assert_eq!(
+//!     Gradient::new("abc", &[red, blue]).stream(Stream::AlwaysColor).to_string(),
+//!     "\x1b[38;2;255;0;0ma\x1b[38;2;128;0;128mb\x1b[38;2;0;0;255mc\x1b[39m",
+//! );
+//! # }
+//! assert_eq!(Gradient::new("abc", &[red, blue]).stream(Stream::NeverColor).to_string(), "abc");
+//! ```
+
+use core::fmt;
+
+use crate::{
+    mode::{ColorKind, Stream},
+    render::Render,
+    rgb::RgbColor,
+    Color, Style, WriteColor,
+};
+
+/// A [`Display`](fmt::Display) adapter which colors each character of some text,
+/// interpolating between the given colors, see the [module docs](self)
+///
+/// The colors are evenly spaced along the text, so the first character has the first color
+/// and the last character has the last color. Each color is only written if it's different from
+/// the previous character's color, and whitespace doesn't get a color at all.
+///
+/// This follows the coloring mode like [`StyledValue`](crate::StyledValue), including the
+/// [`auto-downgrade`](crate::mode::set_auto_downgrade) setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gradient<'a> {
+    text: &'a str,
+    colors: &'a [RgbColor],
+    stream: Option<Stream>,
+}
+
+impl<'a> Gradient<'a> {
+    /// Color the text with a gradient between the `colors`
+    ///
+    /// If there are no colors, then the text is written as is
+    #[inline]
+    pub const fn new(text: &'a str, colors: &'a [RgbColor]) -> Self {
+        Self {
+            text,
+            colors,
+            stream: None,
+        }
+    }
+
+    /// Sets the stream for the gradient
+    #[inline]
+    pub const fn stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Sets the stream for the gradient
+    #[inline]
+    pub const fn stream_opt(mut self, stream: Option<Stream>) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// The color at `t`, where `0.0` is the first color and `1.0` is the last color
+    fn color_at(&self, t: f32) -> Option<RgbColor> {
+        let (&last, rest) = self.colors.split_last()?;
+
+        if rest.is_empty() {
+            return Some(last);
+        }
+
+        let position = t * rest.len() as f32;
+        let index = (position as usize).min(rest.len() - 1);
+        let next = self.colors[index + 1];
+
+        Some(self.colors[index].lerp(next, position - index as f32))
+    }
+}
+
+impl fmt::Display for Gradient<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.text.chars().count();
+        let scale = 1.0 / count.saturating_sub(1).max(1) as f32;
+
+        let mut current = None;
+        let mut start = 0;
+
+        for (i, (offset, c)) in self.text.char_indices().enumerate() {
+            if c.is_whitespace() {
+                continue;
+            }
+
+            let Some(color) = self.color_at(i as f32 * scale) else {
+                break;
+            };
+
+            let style = Style::new().fg(Color::Rgb(color));
+            let color = match crate::render::resolve(&style, self.stream, ColorKind::Rgb) {
+                Render::Plain => break,
+                Render::AsIs => Color::Rgb(color),
+                Render::Runtime(style) => match style.foreground {
+                    Some(color) => color,
+                    None => continue,
+                },
+            };
+
+            if current != Some(color) {
+                f.write_str(&self.text[start..offset])?;
+                color.fmt_foreground(f)?;
+                current = Some(color);
+                start = offset;
+            }
+        }
+
+        f.write_str(&self.text[start..])?;
+
+        if current.is_some() {
+            f.write_str("\x1b[39m")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+//! Converting styled text to HTML
+//!
+//! The styles are written as inline CSS, see [`Style::to_css`] for details.
+
+use core::fmt::{self, Write};
+
+use crate::{css::is_default, sgr::SgrSpans, OptionalColor, Style, StyledValue};
+
+/// A writer which escapes all HTML special characters, and removes any ANSI escape sequences
+struct Escape<'a, W: ?Sized> {
+    out: &'a mut W,
+}
+
+impl<W: Write + ?Sized> Write for Escape<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+
+        while let Some(i) = rest.find(['&', '<', '>', '"', '\'']) {
+            self.out.write_str(&rest[..i])?;
+            self.out.write_str(match rest.as_bytes()[i] {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                _ => "&#39;",
+            })?;
+            rest = &rest[i + 1..];
+        }
+
+        self.out.write_str(rest)
+    }
+}
+
+/// Write `text` in a `<span>` with the given style
+fn write_span(
+    f: &mut fmt::Formatter<'_>,
+    style: &Style,
+    text: impl FnOnce(&mut dyn Write) -> fmt::Result,
+) -> fmt::Result {
+    let plain = style.effects.is_plain()
+        && is_default(style.foreground).is_none()
+        && is_default(style.background).is_none();
+
+    if !plain {
+        f.write_str("<span style=\"")?;
+        crate::css::write_declarations(f, style)?;
+        f.write_str("\">")?;
+    }
+
+    text(&mut Escape { out: f })?;
+
+    if !plain {
+        f.write_str("</span>")?;
+    }
+
+    Ok(())
+}
+
+/// Converts text containing ANSI escape sequences to HTML, see [`ansi_to_html`]
+#[derive(Debug, Clone, Copy)]
+pub struct AnsiToHtml<'a> {
+    text: &'a str,
+}
+
+/// Convert text containing ANSI escape sequences (like the output of other tools) to HTML
+///
+/// Each styled piece of text is written as a `<span>` with an inline `style` attribute, and all
+/// HTML special characters are escaped. See [`sgr::spans`](crate::sgr::spans) for how the text is parsed.
+///
+/// ```rust
+/// use colorz::html::ansi_to_html;
+///
+/// let html = ansi_to_html("\x1b[1;31merror\x1b[0m: <file> not found").to_string();
+///
+/// assert_eq!(
+///     html,
+///     "<span style=\"color: rgb(205, 0, 0); font-weight: bold\">error</span>: &lt;file&gt; not found"
+/// );
+/// ```
+#[inline]
+pub const fn ansi_to_html(text: &str) -> AnsiToHtml<'_> {
+    AnsiToHtml { text }
+}
+
+impl fmt::Display for AnsiToHtml<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        SgrSpans::new(self.text)
+            .try_for_each(|(style, text)| write_span(f, &style, |out| out.write_str(text)))
+    }
+}
+
+impl<T: fmt::Display, F: OptionalColor, B: OptionalColor, U: OptionalColor>
+    StyledValue<T, F, B, U>
+{
+    /// Convert the styled value to HTML
+    ///
+    /// The value is written in a `<span>` with an inline `style` attribute, with all HTML special
+    /// characters escaped. The style is always used, regardless of the [coloring mode](crate::mode).
+    /// Any ANSI escape sequences written by the value itself are removed.
+    ///
+    /// ```rust
+    /// use colorz::Colorize;
+    ///
+    /// assert_eq!(
+    ///     "a < b".blue().italics().to_html().to_string(),
+    ///     "<span style=\"color: rgb(0, 0, 238); font-style: italic\">a &lt; b</span>"
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "html")))]
+    #[inline]
+    pub fn to_html(&self) -> impl fmt::Display + fmt::Debug + '_ {
+        struct Html<'a, T> {
+            value: &'a T,
+            style: Style,
+        }
+
+        impl<T: fmt::Display> fmt::Display for Html<'_, T> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_span(f, &self.style, |out| {
+                    crate::strip::write_stripped(out, self.value)
+                })
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Debug for Html<'_, T> {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(self, f)
+            }
+        }
+
+        Html {
+            value: &self.value,
+            style: self.resolved_style(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn spans_to_html() {
+        struct Html(&'static str);
+
+        impl fmt::Display for Html {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                ansi_to_html(self.0).fmt(f)
+            }
+        }
+
+        let mut out = [0u8; 512];
+        let mut out = crate::sgr::test::Buffer::new(&mut out);
+        write!(
+            out,
+            "{}",
+            Html("\x1b[7;38;5;196;4:2;58;2;1;2;3m'x'\x1b[24;27;9;53m&\x1b[0m")
+        )
+        .unwrap();
+
+        assert_eq!(
+            out.as_str(),
+            "<span style=\"color: Canvas; background-color: rgb(255, 0, 0); text-decoration-line: underline; \
+            text-decoration-style: double; text-decoration-color: rgb(1, 2, 3)\">&#39;x&#39;</span>\
+            <span style=\"color: rgb(255, 0, 0); text-decoration-line: overline line-through\">&amp;</span>"
+        );
+    }
+}
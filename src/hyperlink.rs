@@ -0,0 +1,88 @@
+//! OSC 8 hyperlinks, which degrade to plain text on terminals that don't support them
+
+use core::fmt;
+
+use crate::mode::{self, Stream};
+
+/// A value which is written as a terminal hyperlink (OSC 8) to `url`, created from [`Colorize::hyperlink`](crate::Colorize::hyperlink)
+///
+/// The link is only written if [`mode::should_hyperlink`] allows it for the value's stream,
+/// otherwise only the value is written. So terminals which would print the OSC bytes literally
+/// (and redirected output) only get the plain text. URLs which contain control characters (like
+/// `ESC` or `BEL`, which would end the link early) are never written, only the value is.
+///
+/// ```rust
+/// use colorz::{Colorize, mode::{self, Stream}};
+///
+/// mode::set_hyperlink_support(true);
+///
+/// let link = "docs".blue().into_hyperlink("https://docs.rs/colorz");
+/// println!("see the {link} for details");
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(
+///     "docs".hyperlink("https://docs.rs/colorz").stream(Stream::AlwaysColor).to_string(),
+///     "\x1b]8;;https://docs.rs/colorz\x1b\\docs\x1b]8;;\x1b\\",
+/// );
+/// assert_eq!(
+///     "docs".hyperlink("https://docs.rs\x07\x1b[2J").stream(Stream::AlwaysColor).to_string(),
+///     "docs",
+/// );
+/// # }
+///
+/// mode::set_hyperlink_support(false);
+/// assert_eq!(
+///     "docs".hyperlink("https://docs.rs/colorz").stream(Stream::AlwaysColor).to_string(),
+///     "docs",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Hyperlink<'a, T> {
+    /// The text of the link
+    pub value: T,
+    /// The target of the link
+    pub url: &'a str,
+    /// The stream to use
+    pub stream: Option<Stream>,
+}
+
+impl<'a, T> Hyperlink<'a, T> {
+    /// Create a new hyperlink to `url`
+    #[inline]
+    pub const fn new(value: T, url: &'a str) -> Self {
+        Self {
+            value,
+            url,
+            stream: None,
+        }
+    }
+
+    /// Set the stream this link will be written to, see [`StyledValue::stream`](crate::StyledValue::stream)
+    #[inline]
+    pub const fn stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Set the stream this link will be written to, or use the default stream if `None`
+    #[inline]
+    pub const fn stream_opt(mut self, stream: Option<Stream>) -> Self {
+        self.stream = stream;
+        self
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Hyperlink<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !mode::should_hyperlink(self.stream) || self.url.chars().any(char::is_control) {
+            return self.value.fmt(f);
+        }
+
+        f.write_str("\x1b]8;;")?;
+        f.write_str(self.url)?;
+        f.write_str("\x1b\\")?;
+        self.value.fmt(f)?;
+        f.write_str("\x1b]8;;\x1b\\")
+    }
+}
@@ -0,0 +1,188 @@
+//! Styling every item of an iterator
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{OptionalColor, RuntimeStyledValue, Style, StyledValue};
+
+/// An extension trait for iterators which styles each item
+///
+/// ```rust
+/// use colorz::{ColorizeIter, Style, ansi};
+///
+/// let names = ["colorz", "serde", "clap"];
+///
+/// for name in names.iter().styled_with(Style::new().fg(ansi::Green)) {
+///     println!("{name}");
+/// }
+/// ```
+pub trait ColorizeIter: Iterator + Sized {
+    /// Style each item with the same style
+    ///
+    /// ```rust
+    /// use colorz::{ColorizeIter, Style, ansi};
+    ///
+    /// let style = Style::new().fg(ansi::Red).bold();
+    /// let errors: Vec<_> = ["not found", "timed out"].into_iter().styled_with(style).collect();
+    ///
+    /// assert_eq!(errors[1].value, "timed out");
+    /// assert_eq!(errors[1].style, style);
+    /// ```
+    #[inline]
+    fn styled_with<F, B, U>(self, style: Style<F, B, U>) -> StyledWith<Self, F, B, U>
+    where
+        F: OptionalColor,
+        B: OptionalColor,
+        U: OptionalColor,
+    {
+        StyledWith { iter: self, style }
+    }
+
+    /// Style each item with the style returned by `f`
+    ///
+    /// ```rust
+    /// use colorz::{ColorizeIter, Style, ansi};
+    ///
+    /// let styled: Vec<_> = [3, -1, 0]
+    ///     .into_iter()
+    ///     .styled_by(|&n| match n {
+    ///         n if n < 0 => Style::new().fg(ansi::Red).into_runtime_style(),
+    ///         _ => Style::new().into_runtime_style(),
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(styled[1].style, Style::new().fg(ansi::Red).into_runtime_style());
+    /// assert!(styled[0].style.is_plain());
+    /// ```
+    #[inline]
+    fn styled_by<G>(self, f: G) -> StyledBy<Self, G>
+    where
+        G: FnMut(&Self::Item) -> Style,
+    {
+        StyledBy { iter: self, f }
+    }
+}
+
+impl<I: Iterator> ColorizeIter for I {}
+
+/// An iterator which styles each item with the same style, created from [`ColorizeIter::styled_with`]
+#[derive(Debug, Clone, Copy)]
+pub struct StyledWith<I, F, B, U> {
+    iter: I,
+    style: Style<F, B, U>,
+}
+
+impl<I, F, B, U> Iterator for StyledWith<I, F, B, U>
+where
+    I: Iterator,
+    F: OptionalColor,
+    B: OptionalColor,
+    U: OptionalColor,
+{
+    type Item = StyledValue<I::Item, F, B, U>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        Some(StyledValue::new(value, self.style, None))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, F, B, U> DoubleEndedIterator for StyledWith<I, F, B, U>
+where
+    I: DoubleEndedIterator,
+    F: OptionalColor,
+    B: OptionalColor,
+    U: OptionalColor,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next_back()?;
+        Some(StyledValue::new(value, self.style, None))
+    }
+}
+
+impl<I, F, B, U> ExactSizeIterator for StyledWith<I, F, B, U>
+where
+    I: ExactSizeIterator,
+    F: OptionalColor,
+    B: OptionalColor,
+    U: OptionalColor,
+{
+}
+
+impl<I, F, B, U> FusedIterator for StyledWith<I, F, B, U>
+where
+    I: FusedIterator,
+    F: OptionalColor,
+    B: OptionalColor,
+    U: OptionalColor,
+{
+}
+
+/// An iterator which styles each item with the style returned by a closure, created from [`ColorizeIter::styled_by`]
+#[derive(Clone)]
+pub struct StyledBy<I, G> {
+    iter: I,
+    f: G,
+}
+
+impl<I: fmt::Debug, G> fmt::Debug for StyledBy<I, G> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StyledBy")
+            .field("iter", &self.iter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<I, G> Iterator for StyledBy<I, G>
+where
+    I: Iterator,
+    G: FnMut(&I::Item) -> Style,
+{
+    type Item = RuntimeStyledValue<I::Item>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next()?;
+        let style = (self.f)(&value);
+        Some(StyledValue::new(value, style, None))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I, G> DoubleEndedIterator for StyledBy<I, G>
+where
+    I: DoubleEndedIterator,
+    G: FnMut(&I::Item) -> Style,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let value = self.iter.next_back()?;
+        let style = (self.f)(&value);
+        Some(StyledValue::new(value, style, None))
+    }
+}
+
+impl<I, G> ExactSizeIterator for StyledBy<I, G>
+where
+    I: ExactSizeIterator,
+    G: FnMut(&I::Item) -> Style,
+{
+}
+
+impl<I, G> FusedIterator for StyledBy<I, G>
+where
+    I: FusedIterator,
+    G: FnMut(&I::Item) -> Style,
+{
+}
@@ -7,26 +7,87 @@
     clippy::missing_const_for_fn,
     clippy::missing_inline_in_public_items
 )]
-#![cfg_attr(doc, warn(rustdoc::missing_doc_code_examples))]
-#![cfg_attr(doc, feature(doc_cfg, rustdoc_missing_doc_code_examples))]
+#![cfg_attr(docsrs, warn(rustdoc::missing_doc_code_examples))]
+#![cfg_attr(docsrs, feature(doc_cfg, rustdoc_missing_doc_code_examples))]
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[macro_use]
+mod cap;
 #[macro_use]
 pub mod xterm;
 
 pub mod ansi;
+mod cached;
+#[cfg(feature = "colored-compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "colored-compat")))]
+pub mod compat;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod console;
+#[cfg(feature = "crossterm")]
+mod crossterm;
 pub mod css;
+#[cfg(feature = "defmt")]
+mod defmt;
+mod fmt_fn;
 mod from_str;
+pub mod gradient;
+#[cfg(feature = "html")]
+#[cfg_attr(docsrs, doc(cfg(feature = "html")))]
+pub mod html;
+mod hyperlink;
+mod iter;
+#[cfg(feature = "log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+pub mod log;
+pub mod ls_colors;
+mod macros;
 pub mod mode;
+pub mod palette;
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "std")]
+mod print;
+#[cfg(feature = "std")]
+mod query;
+mod render;
 pub mod rgb;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod sgr;
+#[cfg(feature = "alloc")]
+mod stack;
+pub mod strip;
 mod style;
+#[cfg(feature = "termcolor")]
+#[cfg_attr(docsrs, doc(cfg(feature = "termcolor")))]
+pub mod termcolor;
+#[cfg(feature = "alloc")]
+mod text;
+pub mod theme;
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub mod tracing;
 mod value;
 
-pub use from_str::ParseColorError;
+pub use cached::{CachedStyle, CachedStyledValue};
+pub use fmt_fn::{fmt_fn, FmtFn};
+pub use from_str::{ParseColorError, ParseEffectError, ParseStyleError};
+pub use hyperlink::Hyperlink;
+pub use iter::{ColorizeIter, StyledBy, StyledWith};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use path::{ColorizePath, PathDisplay, StyledPath};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use print::{eprint_styled, eprintln_styled, print_styled, println_styled};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use strip::AutoWriter;
 
 /// A styled value, created from [`Colorize`] or [`StyledValue::new`]
 ///
@@ -39,8 +100,26 @@ pub use from_str::ParseColorError;
 /// let hello: StyledValue<_, ansi::Blue> = "Hello ".blue();
 /// println!("{hello} world");
 /// ```
+///
+/// If a width is given, the styled value is padded outside of the escapes. To find the padding, the
+/// value is measured before it's written, so the value's formatting impl (like `Display`) runs up to
+/// 3 times and should write the same text each time.
+///
+/// Styled values can be compared and hashed if their value and colors can be. Two styled values are
+/// equal if they have the same value, style, stream and settings (like [`StyledValue::per_line`]).
+///
+/// ```rust
+/// use colorz::{Colorize, mode::Stream};
+/// use std::collections::HashSet;
+///
+/// assert_eq!("hello".red().bold(), "hello".red().bold());
+/// assert_ne!("hello".red(), "hello".red().stream(Stream::Stderr));
+///
+/// let cells: HashSet<_> = ["a".red().into_runtime_style(), "a".blue().into_runtime_style()].into();
+/// assert_eq!(cells.len(), 2);
+/// ```
 #[non_exhaustive]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct StyledValue<T, F = NoColor, B = NoColor, U = NoColor> {
     /// The value to style
     pub value: T,
@@ -48,12 +127,75 @@ pub struct StyledValue<T, F = NoColor, B = NoColor, U = NoColor> {
     pub style: Style<F, B, U>,
     /// The stream to use
     pub stream: Option<mode::Stream>,
+    options: value::Options,
 }
 
+/// A [`StyledValue`] whose style is only known at runtime
+///
+/// Unlike the typed [`StyledValue`]s produced by [`Colorize`], all values of this type
+/// have the same type for the same `T`, regardless of which colors are used. So they can be
+/// stored in collections, or picked dynamically (for example from a config file).
+///
+/// Use [`StyledValue::into_runtime_style`] to convert any styled value to this type,
+/// and [`StyledValue::as_dyn_display`] to also erase the value's type.
+///
+/// ```rust
+/// use colorz::{Colorize, RuntimeStyledValue, ansi, xterm};
+///
+/// let values: Vec<RuntimeStyledValue<&str>> = vec![
+///     "error".red().bold().into_runtime_style(),
+///     "warning".fg(xterm::Orange).into_runtime_style(),
+///     "note".into_style().into_runtime_style(),
+/// ];
+///
+/// for value in &values {
+///     println!("{value}");
+/// }
+/// ```
+pub type RuntimeStyledValue<T> = StyledValue<T, Option<Color>, Option<Color>, Option<Color>>;
+
 impl<T: ?Sized> Colorize for T {}
 pub use value::Colorize;
 
-pub use style::{Effect, EffectFlags, EffectFlagsIter, Style};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use stack::StyleStack;
+pub use style::{Effect, EffectFlags, EffectFlagsIter, Style, StyleEscapes};
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use text::{Spans, StyledString, StyledText};
+
+/// Enable ANSI escape sequences on the Windows console, for both stdout and stderr
+///
+/// Older Windows consoles (including the default console on Windows 10) print escape sequences
+/// as is (like `←[31m`) unless virtual terminal processing is enabled. This is done automatically
+/// when detecting color support (for [`Stream::Stdout`](mode::Stream::Stdout) and
+/// [`Stream::Stderr`](mode::Stream::Stderr) in [`Mode::Detect`](mode::Mode::Detect)), but it
+/// should be called manually if colors are written without detection (like with [`Mode::Always`](mode::Mode::Always)).
+///
+/// Returns false if ANSI escape sequences couldn't be enabled (for example, if the output isn't a console).
+/// On other platforms this does nothing and always returns true.
+///
+/// ```rust
+/// if colorz::enable_windows_ansi() {
+///     colorz::mode::set_coloring_mode(colorz::mode::Mode::Always);
+/// }
+/// ```
+#[inline]
+#[allow(clippy::missing_const_for_fn)]
+#[cfg(any(feature = "std", feature = "supports-color"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "supports-color"))))]
+pub fn enable_windows_ansi() -> bool {
+    #[cfg(windows)]
+    {
+        anstyle_query::windows::enable_ansi_colors().unwrap_or(true)
+    }
+
+    #[cfg(not(windows))]
+    {
+        true
+    }
+}
 
 /// A no color placeholder type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -65,19 +207,59 @@ pub struct NoColor;
 ///
 /// you can parse a color from a string, here are the supported formats
 /// * `#rrggbb` - where each `r`, `g`, or `b` is a hex character. This will parse to `Color::Rgb`,
+/// * `#rgb` - shorthand for `#rrggbb`, like in CSS. This will parse to `Color::Rgb`,
+/// * `rgb(r, g, b)` - where each component is in the range 0..=255. This will parse to `Color::Rgb`,
+/// * `hsl(h, s%, l%)` - where the hue is in degrees and the saturation and lightness are in the range 0..=100.
+///   This is converted to rgb, and will parse to `Color::Rgb`,
 /// * [0-9]{1,3} will parse to a `Color::Xterm` color code. Only supports values in the range 0..=255
 /// * `#xx` or `#x` - where each `x` is a hex character. This will parse to `Color::Xterm` color code,
-/// * the name of any ANSI color code case sensitive,  i.e. `red` or `bright blue` will parse to `Color::Ansi`
-///
-/// There isn't a way to parse to a `CssColor` at this time.
+/// * the name of any ANSI color code, ignoring case, i.e. `red`, `Red` or `bright blue` will parse to `Color::Ansi`
+/// * `default` will parse to `Color::Default`, the terminal's default color
+/// * the name of any CSS color, ignoring case, spaces, hyphens and underscores, i.e. `DodgerBlue` or `dodger-blue`
+///   will parse to `Color::Css`. Names which are also ANSI color names (like `red`) parse to `Color::Ansi`,
+///   so add a `css:` prefix (like `css:red`) to get the CSS color. The prefix is allowed for any CSS color.
 ///
 /// ```
-/// use colorz::{Color, xterm, ansi, rgb};
+/// use colorz::{Color, xterm, ansi, rgb, css};
 ///
 /// assert_eq!("#ff".parse::<Color>(), Ok(Color::Xterm(xterm::XtermColor::from_code(0xff))));
 /// assert_eq!("red".parse::<Color>(), Ok(Color::Ansi(ansi::AnsiColor::Red)));
 /// assert_eq!("bright blue".parse::<Color>(), Ok(Color::Ansi(ansi::AnsiColor::BrightBlue)));
+/// assert_eq!("default".parse::<Color>(), Ok(Color::Default));
 /// assert_eq!("#abcdef".parse::<Color>(), Ok(Color::Rgb(rgb::RgbColor { red: 0xab, green: 0xcd, blue: 0xef })));
+/// assert_eq!("#abc".parse::<Color>(), Ok(Color::Rgb(rgb::RgbColor { red: 0xaa, green: 0xbb, blue: 0xcc })));
+/// assert_eq!("rgb(255, 0, 10)".parse::<Color>(), Ok(Color::Rgb(rgb::RgbColor { red: 255, green: 0, blue: 10 })));
+/// assert_eq!("hsl(120, 50%, 50%)".parse::<Color>(), Ok(Color::Rgb(rgb::RgbColor { red: 64, green: 191, blue: 64 })));
+/// assert_eq!("dodger blue".parse::<Color>(), Ok(Color::Css(css::CssColor::DodgerBlue)));
+/// assert_eq!("Red".parse::<Color>(), Ok(Color::Ansi(ansi::AnsiColor::Red)));
+/// assert_eq!("css:red".parse::<Color>(), Ok(Color::Css(css::CssColor::Red)));
+/// ```
+///
+/// # Display
+///
+/// Colors are displayed in a canonical form which can be parsed back into the same color
+/// * `Color::Ansi` is displayed as it's name, i.e. `red` or `bright blue`
+/// * `Color::Xterm` is displayed as `#xx`
+/// * `Color::Rgb` is displayed as `#rrggbb`
+/// * `Color::Css` is displayed as it's `CamelCase` name, i.e. `DodgerBlue`. Names which are also ANSI color
+///   names are displayed with a `css:` prefix, i.e. `css:Red`
+/// * `Color::Default` is displayed as `default`. `Color::Ansi(AnsiColor::Default)` is also displayed as `default`,
+///   so it's parsed back as `Color::Default`
+///
+/// ```
+/// use colorz::{Color, xterm, ansi, rgb, css};
+///
+/// for color in [
+///     Color::Ansi(ansi::AnsiColor::BrightBlue),
+///     Color::Xterm(xterm::XtermColor::from_code(7)),
+///     Color::Css(css::CssColor::Red),
+///     Color::Rgb(rgb::RgbColor { red: 0xab, green: 0x12, blue: 0xcd }),
+///     Color::Default,
+/// ] {
+///     assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+/// }
+///
+/// assert_eq!(Color::Xterm(xterm::XtermColor::from_code(7)).to_string(), "#07");
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Color {
@@ -89,8 +271,18 @@ pub enum Color {
     Css(css::CssColor),
     /// The Rgb color type (see [`rgb`] for details)
     Rgb(rgb::RgbColor),
+    /// The terminal's default color, which explicitly resets the color (unlike `None`, which leaves it unchanged)
+    ///
+    /// This writes the color args `39` on the foreground, `49` on the background and `59` for the underline
+    Default,
 }
 
+// `Color`'s discriminant has unused values, so `None` is stored in it and `Option<Color>`
+// is the same size as `Color`. This keeps runtime styles at 16 bytes, so they are cheap to copy.
+const _: [(); core::mem::size_of::<Color>()] = [(); 4];
+const _: [(); core::mem::size_of::<Option<Color>>()] = [(); 4];
+const _: [(); core::mem::size_of::<Style>()] = [(); 16];
+
 mod seal {
     pub trait Seal: Copy {}
 }
@@ -107,7 +299,7 @@ pub trait ColorSpec: seal::Seal {
     ///
     /// For all single-color types specified by this crate, this is the corresponding `*Color` type.
     /// For [`AnsiColor`](ansi::AnsiColor), [`XtermColor`](xterm::XtermColor), [`CssColor`](css::CssColor), it is themselves
-    type Dynamic: WriteColor;
+    type Dynamic: WriteColor + Into<Color>;
 
     /// The color kind of this Color
     ///
@@ -143,33 +335,44 @@ impl<C: ColorSpec> WriteColor for C {
     }
 
     #[inline]
-    fn fmt_foreground_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(self.foreground_args())
+    fn into_color(self) -> Color {
+        self.into_dynamic().into()
     }
 
     #[inline]
-    fn fmt_background_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(self.background_args())
+    fn write_foreground_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        w: &mut W,
+    ) -> core::fmt::Result {
+        w.write_str(self.foreground_args())
     }
 
     #[inline]
-    fn fmt_underline_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(self.underline_args())
+    fn write_background_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        w: &mut W,
+    ) -> core::fmt::Result {
+        w.write_str(self.background_args())
     }
 
     #[inline]
-    fn fmt_foreground(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(self.foreground_escape())
+    fn write_underline_args_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        w.write_str(self.underline_args())
     }
 
     #[inline]
-    fn fmt_background(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(self.background_escape())
+    fn write_foreground_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        w.write_str(self.foreground_escape())
     }
 
     #[inline]
-    fn fmt_underline(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(self.underline_escape())
+    fn write_background_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        w.write_str(self.background_escape())
+    }
+
+    #[inline]
+    fn write_underline_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        w.write_str(self.underline_escape())
     }
 }
 
@@ -177,43 +380,100 @@ impl<C: ColorSpec> WriteColor for C {
 ///
 /// if you are using this trait directly, then you should use
 /// [`mode::should_color`] to allow users to disable coloring
+///
+/// The `write_*_to` methods write to any [`core::fmt::Write`], which avoids going through
+/// a [`Formatter`](core::fmt::Formatter) when writing to your own buffers
+///
+/// ```rust
+/// use colorz::{WriteColor, ansi, rgb::RgbColor};
+///
+/// let mut buffer = String::new();
+/// ansi::Red.write_foreground_to(&mut buffer)?;
+/// RgbColor { red: 1, green: 2, blue: 3 }.write_background_to(&mut buffer)?;
+///
+/// # if cfg!(not(any(feature = "max-colors-256", feature = "max-colors-16"))) {
+/// assert_eq!(buffer, "\x1b[31m\x1b[48;2;1;2;3m");
+/// # }
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
 pub trait WriteColor: seal::Seal {
     /// The color kind of this Color
     ///
     /// used to detect wether to color is available on a given terminal if the `supports-color` feature is enabled
     fn color_kind(self) -> mode::ColorKind;
 
+    /// Convert to the runtime [`Color`] type
+    fn into_color(self) -> Color;
+
+    /// write the foreground color arguments to `w`
+    fn write_foreground_args_to<W: core::fmt::Write + ?Sized>(self, w: &mut W)
+        -> core::fmt::Result;
+
+    /// write the background color arguments to `w`
+    fn write_background_args_to<W: core::fmt::Write + ?Sized>(self, w: &mut W)
+        -> core::fmt::Result;
+
+    /// write the underline color arguments to `w`
+    fn write_underline_args_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result;
+
+    /// write the foreground color sequence to `w`
+    #[inline]
+    fn write_foreground_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        w.write_str("\x1b[")?;
+        self.write_foreground_args_to(w)?;
+        w.write_str("m")
+    }
+
+    /// write the background color sequence to `w`
+    #[inline]
+    fn write_background_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        w.write_str("\x1b[")?;
+        self.write_background_args_to(w)?;
+        w.write_str("m")
+    }
+
+    /// write the underline color sequence to `w`
+    #[inline]
+    fn write_underline_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        w.write_str("\x1b[58;")?;
+        self.write_underline_args_to(w)?;
+        w.write_str("m")
+    }
+
     /// write the foreground color arguments
-    fn fmt_foreground_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;
+    #[inline]
+    fn fmt_foreground_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_foreground_args_to(f)
+    }
 
     /// write the background color arguments
-    fn fmt_background_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;
+    #[inline]
+    fn fmt_background_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_background_args_to(f)
+    }
 
     /// write the underline color arguments
-    fn fmt_underline_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result;
+    #[inline]
+    fn fmt_underline_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_underline_args_to(f)
+    }
 
     /// write the foreground color sequence
     #[inline]
     fn fmt_foreground(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("\x1b[")?;
-        self.fmt_foreground_args(f)?;
-        f.write_str("m")
+        self.write_foreground_to(f)
     }
 
     /// write the background color sequence
     #[inline]
     fn fmt_background(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("\x1b[")?;
-        self.fmt_background_args(f)?;
-        f.write_str("m")
+        self.write_background_to(f)
     }
 
     /// write the underline color sequence
     #[inline]
     fn fmt_underline(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("\x1b[58;")?;
-        self.fmt_underline_args(f)?;
-        f.write_str("m")
+        self.write_underline_to(f)
     }
 }
 
@@ -226,68 +486,207 @@ impl WriteColor for Color {
             Color::Xterm(_) => mode::ColorKind::Xterm,
             Color::Css(_) => mode::ColorKind::Rgb,
             Color::Rgb(_) => mode::ColorKind::Rgb,
+            Color::Default => mode::ColorKind::Ansi,
         }
     }
 
     #[inline]
-    fn fmt_foreground_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn into_color(self) -> Color {
+        self
+    }
+
+    #[inline]
+    fn write_foreground_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        w: &mut W,
+    ) -> core::fmt::Result {
         match self {
-            Color::Ansi(color) => color.fmt_foreground_args(f),
-            Color::Css(color) => color.fmt_foreground_args(f),
-            Color::Xterm(color) => color.fmt_foreground_args(f),
-            Color::Rgb(color) => color.fmt_background_args(f),
+            Color::Ansi(color) => color.write_foreground_args_to(w),
+            Color::Css(color) => color.write_foreground_args_to(w),
+            Color::Xterm(color) => color.write_foreground_args_to(w),
+            Color::Rgb(color) => color.write_foreground_args_to(w),
+            Color::Default => w.write_str("39"),
         }
     }
 
     #[inline]
-    fn fmt_background_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_background_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        w: &mut W,
+    ) -> core::fmt::Result {
         match self {
-            Color::Ansi(color) => color.fmt_background_args(f),
-            Color::Css(color) => color.fmt_background_args(f),
-            Color::Xterm(color) => color.fmt_background_args(f),
-            Color::Rgb(color) => color.fmt_background_args(f),
+            Color::Ansi(color) => color.write_background_args_to(w),
+            Color::Css(color) => color.write_background_args_to(w),
+            Color::Xterm(color) => color.write_background_args_to(w),
+            Color::Rgb(color) => color.write_background_args_to(w),
+            Color::Default => w.write_str("49"),
         }
     }
 
     #[inline]
-    fn fmt_underline_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_underline_args_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
         match self {
-            Color::Ansi(color) => color.fmt_underline_args(f),
-            Color::Css(color) => color.fmt_underline_args(f),
-            Color::Xterm(color) => color.fmt_underline_args(f),
-            Color::Rgb(color) => color.fmt_underline_args(f),
+            Color::Ansi(color) => color.write_underline_args_to(w),
+            Color::Css(color) => color.write_underline_args_to(w),
+            Color::Xterm(color) => color.write_underline_args_to(w),
+            Color::Rgb(color) => color.write_underline_args_to(w),
+            Color::Default => w.write_str("59"),
         }
     }
 
     #[inline]
-    fn fmt_foreground(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_foreground_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
         match self {
-            Color::Ansi(color) => color.fmt_foreground(f),
-            Color::Css(color) => color.fmt_foreground(f),
-            Color::Xterm(color) => color.fmt_foreground(f),
-            Color::Rgb(color) => color.fmt_foreground(f),
+            Color::Ansi(color) => color.write_foreground_to(w),
+            Color::Css(color) => color.write_foreground_to(w),
+            Color::Xterm(color) => color.write_foreground_to(w),
+            Color::Rgb(color) => color.write_foreground_to(w),
+            Color::Default => w.write_str("\x1b[39m"),
         }
     }
 
     #[inline]
-    fn fmt_background(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_background_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
         match self {
-            Color::Ansi(color) => color.fmt_background(f),
-            Color::Css(color) => color.fmt_background(f),
-            Color::Xterm(color) => color.fmt_background(f),
-            Color::Rgb(color) => color.fmt_background(f),
+            Color::Ansi(color) => color.write_background_to(w),
+            Color::Css(color) => color.write_background_to(w),
+            Color::Xterm(color) => color.write_background_to(w),
+            Color::Rgb(color) => color.write_background_to(w),
+            Color::Default => w.write_str("\x1b[49m"),
         }
     }
 
     #[inline]
-    fn fmt_underline(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_underline_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        match self {
+            Color::Ansi(color) => color.write_underline_to(w),
+            Color::Css(color) => color.write_underline_to(w),
+            Color::Xterm(color) => color.write_underline_to(w),
+            Color::Rgb(color) => color.write_underline_to(w),
+            Color::Default => w.write_str("\x1b[59m"),
+        }
+    }
+}
+
+impl core::fmt::Display for Color {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            Color::Ansi(color) => f.write_str(color.name()),
+            Color::Xterm(color) => write!(f, "#{:02x}", color as u8),
+            Color::Css(color) => {
+                if from_str::parse_ansi_name(color.name().as_bytes(), false).is_some() {
+                    f.write_str("css:")?;
+                }
+                f.write_str(color.name())
+            }
+            Color::Rgb(rgb::RgbColor { red, green, blue }) => {
+                write!(f, "#{red:02x}{green:02x}{blue:02x}")
+            }
+            Color::Default => f.write_str("default"),
+        }
+    }
+}
+
+impl Color {
+    /// The rgb value of this color
+    ///
+    /// ANSI and Xterm colors use the default xterm palette (see [`XtermColor::rgb`](xterm::XtermColor::rgb)),
+    /// since the actual colors shown depend on the terminal's theme. [`AnsiColor::Default`](ansi::AnsiColor::Default)
+    /// and [`Color::Default`] don't have a fixed value, so they're treated as black.
+    /// To get the terminal's actual colors, see `mode::TerminalPalette::to_rgb` (with the `std` feature).
+    ///
+    /// ```
+    /// use colorz::{Color, ansi::AnsiColor, css::CssColor, rgb::RgbColor};
+    ///
+    /// assert_eq!(Color::Ansi(AnsiColor::BrightRed).to_rgb(), RgbColor { red: 255, green: 0, blue: 0 });
+    /// assert_eq!(Color::Xterm(67.into()).to_rgb(), RgbColor { red: 95, green: 135, blue: 175 });
+    /// assert_eq!(Color::Css(CssColor::Tomato).to_rgb(), RgbColor { red: 255, green: 99, blue: 71 });
+    /// ```
+    #[inline]
+    pub const fn to_rgb(self) -> rgb::RgbColor {
         match self {
-            Color::Ansi(color) => color.fmt_underline(f),
-            Color::Css(color) => color.fmt_underline(f),
-            Color::Xterm(color) => color.fmt_underline(f),
-            Color::Rgb(color) => color.fmt_underline(f),
+            Color::Ansi(color) => color.to_xterm().rgb(),
+            Color::Xterm(color) => color.rgb(),
+            Color::Css(color) => color.rgb(),
+            Color::Rgb(color) => color,
+            Color::Default => rgb::RgbColor {
+                red: 0,
+                green: 0,
+                blue: 0,
+            },
         }
     }
+
+    /// Mix two colors, where `t = 0.0` gives `self` and `t = 1.0` gives `other`
+    ///
+    /// Both colors are converted via [`to_rgb`](Self::to_rgb), and the result is interpolated
+    /// with [`RgbColor::lerp`](rgb::RgbColor::lerp), so it's always an rgb color.
+    ///
+    /// ```
+    /// use colorz::{Color, ansi::AnsiColor, css::CssColor, rgb::RgbColor};
+    ///
+    /// let mixed = Color::Ansi(AnsiColor::BrightRed).mix(Color::Css(CssColor::Blue), 0.5);
+    /// assert_eq!(mixed, Color::Rgb(RgbColor { red: 128, green: 0, blue: 128 }));
+    /// ```
+    #[inline]
+    pub const fn mix(self, other: Color, t: f32) -> Color {
+        Color::Rgb(self.to_rgb().lerp(other.to_rgb(), t))
+    }
+
+    /// Convert the color to the nearest color of the given kind, if it's a higher-fidelity color
+    ///
+    /// Colors which are already at or below the given kind are unchanged, and
+    /// [`ColorKind::NoColor`](mode::ColorKind::NoColor) removes the color entirely.
+    /// See [`RgbColor::to_xterm`](rgb::RgbColor::to_xterm), [`RgbColor::to_ansi`](rgb::RgbColor::to_ansi)
+    /// and [`XtermColor::to_ansi_approx`](xterm::XtermColor::to_ansi_approx) for details.
+    /// These use the default xterm palette, to match against the terminal's actual colors
+    /// see `mode::TerminalPalette::downgrade` (with the `std` feature).
+    ///
+    /// ```
+    /// use colorz::{Color, ansi::AnsiColor, mode::ColorKind, rgb::RgbColor, xterm};
+    ///
+    /// let orange = Color::Rgb(RgbColor { red: 255, green: 128, blue: 0 });
+    ///
+    /// assert_eq!(orange.downgrade(ColorKind::Rgb), Some(orange));
+    /// assert_eq!(orange.downgrade(ColorKind::Xterm), Some(Color::Xterm(xterm::DarkOrange.into())));
+    /// assert_eq!(orange.downgrade(ColorKind::Ansi), Some(Color::Ansi(AnsiColor::Yellow)));
+    /// assert_eq!(orange.downgrade(ColorKind::NoColor), None);
+    /// ```
+    #[inline]
+    pub const fn downgrade(self, kind: mode::ColorKind) -> Option<Color> {
+        Some(match kind {
+            mode::ColorKind::NoColor => return None,
+            mode::ColorKind::Rgb => self,
+            mode::ColorKind::Xterm => match self {
+                Color::Css(color) => Color::Xterm(color.rgb().to_xterm()),
+                Color::Rgb(color) => Color::Xterm(color.to_xterm()),
+                Color::Ansi(_) | Color::Xterm(_) | Color::Default => self,
+            },
+            mode::ColorKind::Ansi => match self {
+                Color::Ansi(_) | Color::Default => self,
+                Color::Xterm(color) => Color::Ansi(color.to_ansi_approx()),
+                Color::Css(color) => Color::Ansi(color.rgb().to_ansi()),
+                Color::Rgb(color) => Color::Ansi(color.to_ansi()),
+            },
+        })
+    }
+
+    /// Convert the color to it's canonical string form, which can be parsed back into a [`Color`]
+    ///
+    /// This is the same as `color.to_string()`, see the `Display` section on [`Color`] for details
+    ///
+    /// ```
+    /// use colorz::{Color, ansi};
+    ///
+    /// assert_eq!(Color::Ansi(ansi::AnsiColor::Red).to_parseable_string(), "red");
+    /// ```
+    #[inline]
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_parseable_string(self) -> alloc::string::String {
+        alloc::string::ToString::to_string(&self)
+    }
 }
 
 impl seal::Seal for core::convert::Infallible {}
@@ -298,17 +697,31 @@ impl WriteColor for core::convert::Infallible {
     }
 
     #[inline]
-    fn fmt_foreground_args(self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn into_color(self) -> Color {
+        match self {}
+    }
+
+    #[inline]
+    fn write_foreground_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        _w: &mut W,
+    ) -> core::fmt::Result {
         match self {}
     }
 
     #[inline]
-    fn fmt_background_args(self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_background_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        _w: &mut W,
+    ) -> core::fmt::Result {
         match self {}
     }
 
     #[inline]
-    fn fmt_underline_args(self, _f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_underline_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        _w: &mut W,
+    ) -> core::fmt::Result {
         match self {}
     }
 }
@@ -332,6 +745,12 @@ pub trait OptionalColor: seal::Seal {
     /// Get the color value
     fn get(self) -> Option<Self::Color>;
 
+    /// Convert to the runtime color type
+    #[inline]
+    fn into_runtime_color(self) -> Option<Color> {
+        self.get().map(WriteColor::into_color)
+    }
+
     /// Get the [color kind](mode::ColorKind), this is used to check if
     /// formatting this color is supported on the current terminal when
     /// the `supports-color` feature is enabled
@@ -0,0 +1,149 @@
+//! Colored formatting for the [`log`] crate
+//!
+//! The level of each record is styled using the [`theme`](crate::theme), so applications which
+//! re-theme `error`, `warning`, `info`, `debug` and `trace` also change how their logs look.
+//! The target is styled with the `hint` style.
+//!
+//! ```rust
+//! use colorz::{log::format_record, mode::Stream};
+//!
+//! let record = log::Record::builder()
+//!     .args(format_args!("disk is almost full"))
+//!     .level(log::Level::Warn)
+//!     .target("storage")
+//!     .build();
+//!
+//! assert_eq!(
+//!     format_record(&record).stream(Stream::NeverColor).to_string(),
+//!     "WARN  storage: disk is almost full",
+//! );
+//!
+//! # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+//! assert_eq!(
+//!     format_record(&record).stream(Stream::AlwaysColor).to_string(),
+//!     "\x1b[1m\x1b[33mWARN\x1b[22m\x1b[39m  \x1b[2mstorage\x1b[22m: disk is almost full",
+//! );
+//! # }
+//! ```
+
+use core::fmt;
+
+use ::log::{Level, Record};
+
+use crate::{mode::Stream, Colorize, Style};
+
+/// The [`theme`](crate::theme) style used for the level
+///
+/// ```rust
+/// use colorz::{log::level_style, theme};
+///
+/// assert_eq!(Some(level_style(log::Level::Warn)), theme::get("warning"));
+/// ```
+#[inline]
+pub fn level_style(level: Level) -> Style {
+    let name = match level {
+        Level::Error => "error",
+        Level::Warn => "warning",
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    };
+
+    crate::theme::get_or_plain(name)
+}
+
+/// Format a record as `LEVEL target: message`, with the level and target styled
+///
+/// See the [module docs](self) for details
+#[inline]
+pub const fn format_record<'a>(record: &'a Record<'a>) -> FormattedRecord<'a> {
+    FormattedRecord {
+        record,
+        stream: None,
+    }
+}
+
+/// A [`Display`](fmt::Display) adapter for a log record, created by [`format_record`]
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedRecord<'a> {
+    record: &'a Record<'a>,
+    stream: Option<Stream>,
+}
+
+impl FormattedRecord<'_> {
+    /// Sets the stream for the record
+    #[inline]
+    pub const fn stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Sets the stream for the record
+    #[inline]
+    pub const fn stream_opt(mut self, stream: Option<Stream>) -> Self {
+        self.stream = stream;
+        self
+    }
+}
+
+impl fmt::Display for FormattedRecord<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let record = self.record;
+
+        write!(
+            f,
+            "{:<5} {}: {}",
+            record
+                .level()
+                .as_str()
+                .style_with(level_style(record.level()))
+                .stream_opt(self.stream),
+            record
+                .target()
+                .style_with(crate::theme::get_or_plain("hint"))
+                .stream_opt(self.stream),
+            record.args(),
+        )
+    }
+}
+
+/// A logger which writes colored records to stderr, see [`init`]
+#[cfg(feature = "std")]
+struct StderrLogger;
+
+#[cfg(feature = "std")]
+impl ::log::Log for StderrLogger {
+    fn enabled(&self, metadata: &::log::Metadata<'_>) -> bool {
+        metadata.level() <= ::log::max_level()
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            std::eprintln!("{}", format_record(record).stream(Stream::Stderr));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install a logger which writes colored records (see [`format_record`]) to stderr, and set the max level
+///
+/// # Errors
+///
+/// If a logger was already installed
+///
+/// ```rust
+/// colorz::log::init(log::LevelFilter::Info).unwrap();
+///
+/// log::info!("starting up");
+/// log::debug!("this isn't written");
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn init(level: ::log::LevelFilter) -> Result<(), ::log::SetLoggerError> {
+    ::log::set_logger(&StderrLogger)?;
+    ::log::set_max_level(level);
+    Ok(())
+}
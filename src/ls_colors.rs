@@ -0,0 +1,122 @@
+//! Reading the `LS_COLORS` (and `EXA_COLORS`) file color settings
+//!
+//! These environment variables are a `:` separated list of `key=args` entries, where
+//! `args` are SGR arguments (like `01;34`). The keys are either a file category
+//! (like `di` for directories, or `ex` for executables) or a glob pattern which
+//! matches the end of the file name (like `*.tar`).
+//!
+//! ```rust
+//! use colorz::{Style, ansi, ls_colors::LsColors};
+//!
+//! // usually read from the `LS_COLORS` environment variable
+//! let colors = LsColors::new("di=01;34:ln=target:*.tar=01;31:*.TAR=31");
+//!
+//! assert_eq!(colors.get("di"), Some(Style::new().fg(ansi::Blue).bold().into_runtime_style()));
+//! assert_eq!(colors.get("ex"), None);
+//! assert_eq!(colors.for_file_name("backup.tar"), Some(Style::new().fg(ansi::Red).bold().into_runtime_style()));
+//! assert_eq!(colors.for_file_name("backup.TAR"), Some(Style::new().fg(ansi::Red).into_runtime_style()));
+//! assert_eq!(colors.for_file_name("backup.zip"), None);
+//! ```
+
+use crate::Style;
+
+/// The file color settings from `LS_COLORS` or `EXA_COLORS`, see the [module docs](self)
+///
+/// This borrows the settings and parses them on each lookup, so no allocation is needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LsColors<'a> {
+    spec: &'a str,
+}
+
+impl<'a> LsColors<'a> {
+    /// Read the file color settings in `spec`
+    #[inline]
+    pub const fn new(spec: &'a str) -> Self {
+        Self { spec }
+    }
+
+    /// Iterate over all entries, in the order they were written
+    ///
+    /// ```rust
+    /// use colorz::{Style, ansi, ls_colors::LsColors};
+    ///
+    /// let colors = LsColors::new("di=34:ex=32");
+    /// let mut entries = colors.entries();
+    ///
+    /// assert_eq!(entries.next(), Some(("di", Style::new().fg(ansi::Blue).into_runtime_style())));
+    /// assert_eq!(entries.next(), Some(("ex", Style::new().fg(ansi::Green).into_runtime_style())));
+    /// assert_eq!(entries.next(), None);
+    /// ```
+    #[inline]
+    pub fn entries(&self) -> Entries<'a> {
+        Entries {
+            rest: self.spec.split(':'),
+        }
+    }
+
+    /// The style for the given key (like `di` or `*.tar`)
+    ///
+    /// If the key appears multiple times, the last entry is used. Entries which
+    /// aren't SGR arguments (like `ln=target`) are skipped.
+    ///
+    /// Common file category keys are:
+    /// * `no` - normal text, `fi` - regular files, `di` - directories, `ln` - symbolic links
+    /// * `or` - broken symbolic links, `mi` - missing files which a broken link points to
+    /// * `pi` - named pipes, `so` - sockets, `do` - doors, `bd` - block devices, `cd` - character devices
+    /// * `ex` - executable files, `su` - setuid files, `sg` - setgid files
+    /// * `st` - sticky directories, `ow` - other writable directories, `tw` - both sticky and other writable
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<Style> {
+        self.entries()
+            .filter(|&(entry, _)| entry == key)
+            .last()
+            .map(|(_, style)| style)
+    }
+
+    /// The style for a file name, based on the glob patterns (like `*.tar`)
+    ///
+    /// Patterns which match the case of the file name are preferred, otherwise patterns
+    /// are matched ignoring ascii case. If multiple patterns match, the last one is used.
+    ///
+    /// This doesn't look at the file category, so directories and other special files
+    /// should be looked up via [`get`](Self::get) first.
+    #[inline]
+    pub fn for_file_name(&self, name: &str) -> Option<Style> {
+        let mut ignoring_case = None;
+        let mut exact = None;
+
+        for (key, style) in self.entries() {
+            let Some(suffix) = key.strip_prefix('*') else {
+                continue;
+            };
+
+            if name.ends_with(suffix) {
+                exact = Some(style);
+            } else if name.len() >= suffix.len()
+                && name.as_bytes()[name.len() - suffix.len()..]
+                    .eq_ignore_ascii_case(suffix.as_bytes())
+            {
+                ignoring_case = Some(style);
+            }
+        }
+
+        exact.or(ignoring_case)
+    }
+}
+
+/// An iterator over the entries of [`LsColors`], see [`LsColors::entries`]
+#[derive(Debug, Clone)]
+pub struct Entries<'a> {
+    rest: core::str::Split<'a, char>,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = (&'a str, Style);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rest.find_map(crate::sgr::parse_entry)
+    }
+}
+
+impl core::iter::FusedIterator for Entries<'_> {}
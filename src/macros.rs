@@ -0,0 +1,120 @@
+//! Macros for writing styled text
+
+/// Write formatted text with a style to a writer, like [`write!`]
+///
+/// This formats the text with the given style in a single pass, as if it was wrapped in a
+/// [`StyledValue`](crate::StyledValue). So it follows the coloring mode (using the default stream),
+/// and works with both [`core::fmt::Write`] and `std::io::Write` writers, as well as [`Formatter`](core::fmt::Formatter)s.
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use colorz::{styled_write, ansi, mode, Style};
+///
+/// mode::set_coloring_mode(mode::Mode::Always);
+///
+/// let (name, ms) = ("build", 42);
+/// let mut out = String::new();
+/// styled_write!(out, Style::new().fg(ansi::Green), "{} took {}ms", name, ms)?;
+///
+/// # if colorz::Style::new().bold().should_color(mode::Stream::AlwaysColor) {
+/// assert_eq!(out, "\x1b[32mbuild took 42ms\x1b[39m");
+/// # }
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+#[macro_export]
+macro_rules! styled_write {
+    ($dst:expr, $style:expr, $($arg:tt)+) => {
+        ::core::write!(
+            $dst,
+            "{}",
+            $crate::StyledValue::new(::core::format_args!($($arg)+), $style, ::core::option::Option::None),
+        )
+    };
+}
+
+/// Write formatted text with a style and a newline to a writer, like [`writeln!`]
+///
+/// The newline is written after the style is cleared, see [`styled_write!`]
+///
+/// ```rust
+/// use core::fmt;
+/// use colorz::{styled_writeln, Style};
+///
+/// struct Report<'a> {
+///     name: &'a str,
+///     ms: u32,
+/// }
+///
+/// impl fmt::Display for Report<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         styled_writeln!(f, Style::new().bold(), "{}", self.name)?;
+///         styled_writeln!(f, Style::new().dimmed(), "took {}ms", self.ms)
+///     }
+/// }
+///
+/// println!("{}", Report { name: "build", ms: 42 });
+/// ```
+#[macro_export]
+macro_rules! styled_writeln {
+    ($dst:expr, $style:expr, $($arg:tt)+) => {
+        ::core::write!(
+            $dst,
+            "{}\n",
+            $crate::StyledValue::new(::core::format_args!($($arg)+), $style, ::core::option::Option::None),
+        )
+    };
+}
+
+/// Parse a style at compile time, see [`Style`](crate::Style)'s `FromStr` impl for the format
+///
+/// This expands to a constant runtime [`Style`](crate::Style), so it can be used in `const`s and `static`s.
+/// An invalid style is a compile error.
+///
+/// ```rust
+/// use colorz::{style, ansi, Colorize, Style};
+///
+/// static ERROR: Style = style!("bold red on blue");
+///
+/// assert_eq!(ERROR, Style::new().fg(ansi::Red).bg(ansi::Blue).bold().into_runtime_style());
+/// println!("{}", "error".into_style_with(ERROR));
+/// ```
+///
+/// ```rust,compile_fail
+/// let style = colorz::style!("bold red green");
+/// ```
+#[macro_export]
+macro_rules! style {
+    ($spec:expr $(,)?) => {{
+        const STYLE: $crate::Style = match $crate::Style::parse($spec) {
+            ::core::result::Result::Ok(style) => style,
+            ::core::result::Result::Err(err) => ::core::panic!("{}", err.as_str()),
+        };
+        STYLE
+    }};
+}
+
+/// Get the compile time [`Rgb`](crate::rgb::Rgb) color of a hex color, written as `#rrggbb` or `#rgb`
+///
+/// An invalid hex color is a compile error, see [`RgbColor::from_hex`](crate::rgb::RgbColor::from_hex)
+///
+/// ```rust
+/// use colorz::{color, rgb::Rgb, Colorize};
+///
+/// let dodger_blue: Rgb<30, 144, 255> = color!("#1e90ff");
+///
+/// println!("{}", "hello".fg(color!("#1e90ff")).bg(color!("#fff")));
+/// ```
+///
+/// ```rust,compile_fail
+/// let color = colorz::color!("#1e90fg");
+/// ```
+#[macro_export]
+macro_rules! color {
+    ($hex:expr $(,)?) => {{
+        const COLOR: $crate::rgb::RgbColor = match $crate::rgb::RgbColor::from_hex($hex) {
+            ::core::result::Result::Ok(color) => color,
+            ::core::result::Result::Err(err) => ::core::panic!("{}", err.as_str()),
+        };
+        $crate::rgb::Rgb::<{ COLOR.red }, { COLOR.green }, { COLOR.blue }>
+    }};
+}
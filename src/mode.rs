@@ -18,14 +18,36 @@
 #[cfg(doc)]
 use crate::StyledValue;
 
-use core::{str::FromStr, sync::atomic::AtomicU8};
+use core::{
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU32, AtomicU8},
+};
+
+use crate::EffectFlags;
 
 static COLORING_MODE: AtomicU8 = AtomicU8::new(Mode::DETECT);
 static DEFAULT_STREAM: AtomicU8 = AtomicU8::new(Stream::AlwaysColor.encode());
-#[cfg(any(feature = "std", feature = "supports-color"))]
+static AUTO_DOWNGRADE: AtomicBool = AtomicBool::new(false);
+static SUPPRESSED_EFFECTS: AtomicU32 = AtomicU32::new(0);
+static BRIGHT_FALLBACK: AtomicBool = AtomicBool::new(false);
 static STDOUT_SUPPORT: AtomicU8 = AtomicU8::new(ColorSupport::DETECT);
-#[cfg(any(feature = "std", feature = "supports-color"))]
 static STDERR_SUPPORT: AtomicU8 = AtomicU8::new(ColorSupport::DETECT);
+static HYPERLINK_SUPPORT: AtomicU8 = AtomicU8::new(HYPERLINKS_DETECT);
+#[cfg(feature = "std")]
+static MODE_ENV_VAR: std::sync::RwLock<&'static str> = std::sync::RwLock::new("COLORZ");
+#[cfg(feature = "std")]
+static DETECTOR: std::sync::RwLock<Option<Detector>> = std::sync::RwLock::new(None);
+#[cfg(feature = "std")]
+static COLOR_TRANSFORM: std::sync::RwLock<Option<ColorTransform>> = std::sync::RwLock::new(None);
+/// Set when a color transform is installed, so that formatting can skip locking otherwise
+#[cfg(feature = "std")]
+static COLOR_TRANSFORM_ACTIVE: AtomicBool = AtomicBool::new(false);
+#[cfg(feature = "std")]
+static TERMINAL_PALETTE: std::sync::RwLock<Option<TerminalPalette>> = std::sync::RwLock::new(None);
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use crate::query::TerminalPalette;
 
 /// The coloring mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,7 +70,7 @@ impl std::error::Error for ModeFromStrError {}
 impl core::fmt::Display for ModeFromStrError {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(r#"Invalid mode: valid options include "detect", "always", "never""#)
+        f.write_str(r#"Invalid mode: valid options include "detect", "auto", "always", "never""#)
     }
 }
 
@@ -64,15 +86,70 @@ impl FromStr for Mode {
     }
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl TryFrom<&std::ffi::OsStr> for Mode {
+    type Error = ModeFromStrError;
+
+    #[inline]
+    fn try_from(s: &std::ffi::OsStr) -> Result<Self, Self::Error> {
+        Self::from_ascii_bytes(s.as_encoded_bytes())
+    }
+}
+
+/// `true` is [`Mode::Always`] and `false` is [`Mode::Never`]
+impl From<bool> for Mode {
+    #[inline]
+    fn from(color: bool) -> Self {
+        if color {
+            Mode::Always
+        } else {
+            Mode::Never
+        }
+    }
+}
+
+/// Writes the name accepted by [`Mode`]'s `FromStr` impl
+///
+/// ```rust
+/// use colorz::mode::Mode;
+///
+/// for mode in [Mode::Detect, Mode::Always, Mode::Never] {
+///     assert_eq!(mode.to_string().parse(), Ok(mode));
+/// }
+/// ```
+impl core::fmt::Display for Mode {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Mode::Detect => "detect",
+            Mode::Always => "always",
+            Mode::Never => "never",
+        })
+    }
+}
+
 impl Mode {
-    /// Parse the mode from some ascii encoded bytes
+    /// Parse the mode from some ascii encoded bytes, ignoring case
+    ///
+    /// This accepts `detect` (or `auto`), `always` and `never`
+    ///
+    /// ```rust
+    /// use colorz::mode::Mode;
+    ///
+    /// assert_eq!(Mode::from_ascii_bytes(b"Always"), Ok(Mode::Always));
+    /// assert_eq!(Mode::from_ascii_bytes(b"auto"), Ok(Mode::Detect));
+    /// ```
     #[inline]
     pub const fn from_ascii_bytes(s: &[u8]) -> Result<Self, ModeFromStrError> {
         const DETECT_STR: u64 = u64::from_ne_bytes(*b"detect\0\0") | ASCII_CASE_MASK_SIMD;
         const ALWAYS_STR: u64 = u64::from_ne_bytes(*b"always\0\0") | ASCII_CASE_MASK_SIMD;
         const NEVER_STR: u64 = u64::from_ne_bytes(*b"never\0\0\0") | ASCII_CASE_MASK_SIMD;
 
+        const AUTO_STR: u64 = u64::from_ne_bytes(*b"auto\0\0\0\0") | ASCII_CASE_MASK_SIMD;
+
         let data = match *s {
+            [a, b, c, d] => u64::from_ne_bytes([a, b, c, d, 0, 0, 0, 0]),
             [a, b, c, d, e] => u64::from_ne_bytes([a, b, c, d, e, 0, 0, 0]),
             [a, b, c, d, e, f] => u64::from_ne_bytes([a, b, c, d, e, f, 0, 0]),
             _ => return Err(ModeFromStrError),
@@ -81,7 +158,7 @@ impl Mode {
         let data = data | ASCII_CASE_MASK_SIMD;
 
         match data {
-            DETECT_STR => Ok(Mode::Detect),
+            DETECT_STR | AUTO_STR => Ok(Mode::Detect),
             ALWAYS_STR => Ok(Mode::Always),
             NEVER_STR => Ok(Mode::Never),
             _ => Err(ModeFromStrError),
@@ -115,7 +192,9 @@ impl std::error::Error for StreamFromStrError {}
 impl core::fmt::Display for StreamFromStrError {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(r#"Invalid mode: valid options include "stdout", "stderr", "always", "never""#)
+        f.write_str(
+            r#"Invalid stream: valid options include "stdout", "stderr", "always", "never""#,
+        )
     }
 }
 
@@ -128,6 +207,38 @@ impl FromStr for Stream {
     }
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl TryFrom<&std::ffi::OsStr> for Stream {
+    type Error = StreamFromStrError;
+
+    #[inline]
+    fn try_from(s: &std::ffi::OsStr) -> Result<Self, Self::Error> {
+        Self::from_ascii_bytes(s.as_encoded_bytes())
+    }
+}
+
+/// Writes the name accepted by [`Stream`]'s `FromStr` impl
+///
+/// ```rust
+/// use colorz::mode::Stream;
+///
+/// for stream in [Stream::Stdout, Stream::Stderr, Stream::AlwaysColor, Stream::NeverColor] {
+///     assert_eq!(stream.to_string().parse(), Ok(stream));
+/// }
+/// ```
+impl core::fmt::Display for Stream {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Stream::Stdout => "stdout",
+            Stream::Stderr => "stderr",
+            Stream::AlwaysColor => "always",
+            Stream::NeverColor => "never",
+        })
+    }
+}
+
 impl Stream {
     /// Parse the mode from some ascii encoded bytes
     #[inline]
@@ -170,32 +281,206 @@ pub enum ColorKind {
     NoColor,
 }
 
-#[cfg(any(feature = "std", feature = "supports-color"))]
+/// Color kinds are ordered by fidelity, so
+/// `ColorKind::NoColor < ColorKind::Ansi < ColorKind::Xterm < ColorKind::Rgb`
+///
+/// ```rust
+/// use colorz::mode::ColorKind;
+///
+/// assert!(ColorKind::NoColor < ColorKind::Ansi);
+/// assert!(ColorKind::Ansi < ColorKind::Xterm);
+/// assert!(ColorKind::Xterm < ColorKind::Rgb);
+/// ```
+impl Ord for ColorKind {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.fidelity().cmp(&other.fidelity())
+    }
+}
+
+impl PartialOrd for ColorKind {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl ColorKind {
+    const fn fidelity(self) -> u8 {
+        match self {
+            ColorKind::NoColor => 0,
+            ColorKind::Ansi => 1,
+            ColorKind::Xterm => 2,
+            ColorKind::Rgb => 3,
+        }
+    }
+
+    /// Is this color kind supported, [`ColorKind::NoColor`] is always supported
+    ///
+    /// ```rust
+    /// use colorz::mode::{ColorKind, ColorSupport};
+    ///
+    /// assert!(ColorKind::Xterm.is_supported_by(ColorSupport::XTERM));
+    /// assert!(!ColorKind::Rgb.is_supported_by(ColorSupport::XTERM));
+    /// assert!(ColorKind::NoColor.is_supported_by(ColorSupport::NONE));
+    /// ```
+    #[inline]
+    pub const fn is_supported_by(self, support: ColorSupport) -> bool {
+        support.supports(&[self])
+    }
+
+    /// The higher fidelity of the two color kinds
+    #[inline]
+    pub const fn max(self, other: Self) -> Self {
+        if self.fidelity() < other.fidelity() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// The lower fidelity of the two color kinds
+    #[inline]
+    pub const fn min(self, other: Self) -> Self {
+        if self.fidelity() > other.fidelity() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// The highest fidelity color kind in `kinds`, or [`ColorKind::NoColor`] if `kinds` is empty
+    ///
+    /// This is the kind which a terminal needs to support to write all of the `kinds`
+    ///
+    /// ```rust
+    /// use colorz::mode::ColorKind;
+    ///
+    /// assert_eq!(ColorKind::max_of(&[ColorKind::Ansi, ColorKind::Rgb, ColorKind::NoColor]), ColorKind::Rgb);
+    /// assert_eq!(ColorKind::max_of(&[]), ColorKind::NoColor);
+    /// ```
+    #[inline]
+    pub const fn max_of(kinds: &[ColorKind]) -> Self {
+        let mut max = ColorKind::NoColor;
+        let mut i = 0;
+
+        while i < kinds.len() {
+            max = max.max(kinds[i]);
+            i += 1;
+        }
+
+        max
+    }
+}
+
+/// The highest fidelity color kind which is ever written
+///
+/// This is [`ColorKind::Rgb`] unless one of these features is enabled
+/// * `max-colors-256` - RGB and CSS colors are written as the nearest Xterm color
+/// * `max-colors-16` - RGB, CSS and Xterm colors are written as the nearest ANSI color
+///
+/// The colors are converted via [`Color::downgrade`](crate::Color::downgrade). Compile time colors (like
+/// [`css::Tomato`](crate::css::Tomato)) are converted at compile time, so this has no runtime cost for them.
+/// This doesn't change the color values themselves, only the escape sequences used to write them.
+pub const MAX_COLOR_KIND: ColorKind = if cfg!(feature = "max-colors-16") {
+    ColorKind::Ansi
+} else if cfg!(feature = "max-colors-256") {
+    ColorKind::Xterm
+} else {
+    ColorKind::Rgb
+};
+
+/// Which color kinds a terminal supports, see [`set_stream_support`]
 #[non_exhaustive]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct ColorSupport {
-    ansi: bool,
-    xterm: bool,
-    rgb: bool,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColorSupport {
+    /// Are [`ColorKind::Ansi`] colors supported
+    pub ansi: bool,
+    /// Are [`ColorKind::Xterm`] colors supported
+    pub xterm: bool,
+    /// Are [`ColorKind::Rgb`] colors supported
+    pub rgb: bool,
 }
 
-#[cfg(any(feature = "std", feature = "supports-color"))]
 impl ColorSupport {
     const DETECT: u8 = 0x80;
 
-    #[cfg(feature = "supports-color")]
-    fn encode(self) -> u8 {
-        u8::from(self.ansi) | u8::from(self.xterm) << 1 | u8::from(self.rgb) << 2
+    /// No colors are supported
+    pub const NONE: Self = Self::new(false, false, false);
+    /// Only [`ColorKind::Ansi`] colors are supported
+    pub const ANSI: Self = Self::new(true, false, false);
+    /// [`ColorKind::Ansi`] and [`ColorKind::Xterm`] colors are supported
+    pub const XTERM: Self = Self::new(true, true, false);
+    /// All colors are supported
+    pub const RGB: Self = Self::new(true, true, true);
+
+    const fn new(ansi: bool, xterm: bool, rgb: bool) -> Self {
+        Self { ansi, xterm, rgb }
     }
 
-    #[cfg(feature = "supports-color")]
-    fn decode(x: u8) -> Self {
+    const fn encode(self) -> u8 {
+        self.ansi as u8 | (self.xterm as u8) << 1 | (self.rgb as u8) << 2
+    }
+
+    const fn decode(x: u8) -> Self {
         Self {
             ansi: x & 0b001 != 0,
             xterm: x & 0b010 != 0,
             rgb: x & 0b100 != 0,
         }
     }
+
+    /// The highest fidelity color kind which is supported, or [`ColorKind::NoColor`] if no colors are supported
+    ///
+    /// ```rust
+    /// use colorz::mode::{ColorKind, ColorSupport};
+    ///
+    /// assert_eq!(ColorSupport::XTERM.max_kind(), ColorKind::Xterm);
+    /// assert_eq!(ColorSupport::NONE.max_kind(), ColorKind::NoColor);
+    /// ```
+    #[inline]
+    pub const fn max_kind(self) -> ColorKind {
+        if self.rgb {
+            ColorKind::Rgb
+        } else if self.xterm {
+            ColorKind::Xterm
+        } else if self.ansi {
+            ColorKind::Ansi
+        } else {
+            ColorKind::NoColor
+        }
+    }
+
+    /// Are all of the given color kinds supported
+    ///
+    /// ```rust
+    /// use colorz::mode::{ColorKind, ColorSupport};
+    ///
+    /// assert!(ColorSupport::XTERM.supports(&[ColorKind::Ansi, ColorKind::Xterm]));
+    /// assert!(!ColorSupport::XTERM.supports(&[ColorKind::Rgb]));
+    /// assert!(ColorSupport::NONE.supports(&[ColorKind::NoColor]));
+    /// ```
+    #[inline]
+    pub const fn supports(self, kinds: &[ColorKind]) -> bool {
+        let mut i = 0;
+
+        while i < kinds.len() {
+            let supported = match kinds[i] {
+                ColorKind::Ansi => self.ansi,
+                ColorKind::Xterm => self.xterm,
+                ColorKind::Rgb => self.rgb,
+                ColorKind::NoColor => true,
+            };
+
+            if !supported {
+                return false;
+            }
+
+            i += 1;
+        }
+
+        true
+    }
 }
 
 impl Mode {
@@ -219,28 +504,49 @@ impl Mode {
 
     /// Reads the current mode from the environment
     ///
-    /// * If `NO_COLOR` is set to a non-zero value, [`Mode::Never`] is returned
+    /// The environment variables are checked in this order, and the first one which applies is used
+    /// 1. If the program's own variable (`COLORZ` by default, see [`set_mode_env_var`]) is set to
+    ///    `always`, `never` or `auto` (or `detect`), then that mode is returned
+    /// 2. If `NO_COLOR` is set (to any value, even an empty one), [`Mode::Never`] is returned
+    /// 3. If `CLICOLOR_FORCE`, `FORCE_COLOR` or `ALWAYS_COLOR` is set to a non-empty value other than `0`,
+    ///    [`Mode::Always`] is returned
+    /// 4. If `CLICOLOR` is set to `0`, [`Mode::Never`] is returned
+    /// 5. If `TERM` is set to `dumb`, [`Mode::Never`] is returned
+    /// 6. otherwise None is returned
     ///
-    /// * If `ALWAYS_COLOR`, `CLICOLOR_FORCE`, `FORCE_COLOR` is set to a non-zero value, [`Mode::Always`] is returned
-    ///
-    /// * otherwise None is returned
+    /// See <https://no-color.org> and <https://bixense.com/clicolors> for details on these conventions.
     #[cfg(feature = "std")]
-    #[cfg_attr(doc, doc(cfg(feature = "std")))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
     pub fn from_env() -> Option<Self> {
-        if std::env::var_os("NO_COLOR").is_some_and(|x| x != "0") {
+        Self::from_env_vars(get_mode_env_var(), |name| std::env::var_os(name))
+    }
+
+    #[cfg(feature = "std")]
+    fn from_env_vars(
+        mode_var: &str,
+        var: impl Fn(&str) -> Option<std::ffi::OsString>,
+    ) -> Option<Self> {
+        let is_set = |name| var(name).is_some_and(|x| !x.is_empty() && x != "0");
+
+        if let Some(Ok(mode)) = var(mode_var).map(|mode| Self::try_from(mode.as_os_str())) {
+            return Some(mode);
+        }
+
+        if var("NO_COLOR").is_some() {
             return Some(Self::Never);
         }
 
-        if std::env::var_os("ALWAYS_COLOR").is_some_and(|x| x != "0") {
+        if is_set("CLICOLOR_FORCE") || is_set("FORCE_COLOR") || is_set("ALWAYS_COLOR") {
             return Some(Self::Always);
         }
 
-        if std::env::var_os("CLICOLOR_FORCE").is_some_and(|x| x != "0") {
-            return Some(Self::Always);
+        if var("CLICOLOR").is_some_and(|x| x == "0") {
+            return Some(Self::Never);
         }
 
-        if std::env::var_os("FORCE_COLOR").is_some_and(|x| x != "0") {
-            return Some(Self::Always);
+        if var("TERM").is_some_and(|x| x == "dumb") {
+            return Some(Self::Never);
         }
 
         None
@@ -278,6 +584,88 @@ pub fn set_coloring_mode(mode: Mode) {
     COLORING_MODE.store(Mode::encode(mode), core::sync::atomic::Ordering::Release)
 }
 
+/// Set the name of the program's own environment variable to control the coloring mode,
+/// which is `COLORZ` by default
+///
+/// This lets users control the coloring of just this program (for example with `MYAPP_COLOR=never`),
+/// see [`Mode::from_env`] for details.
+///
+/// ```rust
+/// colorz::mode::set_mode_env_var("MYAPP_COLOR");
+/// assert_eq!(colorz::mode::get_mode_env_var(), "MYAPP_COLOR");
+///
+/// colorz::mode::set_coloring_mode_from_env();
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn set_mode_env_var(name: &'static str) {
+    *MODE_ENV_VAR
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = name;
+}
+
+/// Get the name of the program's own environment variable to control the coloring mode,
+/// see [`set_mode_env_var`]
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn get_mode_env_var() -> &'static str {
+    *MODE_ENV_VAR
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Set the global coloring mode from a `--color <when>` option parsed by [`clap`]
+///
+/// This is the same as `set_coloring_mode(Mode::from(choice))`, see [`set_coloring_mode`]
+///
+/// ```rust
+/// use clap::{Arg, ColorChoice, Command};
+///
+/// let matches = Command::new("app")
+///     .arg(Arg::new("color").long("color").value_parser(clap::value_parser!(ColorChoice)))
+///     .get_matches_from(["app", "--color", "never"]);
+///
+/// if let Some(&choice) = matches.get_one::<ColorChoice>("color") {
+///     colorz::mode::set_from_color_choice(choice);
+/// }
+///
+/// assert_eq!(colorz::mode::get_coloring_mode(), colorz::mode::Mode::Never);
+/// ```
+#[inline]
+#[cfg(feature = "clap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+pub fn set_from_color_choice(choice: clap::ColorChoice) {
+    set_coloring_mode(choice.into())
+}
+
+#[cfg(feature = "clap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+impl From<clap::ColorChoice> for Mode {
+    #[inline]
+    fn from(choice: clap::ColorChoice) -> Self {
+        match choice {
+            clap::ColorChoice::Auto => Mode::Detect,
+            clap::ColorChoice::Always => Mode::Always,
+            clap::ColorChoice::Never => Mode::Never,
+        }
+    }
+}
+
+#[cfg(feature = "clap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+impl From<Mode> for clap::ColorChoice {
+    #[inline]
+    fn from(mode: Mode) -> Self {
+        match mode {
+            Mode::Detect => clap::ColorChoice::Auto,
+            Mode::Always => clap::ColorChoice::Always,
+            Mode::Never => clap::ColorChoice::Never,
+        }
+    }
+}
+
 /// Reads the current mode from the environment
 ///
 /// if no relevant environment variables are set, then the coloring mode is left unchanged
@@ -285,7 +673,7 @@ pub fn set_coloring_mode(mode: Mode) {
 /// see [`Mode::from_env`] for details on which env vars are supported
 #[inline]
 #[cfg(feature = "std")]
-#[cfg_attr(doc, doc(cfg(feature = "std")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 pub fn set_coloring_mode_from_env() {
     if cfg!(feature = "strip-colors") {
         return;
@@ -331,13 +719,278 @@ pub fn set_default_stream(stream: Stream) {
 ///
 /// if one was not set by [`set_default_stream`], then this returns [`Stream::AlwaysColor`]. Otherwise return
 /// the value specified in [`set_default_stream`]
+///
+/// Inside of [`with_default_stream`] (or while formatting a value wrapped by [`stdout`] or [`stderr`]),
+/// this returns the stream given there instead.
 #[inline]
 pub fn get_default_stream() -> Stream {
+    #[cfg(feature = "std")]
+    if let Some(stream) = SCOPED_STREAM.with(core::cell::Cell::get) {
+        return stream;
+    }
+
     Stream::decode(DEFAULT_STREAM.load(core::sync::atomic::Ordering::Acquire))
 }
 
+#[cfg(feature = "std")]
+std::thread_local! {
+    static SCOPED_STREAM: core::cell::Cell<Option<Stream>> = const { core::cell::Cell::new(None) };
+}
+
+/// Run `f` with `stream` as the default stream on the current thread, see [`get_default_stream`]
+///
+/// This is useful to color all values written to the same output the same way, without
+/// setting the stream on each value.
+///
+/// ```rust
+/// use colorz::{Colorize, mode::{self, Stream}};
+///
+/// mode::with_default_stream(Stream::Stderr, || {
+///     assert_eq!(mode::get_default_stream(), Stream::Stderr);
+///     eprintln!("{}: something went wrong", "error".red());
+/// });
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn with_default_stream<R>(stream: Stream, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Stream>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_STREAM.with(|scoped| scoped.set(self.0));
+        }
+    }
+
+    let _restore = Restore(SCOPED_STREAM.with(|scoped| scoped.replace(Some(stream))));
+    f()
+}
+
+/// A value which is formatted with a given default stream, see [`stdout`] and [`stderr`]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WithDefaultStream<T> {
+    /// The default stream used while formatting the value
+    pub stream: Stream,
+    /// The value to format
+    pub value: T,
+}
+
+/// Format `value` with [`Stream::Stdout`] as the default stream, for values written to stdout
+///
+/// All [`StyledValue`]s inside of `value` which don't have a stream will detect coloring based on stdout.
+///
+/// ```rust
+/// use colorz::{Colorize, mode};
+///
+/// let name = "colorz";
+/// println!("{}", mode::stdout(format_args!("{} {}", "hello".green(), name.bold())));
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub const fn stdout<T>(value: T) -> WithDefaultStream<T> {
+    WithDefaultStream {
+        stream: Stream::Stdout,
+        value,
+    }
+}
+
+/// Format `value` with [`Stream::Stderr`] as the default stream, for values written to stderr
+///
+/// All [`StyledValue`]s inside of `value` which don't have a stream will detect coloring based on stderr.
+///
+/// ```rust
+/// use colorz::{Colorize, mode};
+///
+/// eprintln!("{}", mode::stderr(format_args!("{}: something went wrong", "error".red())));
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub const fn stderr<T>(value: T) -> WithDefaultStream<T> {
+    WithDefaultStream {
+        stream: Stream::Stderr,
+        value,
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: core::fmt::Display> core::fmt::Display for WithDefaultStream<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        with_default_stream(self.stream, || self.value.fmt(f))
+    }
+}
+
+/// Set if [`StyledValue`]s should downgrade colors which the stream doesn't support
+///
+/// By default, if a [`StyledValue`] uses a color which isn't supported by it's stream, then
+/// it isn't colored at all. With auto-downgrade enabled, unsupported colors are instead
+/// converted to the nearest supported color (RGB to Xterm to ANSI) when formatting,
+/// see [`Color::downgrade`](crate::Color::downgrade).
+///
+/// ```rust
+/// colorz::mode::set_auto_downgrade(true);
+/// assert!(colorz::mode::get_auto_downgrade());
+/// ```
+#[inline]
+pub fn set_auto_downgrade(enabled: bool) {
+    AUTO_DOWNGRADE.store(enabled, core::sync::atomic::Ordering::Relaxed)
+}
+
+/// Get if [`StyledValue`]s should downgrade colors which the stream doesn't support, see [`set_auto_downgrade`]
+#[inline]
+pub fn get_auto_downgrade() -> bool {
+    AUTO_DOWNGRADE.load(core::sync::atomic::Ordering::Relaxed)
+}
+
+/// Set if [`StyledValue`]s should write bright ANSI colors as bold normal colors
+///
+/// Some terminals (like older terminal emulators and some serial consoles) don't support the
+/// aixterm bright color codes (`90`-`97` and `100`-`107`). With the bright fallback enabled,
+/// a bright foreground color (like [`ansi::BrightRed`](crate::ansi::BrightRed)) is written as
+/// bold and the normal color (`1;31` instead of `91`), since most of these terminals show bold text
+/// in the bright color. Bright background colors are written as the normal background color.
+/// If bold is [suppressed](suppress_effects), only the normal color is written.
+///
+/// ```rust
+/// use colorz::{Colorize, Effect, EffectFlags, mode::{self, Stream}};
+///
+/// mode::set_bright_fallback(true);
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(
+///     "error".bright_red().stream(Stream::AlwaysColor).to_string(),
+///     "\x1b[1m\x1b[31merror\x1b[22m\x1b[39m",
+/// );
+///
+/// mode::suppress_effects(EffectFlags::from_array([Effect::Bold]));
+/// assert_eq!(
+///     "error".bright_red().stream(Stream::AlwaysColor).to_string(),
+///     "\x1b[31merror\x1b[39m",
+/// );
+/// mode::suppress_effects(EffectFlags::new());
+/// # }
+///
+/// mode::set_bright_fallback(false);
+/// ```
+#[inline]
+pub fn set_bright_fallback(enabled: bool) {
+    BRIGHT_FALLBACK.store(enabled, core::sync::atomic::Ordering::Relaxed)
+}
+
+/// Get if [`StyledValue`]s should write bright ANSI colors as bold normal colors, see [`set_bright_fallback`]
+#[inline]
+pub fn get_bright_fallback() -> bool {
+    BRIGHT_FALLBACK.load(core::sync::atomic::Ordering::Relaxed)
+}
+
+/// Set which effects are removed from every [`StyledValue`] when it's formatted
+///
+/// This replaces any previously suppressed effects, so use [`EffectFlags::new()`] to stop suppressing effects.
+/// This is useful to globally disable effects which are an accessibility problem (like blinking text),
+/// while keeping the colors and other effects.
+///
+/// ```rust
+/// use colorz::{Colorize, Effect, EffectFlags, mode::{self, Stream}};
+///
+/// mode::suppress_effects(EffectFlags::from_array([Effect::Blink, Effect::BlinkFast]));
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(
+///     "alert".red().blink().bold().stream(Stream::AlwaysColor).to_string(),
+///     "\x1b[1m\x1b[31malert\x1b[22m\x1b[39m",
+/// );
+/// # }
+///
+/// mode::suppress_effects(EffectFlags::new());
+/// assert!(mode::get_suppressed_effects().is_plain());
+/// ```
+#[inline]
+pub fn suppress_effects(effects: EffectFlags) {
+    SUPPRESSED_EFFECTS.store(effects.bits(), core::sync::atomic::Ordering::Relaxed)
+}
+
+/// Get which effects are removed from every [`StyledValue`] when it's formatted, see [`suppress_effects`]
+#[inline]
+pub fn get_suppressed_effects() -> EffectFlags {
+    EffectFlags::from_bits_truncate(SUPPRESSED_EFFECTS.load(core::sync::atomic::Ordering::Relaxed))
+}
+
+/// A transform applied to every color of a [`StyledValue`] when it's formatted, see [`set_color_transform`]
+pub type ColorTransform = fn(crate::Color) -> Option<crate::Color>;
+
+/// Transform every color of a [`StyledValue`] right before it's written, or stop transforming colors by passing `None`
+///
+/// The transform is called with each color of the style (after [`palette`](crate::palette) remapping), and the
+/// color it returns is written instead. Returning `None` removes the color. This can be used for a global grayscale
+/// mode, dimming, or replacing colors without changing any of the places where the colors are used.
+///
+/// If the stream doesn't support the kind of color returned by the transform, then the color is downgraded to the
+/// nearest color the stream supports (like with [`set_auto_downgrade`]).
+///
+/// ```rust
+/// use colorz::{Color, Colorize, mode::{self, Stream}, rgb::RgbColor};
+///
+/// fn grayscale(color: Color) -> Option<Color> {
+///     let RgbColor { red, green, blue } = color.to_rgb();
+///     let gray = ((red as u16 * 3 + green as u16 * 6 + blue as u16) / 10) as u8;
+///     Some(Color::Rgb(RgbColor { red: gray, green: gray, blue: gray }))
+/// }
+///
+/// mode::set_color_transform(Some(grayscale));
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(
+///     "gray".fg(RgbColor { red: 255, green: 0, blue: 0 }).stream(Stream::AlwaysColor).to_string(),
+///     "\x1b[38;2;76;76;76mgray\x1b[39m",
+/// );
+/// # }
+///
+/// mode::set_color_transform(None);
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn set_color_transform(transform: Option<ColorTransform>) {
+    let mut current = COLOR_TRANSFORM
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    *current = transform;
+    COLOR_TRANSFORM_ACTIVE.store(transform.is_some(), core::sync::atomic::Ordering::Relaxed);
+}
+
+/// Get the transform applied to every color of a [`StyledValue`], see [`set_color_transform`]
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn get_color_transform() -> Option<ColorTransform> {
+    if !COLOR_TRANSFORM_ACTIVE.load(core::sync::atomic::Ordering::Relaxed) {
+        return None;
+    }
+
+    *COLOR_TRANSFORM
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// The highest fidelity color kind supported by the stream
+pub(crate) fn max_color_kind(stream: Option<Stream>) -> ColorKind {
+    [ColorKind::Rgb, ColorKind::Xterm, ColorKind::Ansi]
+        .into_iter()
+        .find(|&kind| should_color(stream, &[kind]))
+        .unwrap_or(ColorKind::NoColor)
+}
+
 /// Should the given stream and color kinds be colored based on the coloring mode.
 ///
+/// This is the same check that [`StyledValue`] uses, so if you write colors manually
+/// (for example using [`WriteColor`](crate::WriteColor)), then you can use this to respect the user's
+/// coloring preferences. Pass the [`ColorKind`] of every color you are going to write, and `None` for the
+/// stream to use the [default stream](get_default_stream).
+///
 /// for example, you can use this to decide if you need to color based on ANSI
 ///
 /// ```rust
@@ -351,6 +1004,34 @@ pub fn get_default_stream() -> Stream {
 /// }
 /// ```
 ///
+/// or to decide whether a pair of colors should be written to stderr
+///
+/// ```rust
+/// use colorz::{mode::{self, Stream}, ansi, xterm, WriteColor};
+///
+/// struct Warning;
+///
+/// impl core::fmt::Display for Warning {
+///     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+///         let kinds = [ansi::Yellow.color_kind(), xterm::Gray0.color_kind()];
+///
+///         if mode::should_color(Stream::Stderr, &kinds) {
+///             ansi::Yellow.fmt_foreground(f)?;
+///             xterm::Gray0.fmt_background(f)?;
+///             f.write_str("warning")?;
+///             f.write_str("\x1b[39;49m")
+///         } else {
+///             f.write_str("warning")
+///         }
+///     }
+/// }
+///
+/// eprintln!("{}", Warning);
+///
+/// mode::set_coloring_mode(mode::Mode::Never);
+/// assert_eq!(Warning.to_string(), "warning");
+/// ```
+///
 /// The `stream` provided may be used to detect whether it supports coloring.
 /// For example, if the `std` feature is enabled, but not the `supports-colors` feature
 /// then using `Stream::StdErr` will check if `stderr` is a terminal and allow coloring
@@ -369,6 +1050,10 @@ pub fn get_default_stream() -> Stream {
 ///     * `Stream::AlwaysColor` -> DO COLOR
 ///     * `Stream::NeverColor` -> NO COLOR
 ///     * `Stream::Stdout`/`Stream::Stderr` -> detect coloring using `std` or `support-color` (see docs on feature flags for details)
+/// * if the current thread's default stream is set (via `mode::with_default_stream`, `mode::stdout` or `mode::stderr`) to
+///     * `Stream::AlwaysColor` -> DO COLOR
+///     * `Stream::NeverColor` -> NO COLOR
+///     * `Stream::Stdout`/`Stream::Stderr` -> detect coloring using `std` or `support-color` (see docs on feature flags for details)
 /// * if global stream is set to
 ///     * `Stream::AlwaysColor` -> DO COLOR
 ///     * `Stream::NeverColor` -> NO COLOR
@@ -399,7 +1084,7 @@ pub fn get_default_stream() -> Stream {
 /// }
 /// ```
 #[inline]
-pub fn should_color(stream: Option<Stream>, kinds: &[ColorKind]) -> bool {
+pub fn should_color(stream: impl Into<Option<Stream>>, kinds: &[ColorKind]) -> bool {
     if cfg!(feature = "strip-colors") {
         return false;
     }
@@ -410,7 +1095,7 @@ pub fn should_color(stream: Option<Stream>, kinds: &[ColorKind]) -> bool {
         Mode::Detect => (),
     }
 
-    let stream = stream.unwrap_or_else(get_default_stream);
+    let stream = stream.into().unwrap_or_else(get_default_stream);
 
     let is_stdout = match stream {
         Stream::Stdout => true,
@@ -422,104 +1107,527 @@ pub fn should_color(stream: Option<Stream>, kinds: &[ColorKind]) -> bool {
     should_color_slow(is_stdout, kinds)
 }
 
+/// Should a given value with the provided color kinds be colored when written to `writer`
+///
+/// This is like [`should_color`], but for any writer which may be a terminal (like a file, or a handle
+/// to `/dev/tty`), instead of only stdout or stderr. The global coloring mode and the `strip-colors`
+/// feature are respected in the same way, and if the writer isn't a terminal then no coloring is done.
+///
+/// Which color kinds are supported can only be detected for stdout and stderr, so this assumes that
+/// the writer is the same terminal as stdout (or stderr, if stdout isn't a terminal), and supports the
+/// same color kinds. If neither is a terminal, then all color kinds are assumed to be supported.
+/// For stdout and stderr themselves, prefer [`should_color`] with [`Stream::Stdout`] or [`Stream::Stderr`].
+///
+/// On Windows, this doesn't enable ANSI escape sequences for the writer, since
+/// [`enable_windows_ansi`](crate::enable_windows_ansi) only applies to the console of stdout and stderr.
+///
+/// ```rust
+/// use colorz::mode::{self, ColorKind};
+///
+/// let file = std::fs::File::open("Cargo.toml").unwrap();
+///
+/// mode::set_coloring_mode(mode::Mode::Detect);
+/// assert!(!mode::should_color_for(&file, &[ColorKind::Ansi]));
+/// ```
 #[inline]
-#[allow(clippy::missing_const_for_fn)]
-#[cfg(all(not(feature = "std"), not(feature = "supports-color")))]
-fn should_color_slow(_is_stdout: bool, _kinds: &[ColorKind]) -> bool {
-    true
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn should_color_for<W: std::io::IsTerminal + ?Sized>(writer: &W, kinds: &[ColorKind]) -> bool {
+    use std::io::IsTerminal;
+
+    if cfg!(feature = "strip-colors") {
+        return false;
+    }
+
+    match get_coloring_mode() {
+        Mode::Always => return true,
+        Mode::Never => return false,
+        Mode::Detect => (),
+    }
+
+    if !writer.is_terminal() {
+        return false;
+    }
+
+    if std::io::stdout().is_terminal() {
+        should_color_slow(true, kinds)
+    } else if std::io::stderr().is_terminal() {
+        should_color_slow(false, kinds)
+    } else {
+        true
+    }
 }
 
-#[cold]
-#[cfg(all(feature = "std", not(feature = "supports-color")))]
-fn should_color_slow(is_stdout: bool, _kinds: &[ColorKind]) -> bool {
+fn should_color_slow(is_stdout: bool, kinds: &[ColorKind]) -> bool {
     use core::sync::atomic::Ordering;
-    use std::io::IsTerminal;
 
     let support_ref = match is_stdout {
         true => &STDOUT_SUPPORT,
         false => &STDERR_SUPPORT,
     };
 
-    #[cold]
-    #[inline(never)]
-    fn detect(is_stdout: bool, support: &AtomicU8) -> bool {
-        let s = if is_stdout {
-            std::io::stdout().is_terminal()
+    let support = match support_ref.load(Ordering::Acquire) {
+        ColorSupport::DETECT => {
+            let support = detect_with_detector(is_stdout);
+            support_ref.store(support.encode(), Ordering::Release);
+            support
+        }
+        support => ColorSupport::decode(support),
+    };
+
+    support.supports(kinds)
+}
+
+#[inline]
+#[cfg(feature = "std")]
+fn detect_with_detector(is_stdout: bool) -> ColorSupport {
+    let detector = *DETECTOR
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    match detector {
+        Some(detector) => detector(if is_stdout {
+            Stream::Stdout
         } else {
-            std::io::stderr().is_terminal()
-        };
+            Stream::Stderr
+        }),
+        None => detect(is_stdout),
+    }
+}
+
+#[inline]
+#[cfg(not(feature = "std"))]
+fn detect_with_detector(is_stdout: bool) -> ColorSupport {
+    detect(is_stdout)
+}
 
-        support.store(s as u8, Ordering::Relaxed);
+#[inline]
+#[allow(clippy::missing_const_for_fn)]
+#[cfg(all(not(feature = "std"), not(feature = "supports-color")))]
+fn detect(_is_stdout: bool) -> ColorSupport {
+    ColorSupport::RGB
+}
 
-        core::sync::atomic::fence(Ordering::SeqCst);
+#[cold]
+#[inline(never)]
+#[cfg(all(feature = "std", not(feature = "supports-color")))]
+fn detect(is_stdout: bool) -> ColorSupport {
+    use std::io::IsTerminal;
 
-        s
-    }
+    let is_terminal = if is_stdout {
+        std::io::stdout().is_terminal()
+    } else {
+        std::io::stderr().is_terminal()
+    };
 
-    match support_ref.load(Ordering::Acquire) {
-        ColorSupport::DETECT => detect(is_stdout, support_ref),
-        0 => false,
-        _ => true,
+    if is_terminal && crate::enable_windows_ansi() {
+        ColorSupport::RGB
+    } else {
+        ColorSupport::NONE
     }
 }
 
 #[cold]
+#[inline(never)]
 #[cfg(feature = "supports-color")]
-fn should_color_slow(is_stdout: bool, kinds: &[ColorKind]) -> bool {
-    use core::sync::atomic::Ordering;
+fn detect(is_stdout: bool) -> ColorSupport {
+    let stream = match is_stdout {
+        true => supports_color::Stream::Stdout,
+        false => supports_color::Stream::Stderr,
+    };
+
+    supports_color::on(stream)
+        .filter(|_| crate::enable_windows_ansi())
+        .map_or(ColorSupport::NONE, |level| ColorSupport {
+            ansi: level.has_basic,
+            xterm: level.has_256,
+            rgb: level.has_16m,
+        })
+}
 
-    use supports_color::Stream;
+/// A custom color support detector, see [`set_detector`]
+pub type Detector = fn(Stream) -> ColorSupport;
 
-    let (stream, support_ref) = match is_stdout {
-        true => (Stream::Stdout, &STDOUT_SUPPORT),
-        false => (Stream::Stderr, &STDERR_SUPPORT),
+/// Replace the built-in color support detection (via `supports-color` or `IsTerminal`) with `detector`,
+/// or go back to the built-in detection by passing `None`
+///
+/// The detector is called with [`Stream::Stdout`] or [`Stream::Stderr`] the first time the color support
+/// for that stream is needed, and the result is cached until [`redetect`] is called. Setting the detector
+/// also clears the cached color support. Streams overridden by [`set_stream_support`] don't use the detector.
+///
+/// Without the `std` feature, use [`set_stream_support`] to set the color support instead.
+///
+/// ```rust
+/// use colorz::mode::{self, ColorKind, ColorSupport, Stream};
+///
+/// fn serial_console(stream: Stream) -> ColorSupport {
+///     match stream {
+///         Stream::Stdout => ColorSupport::ANSI,
+///         _ => ColorSupport::NONE,
+///     }
+/// }
+///
+/// mode::set_detector(Some(serial_console));
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert!(mode::should_color(Some(Stream::Stdout), &[ColorKind::Ansi]));
+/// # }
+/// assert!(!mode::should_color(Some(Stream::Stdout), &[ColorKind::Xterm]));
+/// assert!(!mode::should_color(Some(Stream::Stderr), &[ColorKind::Ansi]));
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn set_detector(detector: Option<Detector>) {
+    *DETECTOR
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = detector;
+    redetect();
+}
+
+/// Override which color kinds are supported by the terminal for [`Stream::Stdout`] or [`Stream::Stderr`],
+/// instead of detecting it
+///
+/// This is ignored for [`Stream::AlwaysColor`] and [`Stream::NeverColor`]. Use [`redetect`] to
+/// go back to detecting the supported colors.
+///
+/// ```rust
+/// use colorz::mode::{self, ColorKind, ColorSupport, Stream};
+///
+/// mode::set_stream_support(Stream::Stdout, ColorSupport::XTERM);
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert!(mode::should_color(Some(Stream::Stdout), &[ColorKind::Xterm]));
+/// # }
+/// assert!(!mode::should_color(Some(Stream::Stdout), &[ColorKind::Rgb]));
+/// ```
+#[inline]
+pub fn set_stream_support(stream: Stream, support: ColorSupport) {
+    let support_ref = match stream {
+        Stream::Stdout => &STDOUT_SUPPORT,
+        Stream::Stderr => &STDERR_SUPPORT,
+        Stream::AlwaysColor | Stream::NeverColor => return,
     };
 
-    let support = support_ref.load(Ordering::Acquire);
-
-    #[cold]
-    #[inline(never)]
-    fn detect(s: Stream, support: &AtomicU8) -> ColorSupport {
-        let s = supports_color::on(s).map_or(
-            ColorSupport {
-                ansi: false,
-                xterm: false,
-                rgb: false,
-            },
-            |level| ColorSupport {
-                ansi: level.has_basic,
-                xterm: level.has_256,
-                rgb: level.has_16m,
-            },
-        );
+    support_ref.store(support.encode(), core::sync::atomic::Ordering::Release);
+}
+
+/// Clear the detected (or overridden) color support for stdout and stderr and the hyperlink support,
+/// so it's detected again the next time it's needed
+///
+/// This is useful if the environment changed since the last detection (for example, `TERM` or
+/// `COLORTERM` were changed, or stdout was redirected).
+///
+/// ```rust
+/// use colorz::mode::{self, ColorKind, ColorSupport, Stream};
+///
+/// mode::set_stream_support(Stream::Stderr, ColorSupport::NONE);
+/// assert!(!mode::should_color(Some(Stream::Stderr), &[ColorKind::Ansi]));
+///
+/// mode::redetect();
+/// ```
+#[inline]
+pub fn redetect() {
+    STDOUT_SUPPORT.store(ColorSupport::DETECT, core::sync::atomic::Ordering::Release);
+    STDERR_SUPPORT.store(ColorSupport::DETECT, core::sync::atomic::Ordering::Release);
+    HYPERLINK_SUPPORT.store(HYPERLINKS_DETECT, core::sync::atomic::Ordering::Release);
+}
+
+const HYPERLINKS_DETECT: u8 = 0;
+const HYPERLINKS_UNSUPPORTED: u8 = 1;
+const HYPERLINKS_SUPPORTED: u8 = 2;
+
+/// Override if the terminal supports hyperlinks (OSC 8), instead of detecting it
+///
+/// Use [`redetect`] to go back to detecting hyperlink support.
+///
+/// ```rust
+/// use colorz::mode::{self, Stream};
+///
+/// mode::set_hyperlink_support(false);
+/// assert!(!mode::should_hyperlink(Stream::AlwaysColor));
+/// ```
+#[inline]
+pub fn set_hyperlink_support(supported: bool) {
+    let support = match supported {
+        true => HYPERLINKS_SUPPORTED,
+        false => HYPERLINKS_UNSUPPORTED,
+    };
+
+    HYPERLINK_SUPPORT.store(support, core::sync::atomic::Ordering::Release);
+}
 
-        support.store(s.encode(), Ordering::Relaxed);
+/// Should a [`Hyperlink`](crate::Hyperlink) written to `stream` include the link, or only it's text
+///
+/// Hyperlinks are only written if the value would be colored (see [`should_color`]), and the terminal
+/// supports hyperlinks. Terminals which don't support hyperlinks may print the escape sequences literally,
+/// so this is detected from the environment with the same heuristics as the `supports-hyperlinks` crate
+/// (`FORCE_HYPERLINK`, `TERM_PROGRAM`, `VTE_VERSION`, etc.), unless overridden with [`set_hyperlink_support`].
+/// Without the `std` feature, hyperlinks are only written if enabled with [`set_hyperlink_support`].
+///
+/// ```rust
+/// use colorz::mode::{self, Stream};
+///
+/// mode::set_hyperlink_support(true);
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert!(mode::should_hyperlink(Stream::AlwaysColor));
+/// # }
+/// assert!(!mode::should_hyperlink(Stream::NeverColor));
+/// ```
+#[inline]
+pub fn should_hyperlink(stream: impl Into<Option<Stream>>) -> bool {
+    use core::sync::atomic::Ordering;
 
-        core::sync::atomic::fence(Ordering::SeqCst);
+    if !should_color(stream, &[ColorKind::Ansi]) {
+        return false;
+    }
 
-        s
+    match HYPERLINK_SUPPORT.load(Ordering::Acquire) {
+        HYPERLINKS_DETECT => {
+            let supported = detect_hyperlinks();
+            set_hyperlink_support(supported);
+            supported
+        }
+        support => support == HYPERLINKS_SUPPORTED,
     }
+}
 
-    let support = if support == ColorSupport::DETECT {
-        detect(stream, support_ref)
-    } else {
-        ColorSupport::decode(support)
-    };
+#[inline]
+#[allow(clippy::missing_const_for_fn)]
+#[cfg(not(feature = "std"))]
+fn detect_hyperlinks() -> bool {
+    false
+}
 
-    for &kind in kinds {
-        let supported = match kind {
-            ColorKind::Ansi => support.ansi,
-            ColorKind::Xterm => support.xterm,
-            ColorKind::Rgb => support.rgb,
-            ColorKind::NoColor => continue,
-        };
+#[cold]
+#[inline(never)]
+#[cfg(feature = "std")]
+fn detect_hyperlinks() -> bool {
+    use std::env::var;
+
+    if let Ok(force) = var("FORCE_HYPERLINK") {
+        return force.trim() != "0";
+    }
+
+    if var("DOMTERM").is_ok() || var("WT_SESSION").is_ok() || var("KONSOLE_VERSION").is_ok() {
+        return true;
+    }
+
+    // VTE based terminals (like GNOME Terminal) support hyperlinks since 0.50
+    if let Ok(version) = var("VTE_VERSION") {
+        if version.parse::<u32>().is_ok_and(|version| version >= 5000) {
+            return true;
+        }
+    }
 
-        if !supported {
-            return false;
+    if let Ok(program) = var("TERM_PROGRAM") {
+        if matches!(
+            program.as_str(),
+            "Hyper" | "iTerm.app" | "terminology" | "WezTerm" | "vscode" | "ghostty"
+        ) {
+            return true;
         }
     }
 
-    true
+    if let Ok(term) = var("TERM") {
+        if matches!(
+            term.as_str(),
+            "xterm-kitty" | "alacritty" | "alacritty-direct" | "xterm-ghostty"
+        ) {
+            return true;
+        }
+    }
+
+    var("COLORTERM").is_ok_and(|term| term == "xfce4-terminal")
+}
+
+/// Query the terminal for the colors it actually uses, waiting at most `timeout` for it to respond
+///
+/// This asks the terminal for its 256 indexed colors (OSC 4) and the default foreground
+/// and background colors (OSC 10/11), and caches the result so that it's available from
+/// [`terminal_palette`].
+///
+/// Any color the terminal didn't report in time is left as `None` in the palette.
+/// This talks to the controlling terminal (`/dev/tty`) directly, so it works even if
+/// stdout/stderr are redirected. It returns an error if there is no controlling terminal,
+/// or on platforms other than unix. The terminal is put into raw mode by running the external
+/// `stty` command, and this blocks until the terminal responds or `timeout` passes.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// if let Ok(palette) = colorz::mode::query_terminal_palette(Duration::from_millis(100)) {
+///     println!("the terminal's red is {:?}", palette.colors[1]);
+/// }
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn query_terminal_palette(timeout: std::time::Duration) -> std::io::Result<TerminalPalette> {
+    let palette = crate::query::query(timeout, true)?;
+    set_terminal_palette(Some(palette));
+    Ok(palette)
+}
+
+/// Get the terminal palette found by [`query_terminal_palette`] or set via [`set_terminal_palette`]
+///
+/// Returns `None` if the terminal hasn't been queried yet
+///
+/// ```rust
+/// assert_eq!(colorz::mode::terminal_palette(), None);
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn terminal_palette() -> Option<TerminalPalette> {
+    *TERMINAL_PALETTE
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Call `f` with the terminal palette, without copying it, or return `None` if it isn't known
+#[cfg(feature = "std")]
+pub(crate) fn with_terminal_palette<R>(f: impl FnOnce(&TerminalPalette) -> R) -> Option<R> {
+    TERMINAL_PALETTE
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .as_ref()
+        .map(f)
+}
+
+/// Override the terminal palette, or clear it by passing `None`
+///
+/// ```rust
+/// use colorz::{mode::TerminalPalette, rgb::RgbColor};
+///
+/// let mut palette = TerminalPalette::EMPTY;
+/// palette.background = Some(RgbColor { red: 0, green: 0, blue: 0 });
+///
+/// colorz::mode::set_terminal_palette(Some(palette));
+/// assert_eq!(colorz::mode::terminal_palette(), Some(palette));
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn set_terminal_palette(palette: Option<TerminalPalette>) {
+    *TERMINAL_PALETTE
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = palette;
+}
+
+/// Whether the terminal has a dark or light background, see [`background_kind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackgroundKind {
+    /// A dark background, where light text is more readable
+    Dark,
+    /// A light background, where dark text is more readable
+    Light,
+}
+
+#[cfg(feature = "std")]
+impl BackgroundKind {
+    /// Classify a background color
+    ///
+    /// The background is dark if white text has a higher contrast ratio than black text on it,
+    /// see [`RgbColor::contrasting_text`](crate::rgb::RgbColor::contrasting_text)
+    ///
+    /// ```rust
+    /// use colorz::{mode::BackgroundKind, rgb::RgbColor};
+    ///
+    /// assert_eq!(BackgroundKind::from_color(RgbColor { red: 30, green: 30, blue: 46 }), BackgroundKind::Dark);
+    /// assert_eq!(BackgroundKind::from_color(RgbColor { red: 253, green: 246, blue: 227 }), BackgroundKind::Light);
+    /// ```
+    #[inline]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn from_color(background: crate::rgb::RgbColor) -> Self {
+        match background.contrasting_text() {
+            crate::rgb::RgbColor {
+                red: 255,
+                green: 255,
+                blue: 255,
+            } => Self::Dark,
+            _ => Self::Light,
+        }
+    }
+}
+
+/// Query the terminal for its default foreground and background colors (OSC 10/11), waiting at
+/// most `timeout` for it to respond, and return whether the background is dark or light
+///
+/// The reported colors are cached in the [`terminal_palette`], so later calls to [`background_kind`]
+/// don't need to query the terminal again. Returns `Ok(None)` if the terminal didn't report its
+/// background color in time, and errors in the same cases as [`query_terminal_palette`].
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use colorz::mode::BackgroundKind;
+///
+/// let kind = colorz::mode::query_background_kind(Duration::from_millis(100))
+///     .ok()
+///     .flatten()
+///     .unwrap_or(BackgroundKind::Dark);
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn query_background_kind(
+    timeout: std::time::Duration,
+) -> std::io::Result<Option<BackgroundKind>> {
+    let queried = crate::query::query(timeout, false)?;
+
+    let mut palette = TERMINAL_PALETTE
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let palette = palette.get_or_insert(TerminalPalette::EMPTY);
+    palette.foreground = queried.foreground.or(palette.foreground);
+    palette.background = queried.background.or(palette.background);
+
+    Ok(queried.background.map(BackgroundKind::from_color))
+}
+
+/// Whether the terminal has a dark or light background, if it's known
+///
+/// This uses the background color in the [`terminal_palette`] (from [`query_background_kind`],
+/// [`query_terminal_palette`] or [`set_terminal_palette`]). If the terminal hasn't been
+/// queried, this falls back to the `COLORFGBG` environment variable, which some terminals set
+/// to `foreground;background` (using ANSI color indices). This never queries the terminal itself.
+///
+/// ```rust
+/// use colorz::{mode::{BackgroundKind, TerminalPalette}, rgb::RgbColor};
+///
+/// let mut palette = TerminalPalette::EMPTY;
+/// palette.background = Some(RgbColor { red: 255, green: 255, blue: 255 });
+/// colorz::mode::set_terminal_palette(Some(palette));
+///
+/// assert_eq!(colorz::mode::background_kind(), Some(BackgroundKind::Light));
+/// ```
+#[inline]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn background_kind() -> Option<BackgroundKind> {
+    if let Some(background) = terminal_palette().and_then(|palette| palette.background) {
+        return Some(BackgroundKind::from_color(background));
+    }
+
+    let colors = std::env::var("COLORFGBG").ok()?;
+    background_kind_from_colorfgbg(&colors)
+}
+
+/// Parse `COLORFGBG`, where the background is the last `;` separated field
+#[cfg(feature = "std")]
+fn background_kind_from_colorfgbg(colors: &str) -> Option<BackgroundKind> {
+    let background = colors.rsplit(';').next()?.trim();
+
+    if background == "default" {
+        return None;
+    }
+
+    match background.parse::<u8>().ok()? {
+        0..=6 | 8 => Some(BackgroundKind::Dark),
+        7 | 9..=15 => Some(BackgroundKind::Light),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -573,6 +1681,11 @@ mod test {
         test_case_insensitive_mode_from_str(*b"detect", Mode::Detect);
     }
 
+    #[test]
+    fn mode_from_str_auto() {
+        test_case_insensitive_mode_from_str(*b"auto", Mode::Detect);
+    }
+
     #[test]
     fn stream_from_str_never() {
         test_case_insensitive_stream_from_str(*b"never", Stream::NeverColor);
@@ -592,4 +1705,59 @@ mod test {
     fn stream_from_str_stderr() {
         test_case_insensitive_stream_from_str(*b"stderr", Stream::Stderr);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn mode_from_env() {
+        let from_env = |vars: &[(&str, &str)]| {
+            Mode::from_env_vars("COLORZ", |name| {
+                vars.iter()
+                    .find(|&&(var, _)| var == name)
+                    .map(|&(_, value)| value.into())
+            })
+        };
+
+        assert_eq!(from_env(&[]), None);
+        assert_eq!(from_env(&[("NO_COLOR", "")]), Some(Mode::Never));
+        assert_eq!(
+            from_env(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]),
+            Some(Mode::Never)
+        );
+        assert_eq!(from_env(&[("CLICOLOR_FORCE", "1")]), Some(Mode::Always));
+        assert_eq!(from_env(&[("CLICOLOR_FORCE", "0")]), None);
+        assert_eq!(from_env(&[("FORCE_COLOR", "")]), None);
+        assert_eq!(from_env(&[("CLICOLOR", "0")]), Some(Mode::Never));
+        assert_eq!(from_env(&[("CLICOLOR", "1")]), None);
+        assert_eq!(from_env(&[("TERM", "dumb")]), Some(Mode::Never));
+        assert_eq!(
+            from_env(&[("TERM", "dumb"), ("FORCE_COLOR", "1")]),
+            Some(Mode::Always)
+        );
+
+        assert_eq!(
+            from_env(&[("COLORZ", "always"), ("NO_COLOR", "")]),
+            Some(Mode::Always)
+        );
+        assert_eq!(
+            from_env(&[("COLORZ", "Auto"), ("NO_COLOR", "")]),
+            Some(Mode::Detect)
+        );
+        assert_eq!(from_env(&[("COLORZ", "never")]), Some(Mode::Never));
+        assert_eq!(
+            from_env(&[("COLORZ", "sometimes"), ("NO_COLOR", "")]),
+            Some(Mode::Never)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn background_from_colorfgbg() {
+        use super::{background_kind_from_colorfgbg as parse, BackgroundKind};
+
+        assert_eq!(parse("15;0"), Some(BackgroundKind::Dark));
+        assert_eq!(parse("0;15"), Some(BackgroundKind::Light));
+        assert_eq!(parse("0;default;15"), Some(BackgroundKind::Light));
+        assert_eq!(parse("15;default"), None);
+        assert_eq!(parse(""), None);
+    }
 }
@@ -0,0 +1,121 @@
+//! Overriding how the 16 ANSI colors are rendered
+//!
+//! Each terminal has it's own palette for the ANSI colors, so `ansi::Red` may look very different
+//! across terminals. To get consistent colors, the ANSI colors can be remapped to specific RGB colors.
+//! When a [`StyledValue`](crate::StyledValue) or [`StyledText`](crate::StyledText) is formatted for a
+//! stream which supports RGB colors, the remapped colors are written instead of the ANSI colors.
+//! Otherwise (or if the value's colors are [capped](crate::StyledValue::max_color) below RGB colors)
+//! the ANSI colors are written as normal.
+//!
+//! This only affects how styled values are formatted, [`Style::apply`](crate::Style::apply) and the
+//! other escape sequence methods always write the ANSI colors.
+//!
+//! ```rust
+//! use colorz::{Colorize, ansi, palette, mode::{ColorKind, Stream}, rgb::RgbColor};
+//!
+//! let brand_red = RgbColor { red: 0xe0, green: 0x1b, blue: 0x24 };
+//! palette::set(ansi::AnsiColor::Red, Some(brand_red));
+//! assert_eq!(palette::get(ansi::AnsiColor::Red), Some(brand_red));
+//!
+//! # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+//! assert_eq!("error".red().stream(Stream::AlwaysColor).to_string(), "\x1b[38;2;224;27;36merror\x1b[39m");
+//! assert_eq!(
+//!     "error".red().stream(Stream::AlwaysColor).max_color(ColorKind::Xterm).to_string(),
+//!     "\x1b[31merror\x1b[39m",
+//! );
+//! # }
+//!
+//! palette::reset();
+//! # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+//! assert_eq!("error".red().stream(Stream::AlwaysColor).to_string(), "\x1b[31merror\x1b[39m");
+//! # }
+//! ```
+
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use crate::{
+    ansi::AnsiColor,
+    mode::{ColorKind, Stream},
+    rgb::RgbColor,
+    Color, Style,
+};
+
+/// Set when any color has been remapped, so that formatting can skip remapping otherwise
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// The remapped colors, encoded as `0x01rrggbb`, or `0` if the color isn't remapped
+static REMAPPED: [AtomicU32; 16] = [const { AtomicU32::new(0) }; 16];
+
+const REMAPPED_TAG: u32 = 1 << 24;
+
+/// Remap an ANSI color to an RGB color, or remove the remapping by passing `None`
+///
+/// [`AnsiColor::Default`] can't be remapped, and is ignored
+#[inline]
+pub fn set(color: AnsiColor, rgb: Option<RgbColor>) {
+    let Some(slot) = REMAPPED.get(color as usize) else {
+        return;
+    };
+
+    let data = match rgb {
+        Some(RgbColor { red, green, blue }) => {
+            REMAPPED_TAG | (red as u32) << 16 | (green as u32) << 8 | blue as u32
+        }
+        None => 0,
+    };
+
+    slot.store(data, Ordering::Relaxed);
+
+    if rgb.is_some() {
+        ACTIVE.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The RGB color which the ANSI color is remapped to, if any
+#[inline]
+pub fn get(color: AnsiColor) -> Option<RgbColor> {
+    let data = REMAPPED.get(color as usize)?.load(Ordering::Relaxed);
+
+    if data & REMAPPED_TAG == 0 {
+        return None;
+    }
+
+    let [_, red, green, blue] = data.to_be_bytes();
+    Some(RgbColor { red, green, blue })
+}
+
+/// Remove all remapped colors
+#[inline]
+pub fn reset() {
+    ACTIVE.store(false, Ordering::Relaxed);
+
+    for slot in &REMAPPED {
+        slot.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Has any color been remapped
+pub(crate) fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Replace any remapped ANSI colors in the style, if the stream supports RGB colors
+pub(crate) fn remap(mut style: Style, stream: Option<Stream>) -> Style {
+    fn remap_color(color: &mut Option<Color>) {
+        if let Some(Color::Ansi(ansi)) = *color {
+            if let Some(rgb) = get(ansi) {
+                *color = Some(Color::Rgb(rgb));
+            }
+        }
+    }
+
+    if !crate::mode::should_color(stream, &[ColorKind::Rgb]) {
+        return style;
+    }
+
+    remap_color(&mut style.foreground);
+    remap_color(&mut style.background);
+    remap_color(&mut style.underline_color);
+
+    style
+}
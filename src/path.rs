@@ -0,0 +1,90 @@
+//! Styling paths and OS strings, which don't implement `Display` themselves
+
+use core::fmt;
+use std::{ffi::OsStr, path::Path};
+
+use crate::{NoColor, StyledValue};
+
+/// Displays a path (or OS string) via [`Path::display`], so it can be styled
+///
+/// Like [`Path::display`], any invalid unicode is replaced with `U+FFFD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PathDisplay<'a> {
+    path: &'a Path,
+}
+
+/// A styled path or OS string, created from [`ColorizePath::styled`]
+pub type StyledPath<'a, F = NoColor, B = NoColor, U = NoColor> =
+    StyledValue<PathDisplay<'a>, F, B, U>;
+
+impl<'a> PathDisplay<'a> {
+    /// Display the given path
+    #[inline]
+    pub const fn new(path: &'a Path) -> Self {
+        Self { path }
+    }
+
+    /// The path which is displayed
+    #[inline]
+    pub const fn as_path(&self) -> &'a Path {
+        self.path
+    }
+}
+
+impl<'a> From<&'a Path> for PathDisplay<'a> {
+    #[inline]
+    fn from(path: &'a Path) -> Self {
+        Self::new(path)
+    }
+}
+
+impl<'a> From<&'a OsStr> for PathDisplay<'a> {
+    #[inline]
+    fn from(os_str: &'a OsStr) -> Self {
+        Self::new(Path::new(os_str))
+    }
+}
+
+impl fmt::Display for PathDisplay<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.path.display(), f)
+    }
+}
+
+/// An extension trait to style paths and OS strings (including `PathBuf` and `OsString`)
+///
+/// Paths don't implement `Display`, so they can't be styled with [`Colorize`](crate::Colorize) directly,
+/// and styling `path.display()` borrows a temporary, which breaks method chaining. [`styled`](Self::styled)
+/// returns a [`StyledValue`] which owns the displayable path instead.
+///
+/// ```rust
+/// use colorz::{ColorizePath, mode::Stream};
+/// use std::path::PathBuf;
+///
+/// let path = PathBuf::from("src/lib.rs");
+/// let listing = path.styled().blue().bold().stream(Stream::AlwaysColor);
+///
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(listing.to_string(), "\x1b[1m\x1b[34msrc/lib.rs\x1b[22m\x1b[39m");
+/// # }
+/// assert_eq!(listing.plain().to_string(), "src/lib.rs");
+/// ```
+pub trait ColorizePath {
+    /// Convert the path to a [`StyledValue`] with no styling yet
+    fn styled(&self) -> StyledPath<'_>;
+}
+
+impl ColorizePath for Path {
+    #[inline]
+    fn styled(&self) -> StyledPath<'_> {
+        StyledValue::new(PathDisplay::new(self), crate::Style::new(), None)
+    }
+}
+
+impl ColorizePath for OsStr {
+    #[inline]
+    fn styled(&self) -> StyledPath<'_> {
+        Path::new(self).styled()
+    }
+}
@@ -0,0 +1,89 @@
+//! Print styled values with a single write to stdout or stderr
+
+use core::fmt::{self, Write as _};
+use std::io;
+
+use crate::mode::{self, Stream};
+
+/// Format `value` with `stream` as the default stream, then write it with a single locked write
+fn print_to(
+    out: &mut dyn io::Write,
+    stream: Stream,
+    value: &dyn fmt::Display,
+    newline: bool,
+) -> io::Result<()> {
+    let mut buffer = std::string::String::new();
+
+    write!(buffer, "{}", mode::WithDefaultStream { stream, value })
+        .map_err(|fmt::Error| io::Error::other("formatter error"))?;
+
+    if newline {
+        buffer.push('\n');
+    }
+
+    out.write_all(buffer.as_bytes())
+}
+
+/// Print `value` to stdout, with [`Stream::Stdout`] as the default stream
+///
+/// The value is formatted into a buffer first, and then written while stdout is locked, so
+/// output from other threads isn't interleaved with it. All [`StyledValue`](crate::StyledValue)s
+/// inside of `value` which don't have a stream will detect coloring based on stdout.
+///
+/// Unlike [`print!`](std::print), this returns an error if writing to stdout fails instead of panicking.
+///
+/// ```rust
+/// use colorz::{Colorize, print_styled};
+///
+/// print_styled(format_args!("{} ", "loading".blue()))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[inline]
+pub fn print_styled(value: impl fmt::Display) -> io::Result<()> {
+    print_to(&mut io::stdout().lock(), Stream::Stdout, &value, false)
+}
+
+/// Print `value` and a newline to stdout, with [`Stream::Stdout`] as the default stream, see [`print_styled`]
+///
+/// To style a whole formatted message, use [`Colorize::into_style_with`](crate::Colorize::into_style_with)
+/// on [`format_args!`]
+///
+/// ```rust
+/// use colorz::{Colorize, Style, println_styled};
+///
+/// let (name, ms) = ("build", 42);
+///
+/// println_styled(format_args!("{}: done", "build".green().bold()))?;
+/// println_styled(format_args!("{name} took {ms}ms").into_style_with(Style::new().dimmed()))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[inline]
+pub fn println_styled(value: impl fmt::Display) -> io::Result<()> {
+    print_to(&mut io::stdout().lock(), Stream::Stdout, &value, true)
+}
+
+/// Print `value` to stderr, with [`Stream::Stderr`] as the default stream, see [`print_styled`]
+///
+/// ```rust
+/// use colorz::{Colorize, eprint_styled};
+///
+/// eprint_styled(format_args!("{} ", "warning:".yellow()))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[inline]
+pub fn eprint_styled(value: impl fmt::Display) -> io::Result<()> {
+    print_to(&mut io::stderr().lock(), Stream::Stderr, &value, false)
+}
+
+/// Print `value` and a newline to stderr, with [`Stream::Stderr`] as the default stream, see [`print_styled`]
+///
+/// ```rust
+/// use colorz::{Colorize, eprintln_styled};
+///
+/// eprintln_styled(format_args!("{}: something went wrong", "error".red()))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[inline]
+pub fn eprintln_styled(value: impl fmt::Display) -> io::Result<()> {
+    print_to(&mut io::stderr().lock(), Stream::Stderr, &value, true)
+}
@@ -0,0 +1,460 @@
+//! Querying the terminal for its colors via OSC escape sequences
+//!
+//! The terminal is put into raw mode (via `stty`) while the queries are in flight,
+//! so that the responses aren't echoed back to the user. A primary device attributes
+//! request (DA1) is sent after all color queries, since every terminal answers it, and
+//! answers arrive in order. This lets us stop waiting as soon as the terminal is done,
+//! instead of always waiting for the full timeout on terminals that ignore OSC 4/10/11.
+//!
+//! Querying spawns the external `stty` command (to save, change and restore the terminal settings) and reads
+//! the responses from `/dev/tty`, so it blocks until the terminal answers or the timeout passes.
+
+use std::time::Duration;
+
+use crate::{ansi::AnsiColor, mode::ColorKind, rgb::RgbColor, xterm::XtermColor, Color, Style};
+
+/// The colors reported by the terminal, see [`query_terminal_palette`](crate::mode::query_terminal_palette)
+///
+/// Any color the terminal didn't report is `None`. The color conversions on the palette (like
+/// [`TerminalPalette::nearest_ansi`]) match against the terminal's actual colors, and fall back
+/// to the default xterm palette for colors which weren't reported. When the [`terminal_palette`](crate::mode::terminal_palette)
+/// is known, styled values use it to downgrade colors while formatting (see [`StyledValue::max_color`](crate::StyledValue::max_color)
+/// and [`set_auto_downgrade`](crate::mode::set_auto_downgrade)).
+///
+/// ```rust
+/// use colorz::{Colorize, ansi::AnsiColor, mode::{self, ColorKind, Stream, TerminalPalette}, rgb::RgbColor};
+///
+/// let orange = RgbColor { red: 255, green: 128, blue: 0 };
+/// assert_eq!(orange.to_ansi(), AnsiColor::Yellow);
+///
+/// // the terminal shows "red" as orange
+/// let mut palette = TerminalPalette::EMPTY;
+/// palette.colors[1] = Some(RgbColor { red: 250, green: 120, blue: 10 });
+///
+/// assert_eq!(palette.nearest_ansi(orange), AnsiColor::Red);
+/// assert_eq!(palette.to_rgb(AnsiColor::Red.into()), RgbColor { red: 250, green: 120, blue: 10 });
+///
+/// mode::set_terminal_palette(Some(palette));
+/// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(
+///     "hi".fg(orange).max_color(ColorKind::Ansi).stream(Stream::AlwaysColor).to_string(),
+///     "\x1b[31mhi\x1b[39m",
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TerminalPalette {
+    /// The default foreground color (OSC 10)
+    pub foreground: Option<RgbColor>,
+    /// The default background color (OSC 11)
+    pub background: Option<RgbColor>,
+    /// The indexed colors (OSC 4), indexed by [`XtermColor`](crate::xterm::XtermColor) code
+    pub colors: [Option<RgbColor>; 256],
+}
+
+impl TerminalPalette {
+    /// A palette where no colors are known
+    pub const EMPTY: Self = Self {
+        foreground: None,
+        background: None,
+        colors: [None; 256],
+    };
+
+    /// Is any color known
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.foreground.is_none()
+            && self.background.is_none()
+            && self.colors.iter().all(Option::is_none)
+    }
+
+    /// The rgb value of an indexed color, or its value in the default xterm palette if it wasn't reported
+    #[inline]
+    pub fn rgb(&self, color: XtermColor) -> RgbColor {
+        self.colors[color as usize].unwrap_or(color.rgb())
+    }
+
+    /// The rgb value of a color, like [`Color::to_rgb`] but using the terminal's colors for
+    /// ANSI and Xterm colors
+    #[inline]
+    pub fn to_rgb(&self, color: Color) -> RgbColor {
+        match color {
+            Color::Ansi(AnsiColor::Default) => color.to_rgb(),
+            Color::Ansi(color) => self.rgb(color.to_xterm()),
+            Color::Xterm(color) => self.rgb(color),
+            Color::Css(_) | Color::Rgb(_) | Color::Default => color.to_rgb(),
+        }
+    }
+
+    /// The ANSI color which looks nearest to `color` in this terminal, like [`RgbColor::to_ansi`]
+    #[inline]
+    pub fn nearest_ansi(&self, color: RgbColor) -> AnsiColor {
+        let code = (0..16)
+            .min_by_key(|&code| color.distance(self.rgb(XtermColor::from_code(code))))
+            .unwrap_or(0);
+
+        AnsiColor::from_index(code).unwrap_or(AnsiColor::Default)
+    }
+
+    /// The Xterm color which looks nearest to `color` in this terminal, like [`RgbColor::to_xterm`]
+    ///
+    /// The first 16 Xterm colors are only picked if the terminal reported them, since otherwise
+    /// their actual value isn't known
+    #[inline]
+    pub fn nearest_xterm(&self, color: RgbColor) -> XtermColor {
+        if self.colors.iter().all(Option::is_none) {
+            return color.to_xterm();
+        }
+
+        let code = (0..=255)
+            .filter(|&code| code >= 16 || self.colors[usize::from(code)].is_some())
+            .min_by_key(|&code| color.distance(self.rgb(XtermColor::from_code(code))))
+            .unwrap_or(16);
+
+        XtermColor::from_code(code)
+    }
+
+    /// Convert the color to the nearest color of the given kind in this terminal, like [`Color::downgrade`]
+    ///
+    /// ```rust
+    /// use colorz::{Color, ansi::AnsiColor, mode::{ColorKind, TerminalPalette}, rgb::RgbColor, xterm::XtermColor};
+    ///
+    /// let mut palette = TerminalPalette::EMPTY;
+    /// palette.colors[4] = Some(RgbColor { red: 40, green: 100, blue: 220 });
+    ///
+    /// let blue = Color::Rgb(RgbColor { red: 50, green: 100, blue: 210 });
+    /// assert_eq!(palette.downgrade(blue, ColorKind::Ansi), Some(Color::Ansi(AnsiColor::Blue)));
+    /// assert_eq!(palette.downgrade(blue, ColorKind::Xterm), Some(Color::Xterm(XtermColor::from_code(4))));
+    /// ```
+    #[inline]
+    pub fn downgrade(&self, color: Color, kind: ColorKind) -> Option<Color> {
+        Some(match kind {
+            ColorKind::NoColor => return None,
+            ColorKind::Rgb => color,
+            ColorKind::Xterm => match color {
+                Color::Css(_) | Color::Rgb(_) => Color::Xterm(self.nearest_xterm(color.to_rgb())),
+                Color::Ansi(_) | Color::Xterm(_) | Color::Default => color,
+            },
+            ColorKind::Ansi => match color {
+                Color::Ansi(_) | Color::Default => color,
+                Color::Xterm(xterm) => match AnsiColor::from_index(xterm as u8) {
+                    Some(ansi) if (xterm as u8) < 16 => Color::Ansi(ansi),
+                    _ => Color::Ansi(self.nearest_ansi(self.rgb(xterm))),
+                },
+                Color::Css(_) | Color::Rgb(_) => Color::Ansi(self.nearest_ansi(color.to_rgb())),
+            },
+        })
+    }
+
+    /// Convert all colors of the style to the nearest color of the given kind in this terminal,
+    /// like [`Style::downgrade`]
+    #[inline]
+    pub fn downgrade_style(&self, style: Style, kind: ColorKind) -> Style {
+        Style {
+            foreground: style
+                .foreground
+                .and_then(|color| self.downgrade(color, kind)),
+            background: style
+                .background
+                .and_then(|color| self.downgrade(color, kind)),
+            underline_color: style
+                .underline_color
+                .and_then(|color| self.downgrade(color, kind)),
+            effects: style.effects,
+        }
+    }
+
+    /// Black or white, whichever is more readable on `background` as this terminal shows it,
+    /// like [`RgbColor::contrasting_text`]
+    #[inline]
+    pub fn contrasting_text(&self, background: Color) -> RgbColor {
+        self.to_rgb(background).contrasting_text()
+    }
+}
+
+impl Default for TerminalPalette {
+    #[inline]
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// Parse a single `rgb:r/g/b` color spec where each component has 1 to 4 hex digits
+fn parse_color_spec(spec: &[u8]) -> Option<RgbColor> {
+    let spec = spec.strip_prefix(b"rgb:")?;
+    let mut parts = spec.split(|&b| b == b'/');
+
+    let mut component = || -> Option<u8> {
+        let part = parts.next()?;
+
+        if part.is_empty() || part.len() > 4 {
+            return None;
+        }
+
+        let mut value = 0u32;
+        for &digit in part {
+            value = value << 4 | char::from(digit).to_digit(16)?;
+        }
+
+        let max = (1u32 << (4 * part.len())) - 1;
+        Some(((value * 255 + max / 2) / max) as u8)
+    };
+
+    let color = RgbColor {
+        red: component()?,
+        green: component()?,
+        blue: component()?,
+    };
+
+    match parts.next() {
+        Some(_) => None,
+        None => Some(color),
+    }
+}
+
+fn parse_index(index: &[u8]) -> Option<u8> {
+    core::str::from_utf8(index).ok()?.parse().ok()
+}
+
+/// Parse all OSC 4/10/11 responses in `data` into `palette`
+///
+/// Returns true if the DA1 response (which marks the end of the responses) was found
+pub(crate) fn parse_responses(data: &[u8], palette: &mut TerminalPalette) -> bool {
+    let mut rest = data;
+    let mut finished = false;
+
+    while let Some(start) = rest.iter().position(|&b| b == 0x1b) {
+        rest = &rest[start + 1..];
+
+        match rest.first() {
+            Some(b']') => {
+                rest = &rest[1..];
+
+                // OSC responses are terminated by either BEL or ST (ESC \)
+                let Some(end) = rest.iter().position(|&b| b == 0x07 || b == 0x1b) else {
+                    break;
+                };
+
+                let body = &rest[..end];
+                rest = &rest[end..];
+
+                let mut fields = body.splitn(3, |&b| b == b';');
+
+                match (fields.next(), fields.next(), fields.next()) {
+                    (Some(b"4"), Some(index), Some(spec)) => {
+                        if let Some(index) = parse_index(index) {
+                            palette.colors[usize::from(index)] = parse_color_spec(spec);
+                        }
+                    }
+                    (Some(b"10"), Some(spec), None) => palette.foreground = parse_color_spec(spec),
+                    (Some(b"11"), Some(spec), None) => palette.background = parse_color_spec(spec),
+                    _ => (),
+                }
+            }
+            Some(b'[') => {
+                // The DA1 response looks like `CSI ? ... c`
+                let body = &rest[1..];
+                if let Some(end) = body.iter().position(|b| (0x40..=0x7e).contains(b)) {
+                    if body.first() == Some(&b'?') && body[end] == b'c' {
+                        finished = true;
+                    }
+                    rest = &body[end + 1..];
+                }
+            }
+            _ => (),
+        }
+    }
+
+    finished
+}
+
+/// Query the terminal, only asking for the indexed colors (OSC 4) if `indexed` is true
+#[cfg(unix)]
+pub(crate) fn query(timeout: Duration, indexed: bool) -> std::io::Result<TerminalPalette> {
+    use std::{
+        fs::{File, OpenOptions},
+        io::{Read, Write},
+        process::{Command, Stdio},
+        time::Instant,
+    };
+
+    fn stty(tty: &File, args: &[&str]) -> std::io::Result<std::vec::Vec<u8>> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(Stdio::from(tty.try_clone()?))
+            .stderr(Stdio::null())
+            .output()?;
+
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(std::io::Error::other(
+                "failed to configure the terminal via `stty`",
+            ))
+        }
+    }
+
+    struct Restore<'a> {
+        tty: &'a File,
+        settings: std::string::String,
+    }
+
+    impl Drop for Restore<'_> {
+        fn drop(&mut self) {
+            let _ = stty(self.tty, &[self.settings.trim()]);
+        }
+    }
+
+    let tty = OpenOptions::new().read(true).write(true).open("/dev/tty")?;
+
+    let settings = stty(&tty, &["-g"])?;
+    let settings = std::string::String::from_utf8(settings).map_err(std::io::Error::other)?;
+    let _restore = Restore {
+        tty: &tty,
+        settings,
+    };
+
+    // `min 0 time 1` makes reads return after at most 100ms, even if nothing was written
+    stty(&tty, &["-icanon", "-echo", "min", "0", "time", "1"])?;
+
+    let mut request = std::vec::Vec::new();
+    if indexed {
+        for index in 0..=255u8 {
+            write!(request, "\x1b]4;{index};?\x07")?;
+        }
+    }
+    request.extend_from_slice(b"\x1b]10;?\x07\x1b]11;?\x07\x1b[c");
+    (&tty).write_all(&request)?;
+    (&tty).flush()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut response = std::vec::Vec::new();
+    let mut buffer = [0; 4096];
+    let mut palette = TerminalPalette::EMPTY;
+
+    while Instant::now() < deadline {
+        let n = (&tty).read(&mut buffer)?;
+        response.extend_from_slice(&buffer[..n]);
+
+        if n != 0 && parse_responses(&response, &mut palette) {
+            break;
+        }
+    }
+
+    parse_responses(&response, &mut palette);
+
+    Ok(palette)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn query(_timeout: Duration, _indexed: bool) -> std::io::Result<TerminalPalette> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "querying the terminal palette is only supported on unix",
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_spec() {
+        assert_eq!(
+            parse_color_spec(b"rgb:ffff/8080/0000"),
+            Some(RgbColor {
+                red: 255,
+                green: 128,
+                blue: 0
+            })
+        );
+        assert_eq!(
+            parse_color_spec(b"rgb:f/8/0"),
+            Some(RgbColor {
+                red: 255,
+                green: 136,
+                blue: 0
+            })
+        );
+        assert_eq!(parse_color_spec(b"rgb:ff/00"), None);
+        assert_eq!(parse_color_spec(b"rgb:ff/00/00/00"), None);
+        assert_eq!(parse_color_spec(b"#ff0000"), None);
+    }
+
+    #[test]
+    fn parse_full_response() {
+        let mut palette = TerminalPalette::EMPTY;
+        let finished = parse_responses(
+            b"\x1b]4;1;rgb:cdcd/0000/0000\x07\x1b]4;255;rgb:eeee/eeee/eeee\x1b\\\x1b]10;rgb:ffff/ffff/ffff\x07\x1b]11;rgb:0000/0000/0000\x07\x1b[?62;22c",
+            &mut palette,
+        );
+
+        assert!(finished);
+        assert_eq!(
+            palette.colors[1],
+            Some(RgbColor {
+                red: 0xcd,
+                green: 0,
+                blue: 0
+            })
+        );
+        assert_eq!(
+            palette.colors[255],
+            Some(RgbColor {
+                red: 0xee,
+                green: 0xee,
+                blue: 0xee
+            })
+        );
+        assert_eq!(palette.colors[2], None);
+        assert_eq!(
+            palette.foreground,
+            Some(RgbColor {
+                red: 255,
+                green: 255,
+                blue: 255
+            })
+        );
+        assert_eq!(
+            palette.background,
+            Some(RgbColor {
+                red: 0,
+                green: 0,
+                blue: 0
+            })
+        );
+    }
+
+    #[test]
+    fn parse_partial_response() {
+        let mut palette = TerminalPalette::EMPTY;
+        assert!(!parse_responses(
+            b"\x1b]11;rgb:0000/0000/0000\x07\x1b]4;2",
+            &mut palette
+        ));
+        assert_eq!(
+            palette.background,
+            Some(RgbColor {
+                red: 0,
+                green: 0,
+                blue: 0
+            })
+        );
+    }
+
+    #[test]
+    fn empty_palette_matches_default_conversions() {
+        let palette = TerminalPalette::EMPTY;
+
+        for (red, green, blue) in [
+            (255, 128, 0),
+            (200, 10, 20),
+            (100, 100, 100),
+            (30, 200, 250),
+        ] {
+            let color = RgbColor { red, green, blue };
+            assert_eq!(palette.nearest_ansi(color), color.to_ansi());
+            assert_eq!(palette.nearest_xterm(color), color.to_xterm());
+        }
+    }
+}
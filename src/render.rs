@@ -0,0 +1,166 @@
+//! Deciding how a style is written when formatting [`StyledValue`](crate::StyledValue)s
+//! and [`StyledText`](crate::StyledText)
+//!
+//! Usually the style is written as is, but the global settings in [`mode`](crate::mode) (like
+//! [`suppress_effects`](crate::mode::suppress_effects) and the color transform) and
+//! [`palette`](crate::palette) may change the style before it is written.
+
+use crate::{
+    mode::{self, ColorKind, Stream},
+    Color, Effect, EffectFlags, OptionalColor, Style,
+};
+
+/// How a style should be written
+pub(crate) enum Render {
+    /// The style shouldn't be written at all
+    Plain,
+    /// The style should be written as is
+    AsIs,
+    /// This style should be written instead
+    Runtime(Style),
+}
+
+/// Decide how `style` should be written to `stream`
+pub(crate) fn resolve<F: OptionalColor, B: OptionalColor, U: OptionalColor>(
+    style: &Style<F, B, U>,
+    stream: Option<Stream>,
+    max_color: ColorKind,
+) -> Render {
+    if max_color != ColorKind::Rgb {
+        let capped = downgrade(style.to_runtime(), max_color);
+
+        // the palette isn't used, since the remapped RGB colors would be downgraded
+        // to a different color than the one the value was styled with
+        return match resolve_global(&capped, stream, false) {
+            Render::AsIs => Render::Runtime(capped),
+            Render::Runtime(style) => Render::Runtime(downgrade(style, max_color)),
+            Render::Plain => Render::Plain,
+        };
+    }
+
+    resolve_global(style, stream, true)
+}
+
+/// Decide how `style` should be written to `stream` with the global settings,
+/// the palette is only used if `use_palette` is true
+fn resolve_global<F: OptionalColor, B: OptionalColor, U: OptionalColor>(
+    style: &Style<F, B, U>,
+    stream: Option<Stream>,
+    use_palette: bool,
+) -> Render {
+    let mut render = if style.should_color(stream) {
+        Render::AsIs
+    } else if mode::get_auto_downgrade() {
+        let style = downgrade(style.to_runtime(), mode::max_color_kind(stream));
+
+        if style.should_color(stream) {
+            Render::Runtime(style)
+        } else {
+            return Render::Plain;
+        }
+    } else {
+        return Render::Plain;
+    };
+
+    let suppressed = mode::get_suppressed_effects();
+
+    if style.effects.is_any(suppressed) {
+        let mut runtime = match render {
+            Render::Runtime(style) => style,
+            _ => style.to_runtime(),
+        };
+
+        runtime.effects =
+            EffectFlags::from_bits_truncate(runtime.effects.bits() & !suppressed.bits());
+        render = Render::Runtime(runtime);
+    }
+
+    if use_palette && crate::palette::is_active() {
+        let runtime = match render {
+            Render::Runtime(style) => style,
+            _ => style.to_runtime(),
+        };
+
+        render = Render::Runtime(crate::palette::remap(runtime, stream));
+    }
+
+    #[cfg(feature = "std")]
+    if let Some(transform) = mode::get_color_transform() {
+        let mut runtime = match render {
+            Render::Runtime(style) => style,
+            _ => style.to_runtime(),
+        };
+
+        runtime.foreground = runtime.foreground.and_then(transform);
+        runtime.background = runtime.background.and_then(transform);
+        runtime.underline_color = runtime.underline_color.and_then(transform);
+
+        if !runtime.should_color(stream) {
+            runtime = downgrade(runtime, mode::max_color_kind(stream));
+        }
+
+        render = Render::Runtime(runtime);
+    }
+
+    if mode::get_bright_fallback() {
+        let runtime = match render {
+            Render::Runtime(style) => style,
+            _ => style.to_runtime(),
+        };
+
+        if let Some(fallback) = bright_fallback(runtime) {
+            render = Render::Runtime(fallback);
+        }
+    }
+
+    render
+}
+
+/// Convert the style's colors to the given kind, matching against the terminal's actual
+/// colors if the [`terminal_palette`](mode::terminal_palette) is known
+#[allow(clippy::missing_const_for_fn)]
+fn downgrade(style: Style, kind: ColorKind) -> Style {
+    #[cfg(feature = "std")]
+    if let Some(style) = mode::with_terminal_palette(|palette| palette.downgrade_style(style, kind))
+    {
+        return style;
+    }
+
+    style.downgrade(kind)
+}
+
+/// Replace bright ANSI colors with bold and the normal color, or `None` if there are no bright colors
+///
+/// Bold isn't added if it's [suppressed](mode::suppress_effects)
+fn bright_fallback(mut style: Style) -> Option<Style> {
+    let mut changed = false;
+
+    if let Some(Color::Ansi(color)) = style.foreground {
+        if color.is_bright() {
+            style.foreground = Some(Color::Ansi(color.to_normal()));
+            if !mode::get_suppressed_effects().is(Effect::Bold) {
+                style.effects.set(Effect::Bold);
+            }
+            changed = true;
+        }
+    }
+
+    if let Some(Color::Ansi(color)) = style.background {
+        if color.is_bright() {
+            style.background = Some(Color::Ansi(color.to_normal()));
+            changed = true;
+        }
+    }
+
+    changed.then_some(style)
+}
+
+/// The style to write in place of `style`, a plain style if it shouldn't be written
+#[cfg(feature = "alloc")]
+pub(crate) fn resolve_runtime(style: Style, stream: Option<Stream>) -> Style {
+    match resolve(&style, stream, ColorKind::Rgb) {
+        Render::Plain => Style::new().into_runtime_style(),
+        Render::AsIs => style,
+        Render::Runtime(style) => style,
+    }
+}
@@ -1,6 +1,6 @@
 //! 48-bit color values. Not as widely supported as standard ANSI or Xterm.
 
-use crate::{ColorSpec, WriteColor};
+use crate::{ansi::AnsiColor, xterm::XtermColor, ColorSpec, WriteColor};
 
 #[cfg(doc)]
 use crate::Color;
@@ -65,7 +65,7 @@ impl RgbBuffer {
         self.len += 1;
     }
 
-    fn write_escape_end(&mut self) {
+    const fn write_escape_end(&mut self) {
         self.write_char(b'm')
     }
 
@@ -74,7 +74,7 @@ impl RgbBuffer {
         self.len += s.len() as u8;
     }
 
-    fn write_char(&mut self, x: u8) {
+    const fn write_char(&mut self, x: u8) {
         self.data[self.len as usize] = x;
         self.len += 1;
     }
@@ -156,62 +156,562 @@ impl RgbBuffer {
     }
 }
 
+const fn percent(x: u8) -> u32 {
+    if x > 100 {
+        100
+    } else {
+        x as u32
+    }
+}
+
+const fn div_round(numerator: u32, denominator: u32) -> u32 {
+    (numerator + denominator / 2) / denominator
+}
+
+impl RgbColor {
+    /// Parse a hex color, written as `#rrggbb` or `#rgb` (the `#` is optional)
+    ///
+    /// This can be used in a const context, see [`color!`](crate::color!) to get the
+    /// compile time [`Rgb`] type of a hex color.
+    ///
+    /// ```rust
+    /// use colorz::{rgb::RgbColor, ParseColorError};
+    ///
+    /// assert_eq!(RgbColor::from_hex("#1e90ff"), Ok(RgbColor { red: 30, green: 144, blue: 255 }));
+    /// assert_eq!(RgbColor::from_hex("f0a"), Ok(RgbColor { red: 255, green: 0, blue: 170 }));
+    /// assert_eq!(RgbColor::from_hex("#1e90fg"), Err(ParseColorError::InvalidHexDigit));
+    /// assert_eq!(RgbColor::from_hex("#1e90f"), Err(ParseColorError::UnknownColor));
+    /// ```
+    #[inline]
+    pub const fn from_hex(hex: &str) -> Result<Self, crate::ParseColorError> {
+        crate::from_str::parse_hex_rgb(hex.as_bytes())
+    }
+
+    /// Convert an HSL color to rgb, where `hue` is in degrees
+    /// and `saturation` and `lightness` are percentages in the range `0..=100`
+    ///
+    /// Percentages above 100 are treated as 100
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// assert_eq!(RgbColor::from_hsl(0, 100, 50), RgbColor { red: 255, green: 0, blue: 0 });
+    /// assert_eq!(RgbColor::from_hsl(120, 50, 50), RgbColor { red: 64, green: 191, blue: 64 });
+    /// ```
+    #[inline]
+    pub const fn from_hsl(hue: u16, saturation: u8, lightness: u8) -> Self {
+        // all values are in units of 1/600_000, which keeps everything in integers
+        let saturation = percent(saturation);
+        let lightness = percent(lightness);
+
+        let chroma = (100 - (2 * lightness).abs_diff(100)) * saturation;
+        let m = lightness * 6000 - chroma * 30;
+
+        Self::from_hue_chroma(hue, chroma, m)
+    }
+
+    /// Convert an HSV (also called HSB) color to rgb, where `hue` is in degrees
+    /// and `saturation` and `value` are percentages in the range `0..=100`
+    ///
+    /// Percentages above 100 are treated as 100
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// assert_eq!(RgbColor::from_hsv(0, 100, 100), RgbColor { red: 255, green: 0, blue: 0 });
+    /// assert_eq!(RgbColor::from_hsv(120, 50, 50), RgbColor { red: 64, green: 128, blue: 64 });
+    /// ```
+    #[inline]
+    pub const fn from_hsv(hue: u16, saturation: u8, value: u8) -> Self {
+        // all values are in units of 1/600_000, which keeps everything in integers
+        let saturation = percent(saturation);
+        let value = percent(value);
+
+        let chroma = value * saturation;
+        let m = value * 6000 - chroma * 60;
+
+        Self::from_hue_chroma(hue, chroma, m)
+    }
+
+    /// Shared implementation of [`from_hsl`](Self::from_hsl) and [`from_hsv`](Self::from_hsv),
+    /// where `chroma` is in units of 1/10_000 and `m` is in units of 1/600_000
+    const fn from_hue_chroma(hue: u16, chroma: u32, m: u32) -> Self {
+        let hue = (hue % 360) as u32;
+        let x = chroma * (60 - (hue % 120).abs_diff(60));
+        let chroma = chroma * 60;
+
+        let (red, green, blue) = match hue / 60 {
+            0 => (chroma, x, 0),
+            1 => (x, chroma, 0),
+            2 => (0, chroma, x),
+            3 => (0, x, chroma),
+            4 => (x, 0, chroma),
+            _ => (chroma, 0, x),
+        };
+
+        const fn scale(x: u32) -> u8 {
+            ((x * 255 + 300_000) / 600_000) as u8
+        }
+
+        Self {
+            red: scale(red + m),
+            green: scale(green + m),
+            blue: scale(blue + m),
+        }
+    }
+
+    /// Convert to HSL, returning `(hue, saturation, lightness)` where `hue` is in degrees (`0..360`)
+    /// and `saturation` and `lightness` are percentages in the range `0..=100`
+    ///
+    /// The components are rounded, so converting back with [`from_hsl`](Self::from_hsl) may
+    /// be off by a little bit
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// assert_eq!(RgbColor { red: 255, green: 0, blue: 0 }.to_hsl(), (0, 100, 50));
+    /// assert_eq!(RgbColor { red: 64, green: 191, blue: 64 }.to_hsl(), (120, 50, 50));
+    /// assert_eq!(RgbColor { red: 128, green: 128, blue: 128 }.to_hsl(), (0, 0, 50));
+    /// ```
+    #[inline]
+    pub const fn to_hsl(self) -> (u16, u8, u8) {
+        let (max, min) = self.max_min();
+        let delta = max - min;
+
+        let lightness = div_round((max + min) * 100, 510);
+        let saturation = if delta == 0 {
+            0
+        } else {
+            div_round(delta * 100, 255 - (max + min).abs_diff(255))
+        };
+
+        (self.hue(), saturation as u8, lightness as u8)
+    }
+
+    /// Convert to HSV (also called HSB), returning `(hue, saturation, value)` where `hue` is in
+    /// degrees (`0..360`) and `saturation` and `value` are percentages in the range `0..=100`
+    ///
+    /// The components are rounded, so converting back with [`from_hsv`](Self::from_hsv) may
+    /// be off by a little bit
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// assert_eq!(RgbColor { red: 255, green: 0, blue: 0 }.to_hsv(), (0, 100, 100));
+    /// assert_eq!(RgbColor { red: 64, green: 128, blue: 64 }.to_hsv(), (120, 50, 50));
+    /// ```
+    #[inline]
+    pub const fn to_hsv(self) -> (u16, u8, u8) {
+        let (max, min) = self.max_min();
+
+        let value = div_round(max * 100, 255);
+        let saturation = if max == 0 {
+            0
+        } else {
+            div_round((max - min) * 100, max)
+        };
+
+        (self.hue(), saturation as u8, value as u8)
+    }
+
+    /// The largest and smallest components
+    const fn max_min(self) -> (u32, u32) {
+        let (red, green, blue) = (self.red as u32, self.green as u32, self.blue as u32);
+        let max = if red > green { red } else { green };
+        let max = if max > blue { max } else { blue };
+        let min = if red < green { red } else { green };
+        let min = if min < blue { min } else { blue };
+        (max, min)
+    }
+
+    /// The hue in degrees, shared by HSL and HSV
+    const fn hue(self) -> u16 {
+        let (red, green, blue) = (self.red as u32, self.green as u32, self.blue as u32);
+        let (max, min) = self.max_min();
+        let delta = max - min;
+
+        if delta == 0 {
+            return 0;
+        }
+
+        // offset so the numerator is never negative
+        let numerator = if max == red {
+            if green >= blue {
+                60 * (green - blue)
+            } else {
+                360 * delta - 60 * (blue - green)
+            }
+        } else if max == green {
+            120 * delta + 60 * blue - 60 * red
+        } else {
+            240 * delta + 60 * red - 60 * green
+        };
+
+        (div_round(numerator, delta) % 360) as u16
+    }
+
+    /// Linearly interpolate between two colors, where `t = 0.0` gives `self` and `t = 1.0` gives `other`
+    ///
+    /// `t` is clamped to the range `0.0..=1.0`. This interpolates each rgb component separately,
+    /// which is fast but not perceptually uniform, see `Oklab::lerp` (with the `std` feature) for a smoother alternative.
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let black = RgbColor { red: 0, green: 0, blue: 0 };
+    /// let orange = RgbColor { red: 255, green: 128, blue: 0 };
+    ///
+    /// assert_eq!(black.lerp(orange, 0.0), black);
+    /// assert_eq!(black.lerp(orange, 0.5), RgbColor { red: 128, green: 64, blue: 0 });
+    /// assert_eq!(black.lerp(orange, 2.0), orange);
+    /// ```
+    #[inline]
+    pub const fn lerp(self, other: RgbColor, t: f32) -> RgbColor {
+        const fn lerp(a: u8, b: u8, t: f32) -> u8 {
+            // `+ 0.5` rounds to the nearest integer, since the result is never negative
+            (a as f32 + (b as f32 - a as f32) * t + 0.5) as u8
+        }
+
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        RgbColor {
+            red: lerp(self.red, other.red, t),
+            green: lerp(self.green, other.green, t),
+            blue: lerp(self.blue, other.blue, t),
+        }
+    }
+
+    /// Composite this color with the given opacity over a `background` color
+    ///
+    /// `alpha` is clamped to the range `0.0..=1.0`, where `0.0` is fully transparent
+    /// (giving the `background`) and `1.0` is fully opaque (giving `self`).
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let red = RgbColor { red: 255, green: 0, blue: 0 };
+    /// let white = RgbColor { red: 255, green: 255, blue: 255 };
+    ///
+    /// assert_eq!(red.with_alpha_over(0.25, white), RgbColor { red: 255, green: 191, blue: 191 });
+    /// ```
+    #[inline]
+    pub const fn with_alpha_over(self, alpha: f32, background: RgbColor) -> RgbColor {
+        background.lerp(self, alpha)
+    }
+
+    /// Increase the HSL lightness by `amount`, where `1.0` is the full range from black to white
+    ///
+    /// The lightness is clamped, so the result is at most white
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let red = RgbColor { red: 255, green: 0, blue: 0 };
+    ///
+    /// assert_eq!(red.lighten(0.2), RgbColor { red: 255, green: 102, blue: 102 });
+    /// assert_eq!(red.lighten(1.0), RgbColor { red: 255, green: 255, blue: 255 });
+    /// ```
+    #[inline]
+    pub const fn lighten(self, amount: f32) -> RgbColor {
+        let (hue, saturation, lightness) = self.to_hsl_f32();
+        Self::from_hsl_f32(hue, saturation, lightness + amount)
+    }
+
+    /// Decrease the HSL lightness by `amount`, where `1.0` is the full range from white to black
+    ///
+    /// The lightness is clamped, so the result is at least black
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let red = RgbColor { red: 255, green: 0, blue: 0 };
+    ///
+    /// assert_eq!(red.darken(0.2), RgbColor { red: 153, green: 0, blue: 0 });
+    /// assert_eq!(red.darken(1.0), RgbColor { red: 0, green: 0, blue: 0 });
+    /// ```
+    #[inline]
+    pub const fn darken(self, amount: f32) -> RgbColor {
+        self.lighten(-amount)
+    }
+
+    /// Increase the HSL saturation by `amount`, where `1.0` is the full range from gray to fully saturated
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let muted = RgbColor { red: 191, green: 64, blue: 64 };
+    ///
+    /// assert_eq!(muted.saturate(0.5), RgbColor { red: 255, green: 0, blue: 0 });
+    /// ```
+    #[inline]
+    pub const fn saturate(self, amount: f32) -> RgbColor {
+        let (hue, saturation, lightness) = self.to_hsl_f32();
+        Self::from_hsl_f32(hue, saturation + amount, lightness)
+    }
+
+    /// Decrease the HSL saturation by `amount`, where `1.0` is the full range from fully saturated to gray
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let red = RgbColor { red: 255, green: 0, blue: 0 };
+    ///
+    /// assert_eq!(red.desaturate(0.5), RgbColor { red: 191, green: 64, blue: 64 });
+    /// assert_eq!(red.desaturate(1.0), RgbColor { red: 128, green: 128, blue: 128 });
+    /// ```
+    #[inline]
+    pub const fn desaturate(self, amount: f32) -> RgbColor {
+        self.saturate(-amount)
+    }
+
+    /// Convert to HSL with full precision, where `hue` is in `0.0..6.0` (in units of 60 degrees)
+    /// and `saturation` and `lightness` are in `0.0..=1.0`
+    const fn to_hsl_f32(self) -> (f32, f32, f32) {
+        let red = self.red as f32 / 255.0;
+        let green = self.green as f32 / 255.0;
+        let blue = self.blue as f32 / 255.0;
+
+        let max = red.max(green).max(blue);
+        let min = red.min(green).min(blue);
+        let delta = max - min;
+        let lightness = (max + min) / 2.0;
+
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == red {
+            (green - blue) / delta + 6.0
+        } else if max == green {
+            (blue - red) / delta + 2.0
+        } else {
+            (red - green) / delta + 4.0
+        };
+
+        (hue % 6.0, saturation, lightness)
+    }
+
+    /// The inverse of [`to_hsl_f32`](Self::to_hsl_f32), which clamps the saturation and lightness
+    const fn from_hsl_f32(hue: f32, saturation: f32, lightness: f32) -> Self {
+        const fn clamp(x: f32) -> f32 {
+            if x.is_nan() {
+                0.0
+            } else {
+                x.clamp(0.0, 1.0)
+            }
+        }
+
+        const fn scale(x: f32) -> u8 {
+            (x * 255.0 + 0.5) as u8
+        }
+
+        let saturation = clamp(saturation);
+        let lightness = clamp(lightness);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let m = lightness - chroma / 2.0;
+
+        let (red, green, blue) = match hue as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        Self {
+            red: scale(red + m),
+            green: scale(green + m),
+            blue: scale(blue + m),
+        }
+    }
+
+    /// The (scaled) perceptual distance between two colors
+    ///
+    /// This is the "redmean" approximation, which weights each channel based on how red the colors are
+    pub(crate) const fn distance(self, other: RgbColor) -> u32 {
+        let red_mean = (self.red as u32 + other.red as u32) / 2;
+        let red = self.red.abs_diff(other.red) as u32;
+        let green = self.green.abs_diff(other.green) as u32;
+        let blue = self.blue.abs_diff(other.blue) as u32;
+        (((512 + red_mean) * red * red) >> 8)
+            + 4 * green * green
+            + (((767 - red_mean) * blue * blue) >> 8)
+    }
+
+    /// The nearest Xterm color, using the default xterm palette
+    ///
+    /// This is the same as [`XtermColor::from_rgb`]
+    ///
+    /// Only the color cube and the grayscale ramp are considered, since the first 16 Xterm
+    /// colors are the ANSI colors, which are often customized by the terminal's theme.
+    /// To match against the terminal's actual colors, see `mode::TerminalPalette` (with the `std` feature)
+    ///
+    /// ```rust
+    /// use colorz::{rgb::RgbColor, xterm};
+    ///
+    /// assert_eq!(RgbColor { red: 255, green: 128, blue: 0 }.to_xterm(), xterm::DarkOrange.into());
+    /// assert_eq!(RgbColor { red: 100, green: 100, blue: 100 }.to_xterm(), xterm::XtermColor::from_code(241));
+    /// ```
+    #[inline]
+    pub const fn to_xterm(self) -> XtermColor {
+        const fn cube_index(x: u8) -> u8 {
+            if x < 48 {
+                0
+            } else if x < 115 {
+                1
+            } else {
+                (x - 35) / 40
+            }
+        }
+
+        let cube = XtermColor::from_cube(
+            cube_index(self.red),
+            cube_index(self.green),
+            cube_index(self.blue),
+        );
+
+        let average = (self.red as u32 + self.green as u32 + self.blue as u32) / 3;
+        let gray_index = if average > 238 {
+            23
+        } else {
+            average.saturating_sub(3) / 10
+        };
+        let gray = XtermColor::gray(gray_index as u8);
+
+        if self.distance(gray.rgb()) < self.distance(cube.rgb()) {
+            gray
+        } else {
+            cube
+        }
+    }
+
+    /// The nearest ANSI color, using the default xterm palette
+    ///
+    /// To match against the terminal's actual colors, see `mode::TerminalPalette` (with the `std` feature)
+    ///
+    /// ```rust
+    /// use colorz::{rgb::RgbColor, ansi::AnsiColor};
+    ///
+    /// assert_eq!(RgbColor { red: 200, green: 10, blue: 20 }.to_ansi(), AnsiColor::Red);
+    /// assert_eq!(RgbColor { red: 250, green: 250, blue: 240 }.to_ansi(), AnsiColor::BrightWhite);
+    /// ```
+    #[inline]
+    pub const fn to_ansi(self) -> AnsiColor {
+        let mut best = 0;
+        let mut best_distance = u32::MAX;
+        let mut code = 0;
+
+        while code < 16 {
+            let distance = self.distance(XtermColor::from_code(code).rgb());
+            if distance < best_distance {
+                best = code;
+                best_distance = distance;
+            }
+            code += 1;
+        }
+
+        match AnsiColor::from_index(best) {
+            Some(color) => color,
+            None => AnsiColor::Default,
+        }
+    }
+}
+
 impl crate::seal::Seal for RgbColor {}
 impl WriteColor for RgbColor {
     #[inline]
     fn color_kind(self) -> crate::mode::ColorKind {
-        crate::mode::ColorKind::Rgb
+        crate::cap::kind(crate::mode::ColorKind::Rgb)
     }
 
     #[inline]
-    fn fmt_foreground_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn into_color(self) -> crate::Color {
+        crate::Color::Rgb(self)
+    }
+
+    #[inline]
+    fn write_foreground_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        w: &mut W,
+    ) -> core::fmt::Result {
+        if let Some(capped) = crate::cap::cap(crate::Color::Rgb(self)) {
+            return w.write_str(capped.foreground_args());
+        }
+
         let mut buffer = RgbBuffer::new();
         buffer.write_args_header(Layer::Foreground);
         buffer.write_args(self.red, self.green, self.blue);
-        f.write_str(buffer.to_str())
+        w.write_str(buffer.to_str())
     }
 
     #[inline]
-    fn fmt_background_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_background_args_to<W: core::fmt::Write + ?Sized>(
+        self,
+        w: &mut W,
+    ) -> core::fmt::Result {
+        if let Some(capped) = crate::cap::cap(crate::Color::Rgb(self)) {
+            return w.write_str(capped.background_args());
+        }
+
         let mut buffer = RgbBuffer::new();
         buffer.write_args_header(Layer::Background);
         buffer.write_args(self.red, self.green, self.blue);
-        f.write_str(buffer.to_str())
+        w.write_str(buffer.to_str())
     }
 
     #[inline]
-    fn fmt_underline_args(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_underline_args_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        if let Some(capped) = crate::cap::cap(crate::Color::Rgb(self)) {
+            return w.write_str(capped.underline_args());
+        }
+
         let mut buffer = RgbBuffer::new();
         buffer.write_args_header(Layer::Underline);
         buffer.write_args(self.red, self.green, self.blue);
-        f.write_str(buffer.to_str())
+        w.write_str(buffer.to_str())
     }
 
     #[inline]
-    fn fmt_foreground(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_foreground_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        if let Some(capped) = crate::cap::cap(crate::Color::Rgb(self)) {
+            return w.write_str(capped.foreground_escape());
+        }
+
         let mut buffer = RgbBuffer::new();
         buffer.write_escape_start(Layer::Foreground);
         buffer.write_args(self.red, self.green, self.blue);
         buffer.write_escape_end();
-        f.write_str(buffer.to_str())
+        w.write_str(buffer.to_str())
     }
 
     #[inline]
-    fn fmt_background(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_background_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        if let Some(capped) = crate::cap::cap(crate::Color::Rgb(self)) {
+            return w.write_str(capped.background_escape());
+        }
+
         let mut buffer = RgbBuffer::new();
         buffer.write_escape_start(Layer::Background);
         buffer.write_args(self.red, self.green, self.blue);
         buffer.write_escape_end();
-        f.write_str(buffer.to_str())
+        w.write_str(buffer.to_str())
     }
 
     #[inline]
-    fn fmt_underline(self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_underline_to<W: core::fmt::Write + ?Sized>(self, w: &mut W) -> core::fmt::Result {
+        if let Some(capped) = crate::cap::cap(crate::Color::Rgb(self)) {
+            return w.write_str(capped.underline_escape());
+        }
+
         let mut buffer = RgbBuffer::new();
         buffer.write_escape_start(Layer::Underline);
         buffer.write_args(self.red, self.green, self.blue);
         buffer.write_escape_end();
-        f.write_str(buffer.to_str())
+        w.write_str(buffer.to_str())
     }
 }
 
@@ -324,42 +824,14 @@ impl<const RED: u8, const GREEN: u8, const BLUE: u8> crate::seal::Seal for Rgb<R
 impl<const RED: u8, const GREEN: u8, const BLUE: u8> ColorSpec for Rgb<RED, GREEN, BLUE> {
     type Dynamic = RgbColor;
 
-    const KIND: crate::mode::ColorKind = crate::mode::ColorKind::Rgb;
+    const KIND: crate::mode::ColorKind = crate::cap::kind(crate::mode::ColorKind::Rgb);
 
     #[inline]
     fn into_dynamic(self) -> Self::Dynamic {
         Self::DYNAMIC
     }
 
-    #[inline]
-    fn foreground_args(self) -> &'static str {
-        Self::FOREGROUND_ARGS
-    }
-
-    #[inline]
-    fn background_args(self) -> &'static str {
-        Self::BACKGROUND_ARGS
-    }
-
-    #[inline]
-    fn underline_args(self) -> &'static str {
-        Self::UNDERLINE_ARGS
-    }
-
-    #[inline]
-    fn foreground_escape(self) -> &'static str {
-        Self::FOREGROUND_ESCAPE
-    }
-
-    #[inline]
-    fn background_escape(self) -> &'static str {
-        Self::BACKGROUND_ESCAPE
-    }
-
-    #[inline]
-    fn underline_escape(self) -> &'static str {
-        Self::UNDERLINE_ESCAPE
-    }
+    comptime_capped_methods!(crate::Color::Rgb(Self::DYNAMIC));
 }
 
 impl From<RgbColor> for crate::Color {
@@ -409,6 +881,384 @@ impl<const RED: u8, const GREEN: u8, const BLUE: u8> crate::ComptimeColor
     const VALUE: Option<crate::Color> = Some(crate::Color::Rgb(Self::DYNAMIC));
 }
 
+/// A color in the [OKLab](https://bottosson.github.io/posts/oklab/) color space
+///
+/// OKLab is perceptually uniform, so distances and interpolation in OKLab match how
+/// different colors look much better than in rgb
+///
+/// ```rust
+/// use colorz::rgb::{Oklab, RgbColor};
+///
+/// let white = RgbColor { red: 255, green: 255, blue: 255 }.to_oklab();
+/// assert!((white.l - 1.0).abs() < 1e-3);
+/// assert!(white.a.abs() < 1e-3 && white.b.abs() < 1e-3);
+///
+/// let orange = RgbColor { red: 255, green: 128, blue: 0 };
+/// assert_eq!(RgbColor::from_oklab(orange.to_oklab()), orange);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Oklab {
+    /// The perceived lightness, from 0 (black) to 1 (white)
+    pub l: f32,
+    /// How green (negative) or red (positive) the color is
+    pub a: f32,
+    /// How blue (negative) or yellow (positive) the color is
+    pub b: f32,
+}
+
+/// A color in the OKLCH color space, the polar form of [`Oklab`]
+///
+/// ```rust
+/// use colorz::rgb::{Oklch, RgbColor};
+///
+/// let red = RgbColor { red: 255, green: 0, blue: 0 }.to_oklch();
+/// assert!((red.h - 29.2).abs() < 0.1);
+///
+/// // rotate the hue and lower the chroma to get a muted green with the same perceived lightness
+/// let green = RgbColor::from_oklch(Oklch { c: 0.1, h: red.h + 120.0, ..red });
+/// assert!((green.to_oklch().l - red.l).abs() < 0.01);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Oklch {
+    /// The perceived lightness, from 0 (black) to 1 (white)
+    pub l: f32,
+    /// The chroma (how colorful the color is), 0 for grays
+    pub c: f32,
+    /// The hue in degrees
+    pub h: f32,
+}
+
+#[cfg(feature = "std")]
+impl Oklab {
+    /// Convert to the polar form, [`Oklch`]
+    #[inline]
+    pub fn to_oklch(self) -> Oklch {
+        let h = self.b.atan2(self.a).to_degrees();
+
+        Oklch {
+            l: self.l,
+            c: self.a.hypot(self.b),
+            h: if h < 0.0 { h + 360.0 } else { h },
+        }
+    }
+
+    /// Linearly interpolate between two colors, where `t = 0.0` gives `self` and `t = 1.0` gives `other`
+    ///
+    /// Since OKLab is perceptually uniform, this gives smoother gradients than [`RgbColor::lerp`]
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let blue = RgbColor { red: 0, green: 0, blue: 255 }.to_oklab();
+    /// let white = RgbColor { red: 255, green: 255, blue: 255 }.to_oklab();
+    ///
+    /// assert_eq!(RgbColor::from_oklab(blue.lerp(white, 0.0)), RgbColor { red: 0, green: 0, blue: 255 });
+    /// assert_eq!(RgbColor::from_oklab(blue.lerp(white, 1.0)), RgbColor { red: 255, green: 255, blue: 255 });
+    /// ```
+    #[inline]
+    pub const fn lerp(self, other: Oklab, t: f32) -> Oklab {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        Oklab {
+            l: self.l + (other.l - self.l) * t,
+            a: self.a + (other.a - self.a) * t,
+            b: self.b + (other.b - self.b) * t,
+        }
+    }
+
+    /// The perceptual distance between two colors (also called ΔEOK)
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let red = RgbColor { red: 255, green: 0, blue: 0 }.to_oklab();
+    /// let dark_red = RgbColor { red: 200, green: 0, blue: 0 }.to_oklab();
+    /// let blue = RgbColor { red: 0, green: 0, blue: 255 }.to_oklab();
+    ///
+    /// assert!(red.distance(dark_red) < red.distance(blue));
+    /// ```
+    #[inline]
+    pub fn distance(self, other: Oklab) -> f32 {
+        let l = self.l - other.l;
+        let a = self.a - other.a;
+        let b = self.b - other.b;
+        (l * l + a * a + b * b).sqrt()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Oklch {
+    /// Convert to the rectangular form, [`Oklab`]
+    #[inline]
+    pub fn to_oklab(self) -> Oklab {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+
+        Oklab {
+            l: self.l,
+            a: self.c * cos,
+            b: self.c * sin,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Oklab> for Oklch {
+    #[inline]
+    fn from(color: Oklab) -> Self {
+        color.to_oklch()
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Oklch> for Oklab {
+    #[inline]
+    fn from(color: Oklch) -> Self {
+        color.to_oklab()
+    }
+}
+
+/// A kind of color vision deficiency, see [`RgbColor::simulate`]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Deficiency {
+    /// No red cones, so reds look darker and are confused with greens
+    Protanopia,
+    /// No green cones, so greens are confused with reds
+    Deuteranopia,
+    /// No blue cones, so blues are confused with greens and yellows with violets
+    Tritanopia,
+}
+
+/// Convert an sRGB component to linear light, in the range `0.0..=1.0`
+#[cfg(feature = "std")]
+pub(crate) fn to_linear(x: u8) -> f32 {
+    let x = x as f32 / 255.0;
+
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light component to sRGB, clamping it to the valid range
+#[cfg(feature = "std")]
+pub(crate) fn from_linear(x: f32) -> u8 {
+    let x = if x <= 0.0031308 {
+        x * 12.92
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    };
+
+    (x.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// the coefficients are copied as is from the reference implementation
+#[cfg(feature = "std")]
+#[allow(clippy::excessive_precision)]
+impl RgbColor {
+    /// Convert to the [`Oklab`] color space
+    #[inline]
+    pub fn to_oklab(self) -> Oklab {
+        let red = to_linear(self.red);
+        let green = to_linear(self.green);
+        let blue = to_linear(self.blue);
+
+        let l = (0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue).cbrt();
+        let m = (0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue).cbrt();
+        let s = (0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue).cbrt();
+
+        Oklab {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+
+    /// Convert from the [`Oklab`] color space, clamping colors which can't be shown in rgb
+    #[inline]
+    pub fn from_oklab(color: Oklab) -> Self {
+        let l = color.l + 0.3963377774 * color.a + 0.2158037573 * color.b;
+        let m = color.l - 0.1055613458 * color.a - 0.0638541728 * color.b;
+        let s = color.l - 0.0894841775 * color.a - 1.2914855480 * color.b;
+
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+        Self {
+            red: from_linear(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+            green: from_linear(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+            blue: from_linear(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+        }
+    }
+
+    /// Convert to the [`Oklch`] color space
+    #[inline]
+    pub fn to_oklch(self) -> Oklch {
+        self.to_oklab().to_oklch()
+    }
+
+    /// Convert from the [`Oklch`] color space, clamping colors which can't be shown in rgb
+    #[inline]
+    pub fn from_oklch(color: Oklch) -> Self {
+        Self::from_oklab(color.to_oklab())
+    }
+
+    /// The [relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance) of this color,
+    /// from `0.0` (black) to `1.0` (white)
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// assert_eq!(RgbColor { red: 0, green: 0, blue: 0 }.relative_luminance(), 0.0);
+    /// assert_eq!(RgbColor { red: 255, green: 255, blue: 255 }.relative_luminance(), 1.0);
+    /// assert!((RgbColor { red: 255, green: 0, blue: 0 }.relative_luminance() - 0.2126).abs() < 1e-4);
+    /// ```
+    #[inline]
+    pub fn relative_luminance(self) -> f32 {
+        0.2126 * to_linear(self.red)
+            + 0.7152 * to_linear(self.green)
+            + 0.0722 * to_linear(self.blue)
+    }
+
+    /// The [contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio) between two colors,
+    /// from `1.0` (no contrast) to `21.0` (black and white)
+    ///
+    /// WCAG recommends a contrast ratio of at least `4.5` between text and it's background
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let black = RgbColor { red: 0, green: 0, blue: 0 };
+    /// let white = RgbColor { red: 255, green: 255, blue: 255 };
+    /// let gray = RgbColor { red: 118, green: 118, blue: 118 };
+    ///
+    /// assert!((black.contrast_ratio(white) - 21.0).abs() < 1e-4);
+    /// assert_eq!(black.contrast_ratio(white), white.contrast_ratio(black));
+    /// assert!(gray.contrast_ratio(white) > 4.5);
+    /// ```
+    #[inline]
+    pub fn contrast_ratio(self, other: RgbColor) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Simulate how this color looks to someone with the given color vision deficiency
+    ///
+    /// This uses the model by [Machado et al. (2009)](https://www.inf.ufrgs.br/~oliveira/pubs_files/CVD_Simulation/CVD_Simulation.html),
+    /// which is useful to check that the colors in a theme can still be told apart.
+    ///
+    /// ```rust
+    /// use colorz::rgb::{Deficiency, RgbColor};
+    ///
+    /// let red = RgbColor { red: 255, green: 0, blue: 0 };
+    /// let green = RgbColor { red: 0, green: 255, blue: 0 };
+    /// let white = RgbColor { red: 255, green: 255, blue: 255 };
+    ///
+    /// // grays aren't affected
+    /// assert_eq!(white.simulate(Deficiency::Deuteranopia), white);
+    ///
+    /// // red and green are much harder to tell apart
+    /// let before = red.to_oklab().distance(green.to_oklab());
+    /// let after = red.simulate(Deficiency::Deuteranopia).to_oklab()
+    ///     .distance(green.simulate(Deficiency::Deuteranopia).to_oklab());
+    /// assert!(after < before / 2.0);
+    /// ```
+    #[inline]
+    pub fn simulate(self, deficiency: Deficiency) -> RgbColor {
+        let matrix = match deficiency {
+            Deficiency::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Deficiency::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Deficiency::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        };
+
+        let color = [
+            to_linear(self.red),
+            to_linear(self.green),
+            to_linear(self.blue),
+        ];
+        let [red, green, blue] =
+            matrix.map(|row| row[0] * color[0] + row[1] * color[1] + row[2] * color[2]);
+
+        RgbColor {
+            red: from_linear(red),
+            green: from_linear(green),
+            blue: from_linear(blue),
+        }
+    }
+
+    /// Black or white, whichever has a higher contrast ratio with this color
+    ///
+    /// This is useful to pick a readable foreground color for text on this background color.
+    /// For ANSI and Xterm backgrounds, use `mode::TerminalPalette::contrasting_text` (with the `std` feature)
+    /// to use the color the terminal actually shows.
+    ///
+    /// ```rust
+    /// use colorz::rgb::RgbColor;
+    ///
+    /// let black = RgbColor { red: 0, green: 0, blue: 0 };
+    /// let white = RgbColor { red: 255, green: 255, blue: 255 };
+    ///
+    /// assert_eq!(RgbColor { red: 255, green: 255, blue: 0 }.contrasting_text(), black);
+    /// assert_eq!(RgbColor { red: 0, green: 0, blue: 128 }.contrasting_text(), white);
+    /// ```
+    #[inline]
+    pub fn contrasting_text(self) -> RgbColor {
+        const BLACK: RgbColor = RgbColor {
+            red: 0,
+            green: 0,
+            blue: 0,
+        };
+        const WHITE: RgbColor = RgbColor {
+            red: 255,
+            green: 255,
+            blue: 255,
+        };
+
+        // the contrast ratio with black is (l + 0.05) / 0.05, and with white is 1.05 / (l + 0.05),
+        // these are equal when (l + 0.05)^2 = 1.05 * 0.05
+        if self.relative_luminance() > 0.179_128_8 {
+            BLACK
+        } else {
+            WHITE
+        }
+    }
+}
+
+#[test]
+fn test_from_hsl() {
+    let rgb = |red, green, blue| RgbColor { red, green, blue };
+
+    assert_eq!(RgbColor::from_hsl(0, 0, 0), rgb(0, 0, 0));
+    assert_eq!(RgbColor::from_hsl(0, 0, 100), rgb(255, 255, 255));
+    assert_eq!(RgbColor::from_hsl(0, 100, 50), rgb(255, 0, 0));
+    assert_eq!(RgbColor::from_hsl(120, 100, 50), rgb(0, 255, 0));
+    assert_eq!(RgbColor::from_hsl(240, 100, 50), rgb(0, 0, 255));
+    assert_eq!(RgbColor::from_hsl(120, 50, 50), rgb(64, 191, 64));
+    assert_eq!(RgbColor::from_hsl(300, 76, 72), rgb(238, 129, 238));
+    assert_eq!(RgbColor::from_hsl(360 + 60, 100, 50), rgb(255, 255, 0));
+}
+
 #[test]
 fn test_write_u8() {
     let mut buffer = RgbBuffer::new();
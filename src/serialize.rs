@@ -0,0 +1,83 @@
+//! `serde::Serialize` impls for styles and styled values
+
+use core::fmt;
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::{OptionalColor, Style, StyledValue};
+
+/// Writes a style in the form accepted by [`Style::parse`], like `bold red on blue underline:green`
+struct StyleSpec(Style);
+
+impl fmt::Display for StyleSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+
+        for effect in self.0.effects {
+            write!(f, "{separator}{effect}")?;
+            separator = " ";
+        }
+
+        if let Some(color) = self.0.foreground {
+            write!(f, "{separator}{color}")?;
+            separator = " ";
+        }
+
+        if let Some(color) = self.0.background {
+            write!(f, "{separator}on {color}")?;
+            separator = " ";
+        }
+
+        if let Some(color) = self.0.underline_color {
+            write!(f, "{separator}underline:{color}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Styles are serialized as a string which can be parsed back into the same style, like `bold red on blue`
+///
+/// This is the same form used to deserialize a [`Theme`](crate::theme::Theme).
+///
+/// ```rust
+/// use colorz::{Style, ansi, css};
+///
+/// let style = Style::new().fg(ansi::Red).bg(css::DodgerBlue).italics();
+/// assert_eq!(serde_json::to_string(&style).unwrap(), r#""italics red on DodgerBlue""#);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Serialize for Style<F, B, U> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&StyleSpec(self.to_runtime()))
+    }
+}
+
+/// Styled values are serialized as only their value by default, so structured output (like JSON logs)
+/// never contains escape sequences. Use [`StyledValue::serialize_style`] to serialize a `{ value, style }`
+/// struct instead, where the style is serialized as a string (see the `Serialize` impl on [`Style`]).
+///
+/// ```rust
+/// use colorz::Colorize;
+///
+/// let count = 3.into_green();
+/// assert_eq!(serde_json::to_string(&count).unwrap(), "3");
+/// assert_eq!(serde_json::to_string(&count.serialize_style()).unwrap(), r#"{"value":3,"style":"green"}"#);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<T: Serialize, F: OptionalColor, B: OptionalColor, U: OptionalColor> Serialize
+    for StyledValue<T, F, B, U>
+{
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !self.options.serialize_style {
+            return self.value.serialize(serializer);
+        }
+
+        let mut value = serializer.serialize_struct("StyledValue", 2)?;
+        value.serialize_field("value", &self.value)?;
+        value.serialize_field("style", &self.resolved_style())?;
+        value.end()
+    }
+}
@@ -0,0 +1,525 @@
+//! Parsing text which contains SGR (Select Graphic Rendition) escape sequences
+//!
+//! This is the inverse of rendering styled values, it recovers the [`Style`] of each piece of text.
+//! This is useful to re-style or transform the colored output of other tools.
+//!
+//! This also parses the `key=args` lists used to configure colors via environment
+//! variables like `GREP_COLORS` and `GCC_COLORS`, see [`KeyStyles`].
+
+use crate::{
+    ansi::AnsiColor, rgb::RgbColor, strip::EscapeScanner, xterm::XtermColor, Color, Effect, Style,
+};
+
+/// Update `style` with the SGR arguments in `args` (the part between `ESC [` and `m`)
+///
+/// Unknown or malformed arguments are ignored
+pub(crate) fn apply_args(style: &mut Style, args: &str) {
+    let mut params = args.split(';');
+
+    while let Some(param) = params.next() {
+        let mut subparams = param.split(':');
+        let code = subparams.next().unwrap_or_default();
+
+        // an empty parameter is the same as 0
+        let Ok(code) = (if code.is_empty() {
+            Ok(0)
+        } else {
+            code.parse::<u16>()
+        }) else {
+            continue;
+        };
+
+        match code {
+            0 => *style = Style::new().into_runtime_style(),
+            1 => style.effects.set(Effect::Bold),
+            2 => style.effects.set(Effect::Dimmed),
+            3 => style.effects.set(Effect::Italic),
+            4 => match subparams.next() {
+                None => style.effects.set(Effect::Underline),
+                Some("0") => clear_underline(style),
+                Some("2") => {
+                    clear_underline(style);
+                    style.effects.set(Effect::DoubleUnderline);
+                }
+                // curly, dotted and dashed underlines are shown as plain underlines
+                Some(_) => {
+                    clear_underline(style);
+                    style.effects.set(Effect::Underline);
+                }
+            },
+            5 => style.effects.set(Effect::Blink),
+            6 => style.effects.set(Effect::BlinkFast),
+            7 => style.effects.set(Effect::Reversed),
+            8 => style.effects.set(Effect::Hidden),
+            9 => style.effects.set(Effect::Strikethrough),
+            21 => {
+                clear_underline(style);
+                style.effects.set(Effect::DoubleUnderline);
+            }
+            22 => {
+                style.effects.unset(Effect::Bold);
+                style.effects.unset(Effect::Dimmed);
+            }
+            23 => style.effects.unset(Effect::Italic),
+            24 => clear_underline(style),
+            25 => {
+                style.effects.unset(Effect::Blink);
+                style.effects.unset(Effect::BlinkFast);
+            }
+            27 => style.effects.unset(Effect::Reversed),
+            28 => style.effects.unset(Effect::Hidden),
+            29 => style.effects.unset(Effect::Strikethrough),
+            39 => style.foreground = None,
+            49 => style.background = None,
+            51 => style.effects.set(Effect::Framed),
+            52 => style.effects.set(Effect::Encircled),
+            53 => style.effects.set(Effect::Overline),
+            54 => {
+                style.effects.unset(Effect::Framed);
+                style.effects.unset(Effect::Encircled);
+            }
+            55 => style.effects.unset(Effect::Overline),
+            59 => style.underline_color = None,
+            73 => style.effects.set(Effect::SuperScript),
+            74 => style.effects.set(Effect::SubScript),
+            75 => {
+                style.effects.unset(Effect::SuperScript);
+                style.effects.unset(Effect::SubScript);
+            }
+            38 | 48 | 58 => {
+                // the color may either be given as subparameters (`38:5:n`) or as parameters (`38;5;n`)
+                let color = if param.contains(':') {
+                    parse_extended_color(subparams, true)
+                } else {
+                    parse_extended_color(&mut params, false)
+                };
+
+                if let Some(color) = color {
+                    match code {
+                        38 => style.foreground = Some(color),
+                        48 => style.background = Some(color),
+                        _ => style.underline_color = Some(color),
+                    }
+                }
+            }
+            _ => {
+                let code = u8::try_from(code).unwrap_or(u8::MAX);
+                if let Some(color) = AnsiColor::from_foreground_code(code) {
+                    style.foreground = Some(Color::Ansi(color));
+                } else if let Some(color) = AnsiColor::from_background_code(code) {
+                    style.background = Some(Color::Ansi(color));
+                }
+            }
+        }
+    }
+}
+
+const fn clear_underline(style: &mut Style) {
+    style.effects.unset(Effect::Underline);
+    style.effects.unset(Effect::DoubleUnderline);
+}
+
+/// Parse the arguments after `38`, `48` or `58`, either `5;n` or `2;r;g;b`
+///
+/// The subparameter form of rgb colors may have a color space id before the components (`2::r:g:b`)
+fn parse_extended_color<'a>(
+    mut args: impl Iterator<Item = &'a str>,
+    subparams: bool,
+) -> Option<Color> {
+    let mut component = || args.next()?.parse::<u8>().ok();
+
+    match component()? {
+        5 => Some(Color::Xterm(XtermColor::from_code(component()?))),
+        2 => {
+            let mut rgb = [0; 4];
+            let mut len = 0;
+
+            for slot in &mut rgb {
+                match args.next() {
+                    Some(arg) => {
+                        *slot = if arg.is_empty() { 0 } else { arg.parse().ok()? };
+                        len += 1;
+                    }
+                    None => break,
+                }
+
+                if len == 3 && !subparams {
+                    break;
+                }
+            }
+
+            let [red, green, blue] = match len {
+                3 => [rgb[0], rgb[1], rgb[2]],
+                4 => [rgb[1], rgb[2], rgb[3]],
+                _ => return None,
+            };
+
+            Some(Color::Rgb(RgbColor { red, green, blue }))
+        }
+        _ => None,
+    }
+}
+
+/// Split text containing SGR escape sequences into pieces of text, and the style of each piece
+///
+/// All escape sequences are removed from the text, escape sequences which aren't SGR sequences
+/// (like hyperlinks) are ignored. Empty pieces of text are skipped.
+///
+/// ```rust
+/// use colorz::{Style, ansi, sgr};
+///
+/// let mut spans = sgr::spans("plain \x1b[1;31mbold red\x1b[22m red\x1b[0m");
+///
+/// assert_eq!(spans.next(), Some((Style::new().into_runtime_style(), "plain ")));
+/// assert_eq!(spans.next(), Some((Style::new().fg(ansi::Red).bold().into_runtime_style(), "bold red")));
+/// assert_eq!(spans.next(), Some((Style::new().fg(ansi::Red).into_runtime_style(), " red")));
+/// assert_eq!(spans.next(), None);
+/// ```
+#[inline]
+pub const fn spans(text: &str) -> SgrSpans<'_> {
+    SgrSpans::new(text)
+}
+
+/// An iterator over the styled pieces of some text, see [`spans`]
+#[derive(Debug, Clone)]
+pub struct SgrSpans<'a> {
+    rest: &'a str,
+    style: Style,
+}
+
+impl<'a> SgrSpans<'a> {
+    /// Create an iterator over the styled pieces of `text`, starting with no style
+    #[inline]
+    pub const fn new(text: &'a str) -> Self {
+        Self::with_style(text, Style::new().const_into_runtime_style())
+    }
+
+    /// Create an iterator over the styled pieces of `text`, starting with the given style
+    #[inline]
+    pub const fn with_style(text: &'a str, style: Style) -> Self {
+        Self { rest: text, style }
+    }
+
+    /// The style at the current position in the text
+    ///
+    /// After the iterator is exhausted, this is the style at the end of the text
+    #[inline]
+    pub const fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Skip the escape sequence at the start of `rest`, applying it if it's an SGR sequence
+    fn skip_escape(&mut self) {
+        if let Some(csi) = self.rest.strip_prefix("\x1b[") {
+            let end = csi
+                .bytes()
+                .position(|b| (0x40..=0x7e).contains(&b))
+                .unwrap_or(csi.len());
+
+            let (args, rest) = csi.split_at(end);
+
+            // private sequences (like `ESC [ ? 25 h`) start with `<`, `=`, `>` or `?`
+            if rest.starts_with('m') && !args.starts_with(['<', '=', '>', '?']) {
+                apply_args(&mut self.style, args);
+            }
+
+            self.rest = rest.get(1..).unwrap_or_default();
+            return;
+        }
+
+        let mut scanner = EscapeScanner::new();
+        let mut chars = self.rest.char_indices();
+        for (_, c) in chars.by_ref() {
+            scanner.next(c);
+            if scanner.is_text() {
+                break;
+            }
+        }
+
+        self.rest = chars.as_str();
+    }
+}
+
+impl<'a> Iterator for SgrSpans<'a> {
+    type Item = (Style, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let end = self.rest.find('\x1b').unwrap_or(self.rest.len());
+
+            if end == 0 {
+                self.skip_escape();
+                continue;
+            }
+
+            let (text, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            return Some((self.style, text));
+        }
+    }
+}
+
+impl core::iter::FusedIterator for SgrSpans<'_> {}
+
+/// Parse a `key=args` entry, where `args` are SGR arguments
+///
+/// Returns `None` if there is no `=`, or `args` isn't a list of SGR arguments
+pub(crate) fn parse_entry(entry: &str) -> Option<(&str, Style)> {
+    let (key, args) = entry.split_once('=')?;
+
+    if !args.bytes().all(|b| b.is_ascii_digit() || b == b';') {
+        return None;
+    }
+
+    Some((key, Style::from_sgr_args(args)))
+}
+
+impl Style {
+    /// Parse a raw list of SGR arguments (the part between `ESC [` and `m`), like `1;38;5;208`
+    ///
+    /// This is the format used by `LS_COLORS`, `GREP_COLORS` and terminfo. Both the `;` separated
+    /// (`38;2;r;g;b`) and the `:` separated (`38:2::r:g:b`) forms of extended colors are supported.
+    /// Like terminals, unknown or malformed arguments are ignored, and later arguments override earlier ones
+    /// (so `0` resets everything before it).
+    ///
+    /// ```rust
+    /// use colorz::{Color, Effect, Style, rgb::RgbColor, xterm::XtermColor};
+    ///
+    /// let style = Style::from_sgr_args("1;38;5;208;48;2;0;0;0");
+    ///
+    /// assert!(style.is(Effect::Bold));
+    /// assert_eq!(style.foreground, Some(Color::Xterm(XtermColor::from_code(208))));
+    /// assert_eq!(style.background, Some(Color::Rgb(RgbColor { red: 0, green: 0, blue: 0 })));
+    /// assert_eq!(Style::from_sgr_args("31;0;4"), Style::new().underline().into_runtime_style());
+    /// ```
+    #[inline]
+    pub fn from_sgr_args(args: &str) -> Style {
+        let mut style = Style::new().into_runtime_style();
+        apply_args(&mut style, args);
+        style
+    }
+}
+
+/// Styles stored as a `:` separated list of `key=args` entries, where `args` are SGR arguments
+///
+/// This is the format used by `GREP_COLORS`, `GCC_COLORS` and many other tools, like
+/// `ms=01;31:fn=35:ln=32:ne`. Entries without an `=` (like `ne`) are boolean flags.
+/// For `LS_COLORS`, see [`LsColors`](crate::ls_colors::LsColors), which also handles file name patterns.
+///
+/// ```rust
+/// use colorz::{Style, ansi, sgr::KeyStyles};
+///
+/// // usually read from the `GREP_COLORS` environment variable
+/// let colors = KeyStyles::new("ms=01;31:fn=35:sl=:ne");
+///
+/// assert_eq!(colors.get("ms"), Some(Style::new().fg(ansi::Red).bold().into_runtime_style()));
+/// assert_eq!(colors.get("fn"), Some(Style::new().fg(ansi::Magenta).into_runtime_style()));
+/// assert_eq!(colors.get("sl"), Some(Style::new().into_runtime_style()));
+/// assert_eq!(colors.get("ln"), None);
+///
+/// assert!(colors.is_set("ne"));
+/// assert!(!colors.is_set("rv"));
+/// ```
+///
+/// This borrows the list and parses it on each lookup, so no allocation is needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyStyles<'a> {
+    spec: &'a str,
+}
+
+impl<'a> KeyStyles<'a> {
+    /// Read the styles in `spec`
+    #[inline]
+    pub const fn new(spec: &'a str) -> Self {
+        Self { spec }
+    }
+
+    /// Iterate over all `key=args` entries, in the order they were written
+    ///
+    /// Flags and malformed entries are skipped
+    #[inline]
+    pub fn entries(&self) -> KeyStylesIter<'a> {
+        KeyStylesIter {
+            rest: self.spec.split(':'),
+        }
+    }
+
+    /// The style for the given key, if the key appears multiple times the last entry is used
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<Style> {
+        self.entries()
+            .filter(|&(entry, _)| entry == key)
+            .last()
+            .map(|(_, style)| style)
+    }
+
+    /// Is the boolean flag `key` (an entry without an `=`) in the list
+    #[inline]
+    pub fn is_set(&self, key: &str) -> bool {
+        self.spec.split(':').any(|entry| entry == key)
+    }
+}
+
+/// An iterator over the entries of [`KeyStyles`], see [`KeyStyles::entries`]
+#[derive(Debug, Clone)]
+pub struct KeyStylesIter<'a> {
+    rest: core::str::Split<'a, char>,
+}
+
+impl<'a> Iterator for KeyStylesIter<'a> {
+    type Item = (&'a str, Style);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rest.find_map(parse_entry)
+    }
+}
+
+impl core::iter::FusedIterator for KeyStylesIter<'_> {}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    fn parse(args: &str) -> Style {
+        Style::from_sgr_args(args)
+    }
+
+    #[test]
+    fn extended_colors() {
+        let red = Some(Color::Rgb(RgbColor {
+            red: 255,
+            green: 0,
+            blue: 0,
+        }));
+
+        assert_eq!(parse("38;2;255;0;0").foreground, red);
+        assert_eq!(parse("48:2:255:0:0").background, red);
+        assert_eq!(parse("58:2::255:0:0").underline_color, red);
+        assert_eq!(
+            parse("38;5;200;1").foreground,
+            Some(Color::Xterm(XtermColor::from_code(200)))
+        );
+        assert!(parse("1;38;5;200").is(Effect::Bold));
+        assert_eq!(parse("38;2;255;0").foreground, None);
+    }
+
+    #[test]
+    fn effects() {
+        assert_eq!(
+            parse("1;2;4:2"),
+            Style::new()
+                .bold()
+                .dimmed()
+                .double_underline()
+                .into_runtime_style()
+        );
+        assert_eq!(
+            parse("1;2;22;3"),
+            Style::new().italics().into_runtime_style()
+        );
+        assert_eq!(parse("4;24;9;0"), Style::new().into_runtime_style());
+        assert_eq!(parse("4;21"), parse("4;4:2"));
+        assert_eq!(parse("4:3"), Style::new().underline().into_runtime_style());
+        assert_eq!(
+            parse("51;52;74"),
+            Style::new()
+                .framed()
+                .encircled()
+                .subscript()
+                .into_runtime_style()
+        );
+        assert_eq!(parse("51;52;54"), Style::new().into_runtime_style());
+    }
+
+    #[test]
+    fn round_trip() {
+        use crate::{ansi, css, xterm};
+
+        let styles = [
+            Style::new()
+                .fg(ansi::BrightRed)
+                .bg(ansi::Blue)
+                .into_runtime_style(),
+            Style::new()
+                .fg(xterm::Aqua)
+                .underline_color(css::Tomato)
+                .underline()
+                .strikethrough()
+                .into_runtime_style(),
+            Style::new()
+                .bg(xterm::Gray0)
+                .bold()
+                .italics()
+                .into_runtime_style(),
+            Style::new().superscript().encircled().into_runtime_style(),
+        ];
+
+        let mut buffer = [0u8; 128];
+        for style in styles {
+            let mut out = Buffer::new(&mut buffer);
+            core::fmt::Write::write_fmt(&mut out, format_args!("{}x", style.apply())).unwrap();
+
+            let mut spans = spans(out.as_str());
+            let (parsed, text) = spans.next().unwrap();
+            assert_eq!(text, "x");
+
+            // colors are capped by the `max-colors-*` features, css colors are written
+            // as rgb colors, and ansi underline colors are written as xterm colors
+            let style = style.downgrade(crate::mode::MAX_COLOR_KIND);
+            let expected = Style {
+                underline_color: style.underline_color.map(|color| match color {
+                    Color::Css(color) => Color::Rgb(color.into()),
+                    Color::Ansi(color) => Color::Xterm(color.into()),
+                    color => color,
+                }),
+                ..style
+            };
+            assert_eq!(parsed, expected);
+        }
+    }
+
+    #[test]
+    fn skip_other_escapes() {
+        let mut spans =
+            spans("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\ \x1b[?25ltext\x1b[31");
+        let plain = Style::new().into_runtime_style();
+
+        assert_eq!(spans.next(), Some((plain, "link")));
+        assert_eq!(spans.next(), Some((plain, " ")));
+        assert_eq!(spans.next(), Some((plain, "text")));
+        assert_eq!(spans.next(), None);
+    }
+
+    pub(crate) struct Buffer<'a> {
+        data: &'a mut [u8],
+        len: usize,
+    }
+
+    impl<'a> Buffer<'a> {
+        pub(crate) fn new(data: &'a mut [u8]) -> Self {
+            Self { data, len: 0 }
+        }
+
+        pub(crate) fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.data[..self.len]).unwrap()
+        }
+    }
+
+    impl core::fmt::Write for Buffer<'_> {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            self.data
+                .get_mut(self.len..end)
+                .ok_or(core::fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+}
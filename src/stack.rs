@@ -0,0 +1,130 @@
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use crate::{mode::Stream, OptionalColor, Style};
+
+/// A stack of styles, which tracks the style that was last written to the terminal
+///
+/// Each style pushed onto the stack is layered on top of the styles below it (see [`Style::merge`]).
+/// Pushing and popping styles doesn't write anything, instead [`write_transition`](Self::write_transition)
+/// writes the minimal escapes needed to switch the terminal from the last written style to the
+/// [`current`](Self::current) style. This makes it easy to correctly nest styles across many writes.
+///
+/// ```rust
+/// use core::fmt::Write;
+/// use colorz::{Style, StyleStack, ansi, mode::Stream};
+///
+/// let mut out = String::new();
+/// let mut stack = StyleStack::new().stream(Stream::AlwaysColor);
+///
+/// stack.push(Style::new().fg(ansi::Red));
+/// stack.write_transition(&mut out)?;
+/// out.push_str("error: ");
+///
+/// stack.push(Style::new().bold());
+/// stack.write_transition(&mut out)?;
+/// out.push_str("file not found");
+/// stack.pop();
+///
+/// stack.pop();
+/// stack.write_transition(&mut out)?;
+///
+/// # if Style::new().bold().should_color(Stream::AlwaysColor) {
+/// assert_eq!(out, "\x1b[31merror: \x1b[1mfile not found\x1b[22;39m");
+/// # }
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StyleStack {
+    // the effective style at each level of the stack
+    stack: Vec<Style>,
+    written: Style,
+    /// The stream to use
+    pub stream: Option<Stream>,
+}
+
+impl Default for StyleStack {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StyleStack {
+    /// Create an empty style stack
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            stack: Vec::new(),
+            written: Style::new().const_into_runtime_style(),
+            stream: None,
+        }
+    }
+
+    /// Sets the stream for the given stack
+    #[inline]
+    pub const fn stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Layer a style on top of the current style
+    #[inline]
+    pub fn push<F, B, U>(&mut self, style: Style<F, B, U>)
+    where
+        F: OptionalColor,
+        B: OptionalColor,
+        U: OptionalColor,
+    {
+        let style = self.current().merge(style.to_runtime());
+        self.stack.push(style);
+    }
+
+    /// Remove the top style, and return the style that was in effect before popping
+    #[inline]
+    pub fn pop(&mut self) -> Option<Style> {
+        self.stack.pop()
+    }
+
+    /// The style that should be in effect, this is the combination of all styles on the stack
+    #[inline]
+    pub fn current(&self) -> Style {
+        self.stack
+            .last()
+            .copied()
+            .unwrap_or(Style::new().const_into_runtime_style())
+    }
+
+    /// The number of styles on the stack
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Are there no styles on the stack
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Write the escapes needed to switch from the last written style to the [`current`](Self::current) style
+    ///
+    /// If the stack's stream shouldn't be colored (see `Coloring Mode` in the crate docs),
+    /// then nothing is written.
+    #[inline]
+    pub fn write_transition<W: fmt::Write + ?Sized>(&mut self, f: &mut W) -> fmt::Result {
+        let current = self.current();
+
+        if current == self.written {
+            return Ok(());
+        }
+
+        if current.should_color(self.stream) {
+            write!(f, "{}", current.transition_from(&self.written))?;
+            self.written = current;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,391 @@
+//! Utilities for text which already contains ANSI escape sequences
+//!
+//! The escape sequences which are recognized are
+//! * `CSI` sequences (`ESC [`), which includes all SGR sequences written by this crate
+//! * `OSC` sequences (`ESC ]`), like hyperlinks and window titles,
+//!   as well as `DCS`, `SOS`, `PM` and `APC` sequences which are terminated the same way
+//! * any other two character escape sequences (like `ESC 7`)
+
+use core::fmt;
+
+/// An incremental scanner for ANSI escape sequences
+///
+/// This keeps track of whether the next character is part of an escape sequence,
+/// so text can be fed to it in pieces (for example from [`core::fmt::Write::write_str`])
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EscapeScanner {
+    state: State,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum State {
+    #[default]
+    Text,
+    /// After an `ESC`
+    Escape,
+    /// After an `ESC` and some intermediate bytes, which ends with a byte in `0x30..=0x7e`
+    Intermediate,
+    /// Inside a `CSI` sequence, which ends with a byte in `0x40..=0x7e`
+    Csi,
+    /// Inside a string sequence (like `OSC`), which ends with `BEL` or `ESC \`
+    String,
+    /// After an `ESC` inside a string sequence
+    StringEscape,
+}
+
+impl EscapeScanner {
+    /// Create a scanner which is outside of any escape sequence
+    pub(crate) const fn new() -> Self {
+        Self { state: State::Text }
+    }
+
+    /// Is this scanner outside of any escape sequence
+    pub(crate) const fn is_text(&self) -> bool {
+        matches!(self.state, State::Text)
+    }
+
+    /// Advance the scanner by one character, and return true if the character is visible text
+    pub(crate) const fn next(&mut self, c: char) -> bool {
+        let (state, is_text) = match (self.state, c) {
+            (State::Text, '\x1b') => (State::Escape, false),
+            (State::Text, _) => (State::Text, true),
+            (State::Escape, '[') => (State::Csi, false),
+            (State::Escape, ']' | 'P' | 'X' | '^' | '_') => (State::String, false),
+            (State::Escape | State::Intermediate, '\x20'..='\x2f') => (State::Intermediate, false),
+            (State::Escape | State::Intermediate, _) => (State::Text, false),
+            (State::Csi, '\x40'..='\x7e') => (State::Text, false),
+            (State::Csi, _) => (State::Csi, false),
+            (State::String | State::StringEscape, '\x07') => (State::Text, false),
+            (State::String | State::StringEscape, '\x1b') => (State::StringEscape, false),
+            (State::StringEscape, '\\') => (State::Text, false),
+            (State::String | State::StringEscape, _) => (State::String, false),
+        };
+
+        self.state = state;
+        is_text
+    }
+}
+
+/// The width of a character when displayed in a terminal
+#[cfg(feature = "unicode-width")]
+fn char_width(c: char) -> usize {
+    unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+}
+
+/// The width of a character when displayed in a terminal
+#[cfg(not(feature = "unicode-width"))]
+const fn char_width(_: char) -> usize {
+    1
+}
+
+/// The width of the text when printed to a terminal, ignoring any ANSI escape sequences
+///
+/// By default each character is counted as one column. If the `unicode-width` feature is enabled,
+/// then the `unicode-width` crate is used instead, so wide characters (like most CJK characters)
+/// count as two columns and zero-width characters don't count at all.
+///
+/// ```rust
+/// use colorz::{Colorize, mode::Stream, strip::visible_width};
+///
+/// let styled = format!("{} world", "hello".red().stream(Stream::AlwaysColor));
+///
+/// assert_eq!(visible_width(&styled), 11);
+/// assert_eq!(visible_width("\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\"), 4);
+/// ```
+#[inline]
+pub fn visible_width(s: &str) -> usize {
+    let mut scanner = EscapeScanner::new();
+
+    s.chars().filter(|&c| scanner.next(c)).map(char_width).sum()
+}
+
+/// A writer which removes all ANSI escape sequences before writing to the inner writer
+struct Strip<'a, W: ?Sized> {
+    out: &'a mut W,
+    scanner: EscapeScanner,
+}
+
+impl<W: fmt::Write + ?Sized> fmt::Write for Strip<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut start = None;
+
+        for (i, c) in s.char_indices() {
+            match (self.scanner.next(c), start) {
+                (true, None) => start = Some(i),
+                (false, Some(run)) => {
+                    self.out.write_str(&s[run..i])?;
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+
+        match start {
+            Some(run) => self.out.write_str(&s[run..]),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A wrapper which displays the inner value with all ANSI escape sequences removed
+///
+/// This is useful to write the same message to a terminal and to a log file.
+/// The formatting flags (like `{:#}` or `{:.2}`) are passed to the inner value,
+/// except for the width, fill and alignment.
+///
+/// ```rust
+/// use colorz::{Colorize, mode::Stream, strip::StripAnsi};
+///
+/// let message = format!("{}: file not found", "error".red().stream(Stream::AlwaysColor));
+///
+/// assert_eq!(StripAnsi(&message).to_string(), "error: file not found");
+/// assert_eq!(StripAnsi("hello".red().stream(Stream::AlwaysColor)).to_string(), "hello");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StripAnsi<T>(pub T);
+
+impl<T: fmt::Display> fmt::Display for StripAnsi<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = crate::value::Flags::new(f);
+        let mut out = Strip {
+            out: f,
+            scanner: EscapeScanner::new(),
+        };
+        flags.write(&mut out, &self.0)
+    }
+}
+
+/// Write `value` to `out`, with all ANSI escape sequences removed
+#[cfg(feature = "html")]
+pub(crate) fn write_stripped(out: &mut dyn fmt::Write, value: &dyn fmt::Display) -> fmt::Result {
+    let mut out = Strip {
+        out,
+        scanner: EscapeScanner::new(),
+    };
+    fmt::Write::write_fmt(&mut out, format_args!("{value}"))
+}
+
+/// Write the text to `out`, with all ANSI escape sequences removed
+///
+/// ```rust
+/// use colorz::strip::strip_ansi_into;
+///
+/// let mut out = String::new();
+/// strip_ansi_into("\x1b[1mhello\x1b[22m world", &mut out)?;
+/// assert_eq!(out, "hello world");
+/// # Ok::<(), core::fmt::Error>(())
+/// ```
+#[inline]
+pub fn strip_ansi_into<W: fmt::Write + ?Sized>(s: &str, out: &mut W) -> fmt::Result {
+    fmt::Write::write_str(
+        &mut Strip {
+            out,
+            scanner: EscapeScanner::new(),
+        },
+        s,
+    )
+}
+
+/// Remove all ANSI escape sequences from the text
+///
+/// ```rust
+/// use colorz::strip::strip_ansi;
+///
+/// assert_eq!(strip_ansi("\x1b[31merror\x1b[39m: file not found"), "error: file not found");
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[inline]
+pub fn strip_ansi(s: &str) -> alloc::string::String {
+    let mut out = alloc::string::String::with_capacity(s.len());
+    // writing to a `String` can't fail
+    let _ = strip_ansi_into(s, &mut out);
+    out
+}
+
+/// A writer which passes output through when coloring is enabled for a stream,
+/// and removes all ANSI escape sequences otherwise
+///
+/// This lets output which already contains escape sequences (like from [`Style::apply`](crate::Style::apply),
+/// or from another program) respect the current coloring mode. Whether to color is decided
+/// once, when the writer is created, using [`mode::should_color`](crate::mode::should_color).
+///
+/// Escape sequences which are split across multiple writes are removed correctly.
+///
+/// ```rust
+/// use std::io::Write;
+/// use colorz::{AutoWriter, mode::Stream};
+///
+/// let mut writer = AutoWriter::new(Vec::new(), Stream::NeverColor);
+/// write!(writer, "\x1b[31merror\x1b[39m: file not found")?;
+/// write!(writer, "\x1b[")?;
+/// write!(writer, "1mdone\x1b[22m")?;
+/// assert_eq!(writer.into_inner(), b"error: file not founddone");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct AutoWriter<W> {
+    writer: W,
+    color: bool,
+    scanner: EscapeScanner,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> AutoWriter<W> {
+    /// Create a new writer which colors its output if `stream` should be colored
+    ///
+    /// ```rust
+    /// use colorz::{AutoWriter, mode::Stream};
+    ///
+    /// let writer = AutoWriter::new(std::io::stderr(), Stream::Stderr);
+    /// ```
+    #[inline]
+    pub fn new(writer: W, stream: crate::mode::Stream) -> Self {
+        Self {
+            writer,
+            color: crate::mode::should_color(stream, &[crate::mode::ColorKind::Ansi]),
+            scanner: EscapeScanner::new(),
+        }
+    }
+
+    /// Does this writer pass escape sequences through to the inner writer
+    ///
+    /// ```rust
+    /// use colorz::{AutoWriter, mode::Stream};
+    ///
+    /// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+    /// assert!(AutoWriter::new(Vec::new(), Stream::AlwaysColor).is_colored());
+    /// # }
+    /// assert!(!AutoWriter::new(Vec::new(), Stream::NeverColor).is_colored());
+    /// ```
+    #[inline]
+    pub const fn is_colored(&self) -> bool {
+        self.color
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W> AutoWriter<W> {
+    /// Get a reference to the inner writer
+    #[inline]
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get a mutable reference to the inner writer
+    ///
+    /// Writing directly to the inner writer bypasses stripping
+    #[inline]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Get the inner writer
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for AutoWriter<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.color {
+            return self.writer.write(buf);
+        }
+
+        // all bytes which are part of escape sequences are ASCII, so scanning
+        // byte by byte also works for text which is split inside of a UTF-8 character
+        let mut start = None;
+
+        for (i, &b) in buf.iter().enumerate() {
+            match (self.scanner.next(char::from(b)), start) {
+                (true, None) => start = Some(i),
+                (false, Some(run)) => {
+                    self.writer.write_all(&buf[run..i])?;
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(run) = start {
+            self.writer.write_all(&buf[run..])?;
+        }
+
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escapes_are_invisible() {
+        assert_eq!(visible_width(""), 0);
+        assert_eq!(visible_width("\x1b[1;38;2;255;0;0mred\x1b[0m"), 3);
+        assert_eq!(visible_width("\x1b]0;title\x07text"), 4);
+        assert_eq!(visible_width("\x1b(Bab\x1b7c"), 3);
+        // an unterminated escape hides the rest of the text
+        assert_eq!(visible_width("ab\x1b[12"), 2);
+    }
+
+    #[test]
+    fn strip_split_escapes() {
+        use core::fmt::Write;
+
+        struct Pieces(&'static [&'static str]);
+
+        impl fmt::Display for Pieces {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.iter().try_for_each(|piece| f.write_str(piece))
+            }
+        }
+
+        let mut out = Buffer::default();
+        let pieces = Pieces(&["a\x1b", "[3", "1mb", "\x1b]8;;x\x1b", "\\c\x1b[0", "m"]);
+        write!(out, "{}", StripAnsi(pieces)).unwrap();
+        assert_eq!(out.as_str(), "abc");
+    }
+
+    #[derive(Default)]
+    struct Buffer {
+        data: [u8; 32],
+        len: usize,
+    }
+
+    impl Buffer {
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.data[..self.len]).unwrap()
+        }
+    }
+
+    impl fmt::Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.data
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unicode-width")]
+    fn wide_characters() {
+        assert_eq!(visible_width("\x1b[31m日本\x1b[39m"), 4);
+        assert_eq!(visible_width("e\u{301}"), 1);
+    }
+}
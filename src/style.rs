@@ -1,6 +1,14 @@
-use core::{fmt, num::NonZeroU16};
-
-use crate::{ansi, mode::Stream, Color, ComptimeColor, OptionalColor, WriteColor};
+use core::{
+    fmt,
+    num::NonZeroU32,
+    ops::{Add, BitOr},
+};
+
+use crate::{
+    ansi,
+    mode::{ColorKind, Stream},
+    Color, ComptimeColor, OptionalColor, StyledValue, WriteColor,
+};
 
 /// A generic style format, this specifies the colors of the foreground, background, underline,
 /// and what effects the text should have (bold, italics, etc.)
@@ -39,12 +47,12 @@ pub struct Style<F = Option<Color>, B = Option<Color>, U = Option<Color>> {
     pub effects: EffectFlags,
 }
 
-const _: [(); core::mem::size_of::<Style>()] = [(); 14];
+const _: [(); core::mem::size_of::<Style>()] = [(); 16];
 
 /// A collection of [`Effect`]s
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct EffectFlags {
-    data: u16,
+    data: u32,
 }
 
 impl core::fmt::Debug for EffectFlags {
@@ -90,6 +98,9 @@ macro_rules! Effect {
 
         const ALL_EFFECTS: EffectFlags = EffectFlags::new() $(.with(Effect::$name))*;
 
+        /// All effects, in declaration order
+        const EFFECT_LIST: &[Effect] = &[$(Effect::$name,)*];
+
         impl Effect {
             fn decode(x: u8) -> Self {
                 #[cold]
@@ -136,7 +147,33 @@ macro_rules! Effect {
                 }
             }
 
-            const fn mask(self) -> u16 {
+            /// The name of the effect, this is the same as the name of the
+            /// corresponding [`Style`] method (like `bold` or `double_underline`)
+            ///
+            /// The name can be parsed back into an [`Effect`] via [`FromStr`](core::str::FromStr)
+            #[inline]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$name => stringify!($set_func),)*
+                }
+            }
+
+            /// Iterate over every effect, in declaration order
+            ///
+            /// This is equivalent to `EffectFlags::all().iter()`
+            ///
+            /// ```rust
+            /// use colorz::Effect;
+            ///
+            /// let names: Vec<&str> = Effect::variants().map(Effect::name).collect();
+            /// assert_eq!(names[..3], ["bold", "dimmed", "italics"]);
+            /// ```
+            #[inline]
+            pub const fn variants() -> EffectFlagsIter {
+                EffectFlags::all().iter()
+            }
+
+            const fn mask(self) -> u32 {
                 1 << self as u8
             }
         }
@@ -159,6 +196,8 @@ impl EffectFlags {
     }
 
     /// Create a set of all effects
+    ///
+    /// Iterating over this set yields the same effects as [`Effect::variants`]
     #[inline(always)]
     pub const fn all() -> Self {
         ALL_EFFECTS
@@ -202,6 +241,80 @@ impl EffectFlags {
         self.data & opt.data != 0
     }
 
+    /// Are there no effects, this is the same as [`is_plain`](Self::is_plain)
+    #[inline(always)]
+    pub const fn is_empty(self) -> bool {
+        self.is_plain()
+    }
+
+    /// The number of effects in the set
+    ///
+    /// ```rust
+    /// use colorz::{Effect, EffectFlags};
+    ///
+    /// assert_eq!(EffectFlags::new().len(), 0);
+    /// assert_eq!(EffectFlags::from_array([Effect::Bold, Effect::Italic]).len(), 2);
+    /// ```
+    #[inline(always)]
+    pub const fn len(self) -> usize {
+        self.data.count_ones() as usize
+    }
+
+    /// Are all effects in `other` also in this set
+    ///
+    /// ```rust
+    /// use colorz::{Effect, EffectFlags};
+    ///
+    /// let flags = EffectFlags::from_array([Effect::Bold, Effect::Italic]);
+    /// assert!(flags.contains_all(EffectFlags::from_array([Effect::Bold])));
+    /// assert!(!flags.contains_all(EffectFlags::from_array([Effect::Bold, Effect::Dimmed])));
+    /// ```
+    #[inline(always)]
+    pub const fn contains_all(self, other: EffectFlags) -> bool {
+        self.data & other.data == other.data
+    }
+
+    /// The effects which are in both sets
+    #[must_use = "EffectFlags::intersection returns a new instance without modifying the original"]
+    #[inline(always)]
+    pub const fn intersection(self, other: EffectFlags) -> Self {
+        Self {
+            data: self.data & other.data,
+        }
+    }
+
+    /// The effects which are in either set
+    #[must_use = "EffectFlags::union returns a new instance without modifying the original"]
+    #[inline(always)]
+    pub const fn union(self, other: EffectFlags) -> Self {
+        Self {
+            data: self.data | other.data,
+        }
+    }
+
+    /// The effects which are in this set, but not in `other`
+    ///
+    /// ```rust
+    /// use colorz::{Effect, EffectFlags};
+    ///
+    /// let flags = EffectFlags::from_array([Effect::Bold, Effect::Italic]);
+    /// let allowed = EffectFlags::from_array([Effect::Bold, Effect::Dimmed]);
+    ///
+    /// assert_eq!(flags.difference(allowed), EffectFlags::from_array([Effect::Italic]));
+    /// assert_eq!(flags.intersection(allowed), EffectFlags::from_array([Effect::Bold]));
+    /// assert_eq!(
+    ///     flags.union(allowed),
+    ///     EffectFlags::from_array([Effect::Bold, Effect::Italic, Effect::Dimmed]),
+    /// );
+    /// ```
+    #[must_use = "EffectFlags::difference returns a new instance without modifying the original"]
+    #[inline(always)]
+    pub const fn difference(self, other: EffectFlags) -> Self {
+        Self {
+            data: self.data & !other.data,
+        }
+    }
+
     /// Add an effect to the set
     #[must_use = "EffectFlags::with returns a new instance without modifying the original"]
     #[inline(always)]
@@ -231,19 +344,19 @@ impl EffectFlags {
 
     /// Add an effect to the set in place
     #[inline(always)]
-    pub fn set(&mut self, opt: Effect) {
+    pub const fn set(&mut self, opt: Effect) {
         *self = self.with(opt)
     }
 
     /// Remove an effect from the set in place
     #[inline(always)]
-    pub fn unset(&mut self, opt: Effect) {
+    pub const fn unset(&mut self, opt: Effect) {
         *self = self.without(opt)
     }
 
     /// Toggle an effect in the set in place
     #[inline(always)]
-    pub fn toggle(&mut self, opt: Effect) {
+    pub const fn toggle(&mut self, opt: Effect) {
         *self = self.toggled(opt)
     }
 
@@ -252,6 +365,20 @@ impl EffectFlags {
     pub const fn iter(self) -> EffectFlagsIter {
         EffectFlagsIter { data: self.data }
     }
+
+    /// The raw bits of this set, one bit per effect
+    #[inline(always)]
+    pub(crate) const fn bits(self) -> u32 {
+        self.data
+    }
+
+    /// Create a set from it's raw bits, ignoring any bits which don't correspond to an effect
+    #[inline(always)]
+    pub(crate) const fn from_bits_truncate(data: u32) -> Self {
+        Self {
+            data: data & ALL_EFFECTS.data,
+        }
+    }
 }
 
 impl Default for EffectFlags {
@@ -333,6 +460,341 @@ impl Default for Style<crate::NoColor, crate::NoColor, crate::NoColor> {
     }
 }
 
+impl Style {
+    /// Create a new runtime style, with no colors or effects
+    ///
+    /// Unlike [`Style::new`], the colors of this style are [`Option<Color>`], so it can be
+    /// built in a `const` or `static` with [`fg_const`](Self::fg_const) and friends, and the
+    /// result always has the same type.
+    ///
+    /// ```rust
+    /// use colorz::{Style, Color, ansi::AnsiColor, xterm::XtermColor};
+    ///
+    /// const ERROR: Style = Style::new_runtime().fg_const(Color::Ansi(AnsiColor::Red)).bold();
+    /// const NOTE: Style = Style::new_runtime().fg_const(Color::Xterm(XtermColor::from_code(33)));
+    ///
+    /// static STYLES: [Style; 2] = [ERROR, NOTE];
+    ///
+    /// assert_eq!(Style::new_runtime(), Style::new().into_runtime_style());
+    /// assert_eq!(STYLES[0], Style::new().fg(AnsiColor::Red).bold().into_runtime_style());
+    /// ```
+    #[inline]
+    pub const fn new_runtime() -> Self {
+        Style::new().const_into_runtime_style()
+    }
+
+    /// Set the foreground color, without changing the type of the style
+    ///
+    /// ```rust
+    /// use colorz::{Style, Color, css::CssColor};
+    ///
+    /// const STYLE: Style = Style::new_runtime().fg_const(Color::Css(CssColor::DodgerBlue));
+    ///
+    /// assert_eq!(STYLE.foreground, Some(Color::Css(CssColor::DodgerBlue)));
+    /// ```
+    #[inline]
+    pub const fn fg_const(self, color: Color) -> Style {
+        Style {
+            foreground: Some(color),
+            ..self
+        }
+    }
+
+    /// Set the background color, without changing the type of the style
+    ///
+    /// ```rust
+    /// use colorz::{Style, Color, ansi::AnsiColor};
+    ///
+    /// const STYLE: Style = Style::new_runtime().bg_const(Color::Ansi(AnsiColor::Blue));
+    ///
+    /// assert_eq!(STYLE.background, Some(Color::Ansi(AnsiColor::Blue)));
+    /// ```
+    #[inline]
+    pub const fn bg_const(self, color: Color) -> Style {
+        Style {
+            background: Some(color),
+            ..self
+        }
+    }
+
+    /// Set the underline color, without changing the type of the style
+    ///
+    /// Like [`underline_color`](Style::underline_color), this doesn't underline the text
+    ///
+    /// ```rust
+    /// use colorz::{Style, Color, ansi::AnsiColor};
+    ///
+    /// const STYLE: Style = Style::new_runtime().underline_color_const(Color::Ansi(AnsiColor::Red)).underline();
+    ///
+    /// assert_eq!(STYLE.underline_color, Some(Color::Ansi(AnsiColor::Red)));
+    /// ```
+    #[inline]
+    pub const fn underline_color_const(self, color: Color) -> Style {
+        Style {
+            underline_color: Some(color),
+            ..self
+        }
+    }
+
+    /// Convert all colors to the nearest color of the given kind, see [`Color::downgrade`]
+    ///
+    /// ```rust
+    /// use colorz::{Style, ansi, mode::ColorKind, rgb::RgbColor};
+    ///
+    /// let style = Style::new()
+    ///     .fg(RgbColor { red: 200, green: 10, blue: 20 })
+    ///     .bold()
+    ///     .into_runtime_style();
+    ///
+    /// assert_eq!(style.downgrade(ColorKind::Ansi), Style::new().fg(ansi::Red).bold().into_runtime_style());
+    /// assert_eq!(style.downgrade(ColorKind::NoColor), Style::new().bold().into_runtime_style());
+    /// ```
+    #[inline]
+    pub const fn downgrade(self, kind: ColorKind) -> Style {
+        const fn downgrade(color: Option<Color>, kind: ColorKind) -> Option<Color> {
+            match color {
+                Some(color) => color.downgrade(kind),
+                None => None,
+            }
+        }
+
+        Style {
+            foreground: downgrade(self.foreground, kind),
+            background: downgrade(self.background, kind),
+            underline_color: downgrade(self.underline_color, kind),
+            effects: self.effects,
+        }
+    }
+
+    /// Layer `other` on top of this style
+    ///
+    /// Any color set in `other` overrides the corresponding color in `self`,
+    /// and the effects of both styles are combined.
+    ///
+    /// This is also available via the `+` and `|` operators
+    ///
+    /// ```
+    /// use colorz::{Style, ansi};
+    ///
+    /// let defaults = Style::new().fg(ansi::Red).bg(ansi::Black).bold().into_runtime_style();
+    /// let theme = Style::new().fg(ansi::Blue).italics().into_runtime_style();
+    ///
+    /// assert_eq!(
+    ///     defaults.merge(theme),
+    ///     Style::new().fg(ansi::Blue).bg(ansi::Black).bold().italics().into_runtime_style(),
+    /// );
+    /// assert_eq!(defaults.merge(theme), defaults + theme);
+    /// assert_eq!(defaults.merge(theme), defaults | theme);
+    /// ```
+    #[inline]
+    pub const fn merge(self, other: Style) -> Style {
+        const fn or(a: Option<Color>, b: Option<Color>) -> Option<Color> {
+            match b {
+                Some(b) => Some(b),
+                None => a,
+            }
+        }
+
+        Style {
+            foreground: or(self.foreground, other.foreground),
+            background: or(self.background, other.background),
+            underline_color: or(self.underline_color, other.underline_color),
+            effects: EffectFlags {
+                data: self.effects.data | other.effects.data,
+            },
+        }
+    }
+
+    /// The underline color, if it's visible (i.e. the text is underlined)
+    const fn effective_underline_color(&self) -> Option<Color> {
+        if self.effects.is_any(ANY_UNDERLINE) {
+            self.underline_color
+        } else {
+            None
+        }
+    }
+
+    fn fmt_transition_from(&self, prev: &Style, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut semicolon = false;
+        let mut arg = |f: &mut fmt::Formatter<'_>| -> fmt::Result {
+            f.write_str(if semicolon { ";" } else { "\x1b[" })?;
+            semicolon = true;
+            Ok(())
+        };
+
+        let removed = prev.effects.data & !self.effects.data;
+        let mut added = self.effects.data & !prev.effects.data;
+
+        // some effects share a clear code (like bold and dimmed), so clearing one effect
+        // may clear other effects that should be kept, those need to be applied again
+        let mut cleared = 0;
+        for effect in (EffectFlags { data: removed }) {
+            if cleared & effect.mask() != 0 {
+                continue;
+            }
+
+            arg(f)?;
+            f.write_str(effect.clear_args())?;
+
+            for other in ALL_EFFECTS {
+                if other.clear_args() == effect.clear_args() {
+                    cleared |= other.mask();
+                }
+            }
+        }
+        added |= self.effects.data & cleared;
+
+        if self.foreground != prev.foreground {
+            arg(f)?;
+            match self.foreground {
+                Some(color) => color.fmt_foreground_args(f)?,
+                None => ansi::Default.fmt_foreground_args(f)?,
+            }
+        }
+
+        if self.background != prev.background {
+            arg(f)?;
+            match self.background {
+                Some(color) => color.fmt_background_args(f)?,
+                None => ansi::Default.fmt_background_args(f)?,
+            }
+        }
+
+        let underline_color = self.effective_underline_color();
+        if underline_color != prev.effective_underline_color() {
+            arg(f)?;
+            match underline_color {
+                Some(color) => color.fmt_underline_args(f)?,
+                None => f.write_str("59")?,
+            }
+        }
+
+        for effect in (EffectFlags { data: added }) {
+            arg(f)?;
+            f.write_str(effect.apply_args())?;
+        }
+
+        if semicolon {
+            f.write_str("m")?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the minimal ANSI codes needed to change the style of the terminal from `prev` to this style
+    ///
+    /// This is equivalent to writing `prev.clear()` then `self.apply()`, but only the colors and
+    /// effects which changed are written. This can greatly reduce the size of the output when
+    /// writing many adjacent styled values (like the cells of a table).
+    ///
+    /// Transitioning from [`Style::new`] is the same as [`apply`](Self::apply), and transitioning
+    /// to [`Style::new`] is the same as [`clear`](Self::clear). Like `apply` and `clear`,
+    /// this doesn't check the [coloring mode](crate::mode).
+    ///
+    /// ```
+    /// use colorz::{Style, ansi};
+    ///
+    /// let plain = Style::new().into_runtime_style();
+    /// let red = Style::new().fg(ansi::Red).bold().into_runtime_style();
+    /// let blue = Style::new().fg(ansi::Blue).bold().into_runtime_style();
+    ///
+    /// assert_eq!(red.transition_from(&plain).to_string(), "\x1b[31;1m");
+    /// assert_eq!(blue.transition_from(&red).to_string(), "\x1b[34m");
+    /// assert_eq!(blue.transition_from(&blue).to_string(), "");
+    /// assert_eq!(plain.transition_from(&blue).to_string(), "\x1b[22;39m");
+    /// ```
+    #[inline]
+    pub fn transition_from(&self, prev: &Style) -> impl fmt::Display + fmt::Debug {
+        struct Transition {
+            prev: Style,
+            next: Style,
+        }
+
+        impl fmt::Display for Transition {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.next.fmt_transition_from(&self.prev, f)
+            }
+        }
+
+        impl fmt::Debug for Transition {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.next.fmt_transition_from(&self.prev, f)
+            }
+        }
+
+        Transition {
+            prev: *prev,
+            next: *self,
+        }
+    }
+}
+
+impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
+    /// Writes the style as CSS declarations, like `color: rgb(205, 0, 0); font-weight: bold`
+    ///
+    /// This can be used in an inline `style` attribute, or in a CSS rule. All colors are written as
+    /// `rgb(..)` colors, using the default xterm palette for ANSI and Xterm colors. The terminal's
+    /// default colors aren't known, so they are left to the surrounding CSS. Effects are
+    /// mapped to the closest CSS property (like `font-weight`, `font-style` or `text-decoration-line`).
+    ///
+    /// ```rust
+    /// use colorz::{Style, ansi, rgb::RgbColor};
+    ///
+    /// let style = Style::new()
+    ///     .fg(ansi::Red)
+    ///     .bg(RgbColor { red: 1, green: 2, blue: 3 })
+    ///     .bold()
+    ///     .underline();
+    ///
+    /// assert_eq!(
+    ///     style.to_css().to_string(),
+    ///     "color: rgb(205, 0, 0); background-color: rgb(1, 2, 3); font-weight: bold; text-decoration-line: underline"
+    /// );
+    /// ```
+    #[inline]
+    pub fn to_css(&self) -> impl fmt::Display + fmt::Debug {
+        struct Css(Style);
+
+        impl fmt::Display for Css {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                crate::css::write_declarations(f, &self.0)
+            }
+        }
+
+        impl fmt::Debug for Css {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                crate::css::write_declarations(f, &self.0)
+            }
+        }
+
+        Css(self.to_runtime())
+    }
+}
+
+/// Equivalent to [`Style::merge`]
+impl Add for Style {
+    type Output = Style;
+
+    #[inline]
+    fn add(self, other: Style) -> Self::Output {
+        self.merge(other)
+    }
+}
+
+/// Equivalent to [`Style::merge`]
+impl BitOr for Style {
+    type Output = Style;
+
+    #[inline]
+    fn bitor(self, other: Style) -> Self::Output {
+        self.merge(other)
+    }
+}
+
 impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
     /// Set the foreground color
     #[inline(always)]
@@ -367,6 +829,69 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
         }
     }
 
+    /// Remove the foreground color
+    ///
+    /// ```rust
+    /// use colorz::{NoColor, Style, ansi};
+    ///
+    /// const ERROR: Style<ansi::Red, ansi::White, NoColor> = Style::new().fg(ansi::Red).bg(ansi::White).bold();
+    /// const ERROR_NO_FG: Style<NoColor, ansi::White, NoColor> = ERROR.without_fg();
+    ///
+    /// assert_eq!(ERROR_NO_FG, Style::new().bg(ansi::White).bold());
+    /// ```
+    #[inline(always)]
+    pub const fn without_fg(self) -> Style<crate::NoColor, B, U> {
+        self.fg(crate::NoColor)
+    }
+
+    /// Remove the background color
+    ///
+    /// ```rust
+    /// use colorz::{NoColor, Style, ansi};
+    ///
+    /// const ERROR: Style<ansi::Red, ansi::White, NoColor> = Style::new().fg(ansi::Red).bg(ansi::White).bold();
+    /// const ERROR_NO_BG: Style<ansi::Red, NoColor, NoColor> = ERROR.without_bg();
+    ///
+    /// assert_eq!(ERROR_NO_BG, Style::new().fg(ansi::Red).bold());
+    /// ```
+    #[inline(always)]
+    pub const fn without_bg(self) -> Style<F, crate::NoColor, U> {
+        self.bg(crate::NoColor)
+    }
+
+    /// Remove the underline color
+    #[inline(always)]
+    pub const fn without_underline_color(self) -> Style<F, B, crate::NoColor> {
+        self.underline_color(crate::NoColor)
+    }
+
+    /// Style the given value, this is the same as [`StyledValue::new(value, self, None)`](StyledValue::new)
+    ///
+    /// ```rust
+    /// use colorz::{NoColor, Style, ansi};
+    ///
+    /// const ERROR: Style<ansi::Red, NoColor, NoColor> = Style::new().fg(ansi::Red).bold();
+    ///
+    /// let error = ERROR.apply_to("error");
+    /// assert_eq!(error.style, ERROR);
+    /// println!("{error}: file not found");
+    /// ```
+    #[inline(always)]
+    pub const fn apply_to<T>(self, value: T) -> StyledValue<T, F, B, U> {
+        StyledValue::new(value, self, None)
+    }
+
+    /// Convert to a type-erased style, this works for any color type (unlike [`into_runtime_style`](Self::into_runtime_style))
+    #[inline]
+    pub(crate) fn to_runtime(self) -> Style {
+        Style {
+            foreground: self.foreground.into_runtime_color(),
+            background: self.background.into_runtime_color(),
+            underline_color: self.underline_color.into_runtime_color(),
+            effects: self.effects,
+        }
+    }
+
     /// Does this style apply any colors or effects
     #[inline(always)]
     pub fn is_plain(&self) -> bool {
@@ -558,7 +1083,241 @@ Effect! {
     ///
     /// println!("{}", "hello world".subscript());
     /// ```
-    SubScript 73 75 -> subscript,
+    SubScript 74 75 -> subscript,
+
+    /// Draws a frame around the value
+    ///
+    /// ```
+    /// use colorz::Colorize;
+    ///
+    /// println!("{}", "hello world".framed());
+    /// ```
+    Framed 51 54 -> framed,
+
+    /// Draws a circle around the value
+    ///
+    /// ```
+    /// use colorz::Colorize;
+    ///
+    /// println!("{}", "hello world".encircled());
+    /// ```
+    Encircled 52 54 -> encircled,
+}
+
+/// A fixed size buffer for a single SGR escape sequence, which can be written in a const context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct EscapeBuffer {
+    data: [u8; 128],
+    len: u8,
+}
+
+impl EscapeBuffer {
+    pub(crate) const fn new() -> Self {
+        Self {
+            data: [0; 128],
+            len: 0,
+        }
+    }
+
+    const fn push(&mut self, s: &str) {
+        let s = s.as_bytes();
+        let mut i = 0;
+        while i < s.len() {
+            self.data[self.len as usize] = s[i];
+            self.len += 1;
+            i += 1;
+        }
+    }
+
+    const fn push_u8(&mut self, x: u8) {
+        if x >= 100 {
+            self.data[self.len as usize] = x / 100 + b'0';
+            self.len += 1;
+        }
+
+        if x >= 10 {
+            self.data[self.len as usize] = x % 100 / 10 + b'0';
+            self.len += 1;
+        }
+
+        self.data[self.len as usize] = x % 10 + b'0';
+        self.len += 1;
+    }
+
+    /// Start a new argument, writing the `\x1b[` prefix or a separator
+    const fn start_arg(&mut self) {
+        self.push(if self.len == 0 { "\x1b[" } else { ";" });
+    }
+
+    const fn push_arg(&mut self, arg: &str) {
+        self.start_arg();
+        self.push(arg);
+    }
+
+    /// Push the color args, where `layer` is `3` (foreground), `4` (background) or `5` (underline)
+    const fn push_color(&mut self, color: Color, layer: u8) {
+        let args = match (color, layer) {
+            (Color::Ansi(color), 3) => color.foreground_args(),
+            (Color::Ansi(color), 4) => color.background_args(),
+            (Color::Ansi(color), _) => color.underline_args(),
+            (Color::Xterm(color), 3) => color.foreground_args(),
+            (Color::Xterm(color), 4) => color.background_args(),
+            (Color::Xterm(color), _) => color.underline_args(),
+            (Color::Css(color), 3) => color.foreground_args(),
+            (Color::Css(color), 4) => color.background_args(),
+            (Color::Css(color), _) => color.underline_args(),
+            (Color::Default, 3) => "39",
+            (Color::Default, 4) => "49",
+            (Color::Default, _) => "59",
+            (Color::Rgb(rgb), _) => match crate::cap::cap(color) {
+                Some(capped) => match layer {
+                    3 => capped.foreground_args(),
+                    4 => capped.background_args(),
+                    _ => capped.underline_args(),
+                },
+                None => {
+                    self.start_arg();
+                    self.push_u8(layer * 10 + 8);
+                    self.push(";2;");
+                    self.push_u8(rgb.red);
+                    self.push(";");
+                    self.push_u8(rgb.green);
+                    self.push(";");
+                    self.push_u8(rgb.blue);
+                    return;
+                }
+            },
+        };
+
+        self.push_arg(args);
+    }
+
+    /// Finish the escape sequence, if any arguments were written
+    const fn finish(mut self) -> Self {
+        if self.len != 0 {
+            self.push("m");
+        }
+        self
+    }
+
+    pub(crate) const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.data.split_at(self.len as usize).0) {
+            Ok(s) => s,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl fmt::Write for EscapeBuffer {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let start = self.len as usize;
+        let end = start + s.len();
+
+        match self.data.get_mut(start..end) {
+            Some(data) => {
+                data.copy_from_slice(s.as_bytes());
+                self.len = end as u8;
+                Ok(())
+            }
+            None => Err(fmt::Error),
+        }
+    }
+}
+
+/// The escape sequences of a style, which can be computed at compile time, see [`Style::escapes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StyleEscapes {
+    prefix: EscapeBuffer,
+    suffix: EscapeBuffer,
+}
+
+impl StyleEscapes {
+    /// The escape sequence which applies the style, like [`Style::apply`]
+    ///
+    /// This is empty if the style is plain
+    #[inline]
+    pub const fn prefix(&self) -> &str {
+        self.prefix.as_str()
+    }
+
+    /// The escape sequence which clears the style, like [`Style::clear`]
+    ///
+    /// This is empty if the style is plain
+    #[inline]
+    pub const fn suffix(&self) -> &str {
+        self.suffix.as_str()
+    }
+}
+
+impl Style {
+    /// Compute the escape sequences which apply and clear this style
+    ///
+    /// This can be used in a const context, so for styles known at compile time (like from
+    /// [`style!`](crate::style!) or [`const_into_runtime_style`](Style::const_into_runtime_style)),
+    /// hot paths can write a single constant string instead of formatting the style each time.
+    /// Each of the escapes is written as a single SGR sequence.
+    ///
+    /// Like [`Style::apply`], this doesn't respect the coloring mode,
+    /// use [`Style::should_color`] to check if the escapes should be written.
+    ///
+    /// ```rust
+    /// use colorz::{style, Style, StyleEscapes, rgb::Rgb};
+    ///
+    /// const ERROR: StyleEscapes = style!("bold red").escapes();
+    /// const ERROR_PREFIX: &str = ERROR.prefix();
+    ///
+    /// assert_eq!(ERROR_PREFIX, "\x1b[31;1m");
+    /// assert_eq!(ERROR.suffix(), "\x1b[39;22m");
+    ///
+    /// const LINK: StyleEscapes = Style::new()
+    ///     .fg(Rgb::<30, 144, 255>)
+    ///     .underline()
+    ///     .const_into_runtime_style()
+    ///     .escapes();
+    ///
+    /// # if cfg!(not(any(feature = "max-colors-256", feature = "max-colors-16"))) {
+    /// assert_eq!(LINK.prefix(), "\x1b[38;2;30;144;255;4m");
+    /// # }
+    /// assert_eq!(LINK.suffix(), "\x1b[39;24m");
+    ///
+    /// assert_eq!(Style::new_runtime().escapes().prefix(), "");
+    /// ```
+    #[inline]
+    pub const fn escapes(self) -> StyleEscapes {
+        let mut prefix = EscapeBuffer::new();
+        let mut suffix = EscapeBuffer::new();
+
+        if let Some(color) = self.effective_underline_color() {
+            prefix.push_color(color, 5);
+            suffix.push_arg("59");
+        }
+
+        if let Some(color) = self.foreground {
+            prefix.push_color(color, 3);
+            suffix.push_arg("39");
+        }
+
+        if let Some(color) = self.background {
+            prefix.push_color(color, 4);
+            suffix.push_arg("49");
+        }
+
+        let mut i = 0;
+        while i < EFFECT_LIST.len() {
+            let effect = EFFECT_LIST[i];
+            if self.effects.is(effect) {
+                prefix.push_arg(effect.apply_args());
+                suffix.push_arg(effect.clear_args());
+            }
+            i += 1;
+        }
+
+        StyleEscapes {
+            prefix: prefix.finish(),
+            suffix: suffix.finish(),
+        }
+    }
 }
 
 const ANY_UNDERLINE: EffectFlags = EffectFlags::new()
@@ -581,10 +1340,84 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
         )
     }
 
-    fn fmt_apply(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// The number of escape sequences written by [`write_apply`](Self::write_apply)
+    /// and [`write_clear`](Self::write_clear)
+    fn escape_count(&self) -> u32 {
+        let underline =
+            (self.effects.is_any(ANY_UNDERLINE) && self.underline_color.get().is_some()) as u32;
+
+        let main = if self.effects.at_most_one_effect() {
+            self.effects.data.count_ones()
+                + self.foreground.get().is_some() as u32
+                + self.background.get().is_some() as u32
+        } else {
+            1
+        };
+
+        underline + main
+    }
+
+    // Calls to the formatter are an optimization barrier, and comparatively expensive
+    // (especially when writing to `std::io` adapters), so when the escapes would take
+    // multiple calls they are collected in a stack buffer first, and written all at once
+
+    /// Write the apply escapes, with a single call to the formatter
+    fn fmt_apply_batched(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = EscapeBuffer::new();
+
+        if self.escape_count() > 1 && self.write_apply(&mut buffer).is_ok() {
+            f.write_str(buffer.as_str())
+        } else {
+            self.write_apply(f)
+        }
+    }
+
+    /// Write the clear escapes, with a single call to the formatter
+    fn fmt_clear_batched(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buffer = EscapeBuffer::new();
+
+        if self.escape_count() > 1 && self.write_clear(&mut buffer).is_ok() {
+            f.write_str(buffer.as_str())
+        } else {
+            self.write_clear(f)
+        }
+    }
+
+    /// Write the ANSI color and effect codes to `w`, like [`apply`](Self::apply)
+    ///
+    /// This writes directly to `w` without going through a [`Formatter`](fmt::Formatter),
+    /// which is useful when writing to your own buffers
+    ///
+    /// ```rust
+    /// use colorz::{Style, ansi};
+    ///
+    /// let style = Style::new().fg(ansi::Red).bold();
+    ///
+    /// let mut buffer = String::new();
+    /// style.write_apply_to(&mut buffer)?;
+    /// buffer.push_str("error");
+    /// style.write_clear_to(&mut buffer)?;
+    ///
+    /// assert_eq!(buffer, format!("{}error{}", style.apply(), style.clear()));
+    /// # Ok::<(), core::fmt::Error>(())
+    /// ```
+    #[inline]
+    pub fn write_apply_to<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        self.write_apply(w)
+    }
+
+    /// Write the ANSI color and effect clear codes to `w`, like [`clear`](Self::clear)
+    ///
+    /// See [`write_apply_to`](Self::write_apply_to) for an example
+    #[inline]
+    pub fn write_clear_to<W: fmt::Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        self.write_clear(w)
+    }
+
+    fn write_apply<W: fmt::Write + ?Sized>(&self, f: &mut W) -> fmt::Result {
         if self.effects.is_any(ANY_UNDERLINE) {
             if let Some(color) = self.underline_color.get() {
-                color.fmt_underline(f)?
+                color.write_underline_to(f)?
             }
         }
 
@@ -609,7 +1442,7 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
                         f.write_str(effect.apply_escape())?;
                     }
 
-                    return self.foreground.get().unwrap().fmt_foreground(f);
+                    return self.foreground.get().unwrap().write_foreground_to(f);
                 }
             }
             (crate::Kind::NeverSome, crate::Kind::AlwaysSome) => {
@@ -620,27 +1453,27 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
                     }
 
                     if let Some(bg) = self.background.get() {
-                        return bg.fmt_background(f);
+                        return bg.write_background_to(f);
                     }
                 }
             }
         }
 
-        self.fmt_apply_slow(f)
+        self.write_apply_slow(f)
     }
 
-    fn fmt_apply_slow(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_apply_slow<W: fmt::Write + ?Sized>(&self, f: &mut W) -> fmt::Result {
         if self.effects.at_most_one_effect() {
             if let Some(effect) = self.effects.iter().next() {
                 f.write_str(effect.apply_escape())?;
             }
 
             if let Some(fg) = self.foreground.get() {
-                fg.fmt_foreground(f)?;
+                fg.write_foreground_to(f)?;
             }
 
             if let Some(bg) = self.background.get() {
-                bg.fmt_background(f)?;
+                bg.write_background_to(f)?;
             }
 
             return Ok(());
@@ -663,13 +1496,13 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
 
         if let Some(fg) = self.foreground.get() {
             semicolon = true;
-            fg.fmt_foreground_args(f)?;
+            fg.write_foreground_args_to(f)?;
         }
 
         if let Some(bg) = self.background.get() {
             semi!();
             semicolon = true;
-            bg.fmt_background_args(f)?;
+            bg.write_background_args_to(f)?;
         }
 
         if !self.effects.at_most_one_effect() {
@@ -686,7 +1519,7 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
         Ok(())
     }
 
-    fn fmt_clear(&self, f: &mut fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_clear<W: fmt::Write + ?Sized>(&self, f: &mut W) -> fmt::Result {
         if self.effects.is_any(ANY_UNDERLINE) && self.underline_color.get().is_some() {
             f.write_str("\x1b[59m")?
         }
@@ -709,7 +1542,7 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
                         f.write_str(effect.clear_escape())?;
                     }
 
-                    return ansi::Default.fmt_foreground(f);
+                    return ansi::Default.write_foreground_to(f);
                 }
             }
             (crate::Kind::NeverSome, crate::Kind::AlwaysSome) => {
@@ -719,16 +1552,16 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
                         f.write_str(effect.clear_escape())?;
                     }
 
-                    return ansi::Default.fmt_background(f);
+                    return ansi::Default.write_background_to(f);
                 }
             }
         }
 
-        self.fmt_clear_slow(f)
+        self.write_clear_slow(f)
     }
 
     #[cold]
-    fn fmt_clear_slow(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    fn write_clear_slow<W: fmt::Write + ?Sized>(&self, f: &mut W) -> fmt::Result {
         let at_most_one_effect = self.effects.at_most_one_effect();
         if at_most_one_effect {
             if let Some(effect) = self.effects.iter().next() {
@@ -736,11 +1569,11 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
             }
 
             if self.foreground.get().is_some() {
-                ansi::Default.fmt_foreground(f)?;
+                ansi::Default.write_foreground_to(f)?;
             }
 
             if self.background.get().is_some() {
-                ansi::Default.fmt_background(f)?;
+                ansi::Default.write_background_to(f)?;
             }
 
             return Ok(());
@@ -763,13 +1596,13 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
 
         if self.foreground.get().is_some() {
             semicolon = true;
-            ansi::Default.fmt_foreground_args(f)?;
+            ansi::Default.write_foreground_args_to(f)?;
         }
 
         if self.background.get().is_some() {
             semi!();
             semicolon = true;
-            ansi::Default.fmt_background_args(f)?;
+            ansi::Default.write_background_args_to(f)?;
         }
 
         if !at_most_one_effect {
@@ -796,14 +1629,14 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
         impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> core::fmt::Display for Prefix<F, B, U> {
             #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                self.style.fmt_apply(f)
+                self.style.fmt_apply_batched(f)
             }
         }
 
         impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> core::fmt::Debug for Prefix<F, B, U> {
             #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                self.style.fmt_apply(f)
+                self.style.fmt_apply_batched(f)
             }
         }
 
@@ -820,14 +1653,14 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
         impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> core::fmt::Display for Suffix<F, B, U> {
             #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                self.style.fmt_clear(f)
+                self.style.fmt_clear_batched(f)
             }
         }
 
         impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> core::fmt::Debug for Suffix<F, B, U> {
             #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                self.style.fmt_clear(f)
+                self.style.fmt_clear_batched(f)
             }
         }
 
@@ -838,7 +1671,7 @@ impl<F: OptionalColor, B: OptionalColor, U: OptionalColor> Style<F, B, U> {
 /// An iterator for the [`EffectFlags`] type, which yields [`Effect`]s
 #[derive(Clone)]
 pub struct EffectFlagsIter {
-    data: u16,
+    data: u32,
 }
 
 impl core::fmt::Debug for EffectFlagsIter {
@@ -848,6 +1681,32 @@ impl core::fmt::Debug for EffectFlagsIter {
     }
 }
 
+/// Displays the effect's [`name`](Effect::name)
+impl fmt::Display for Effect {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Displays a comma separated list of the effect's names, like `bold,italics`
+///
+/// This can be parsed back into an [`EffectFlags`] via [`FromStr`](core::str::FromStr)
+impl fmt::Display for EffectFlags {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, effect) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(",")?;
+            }
+
+            f.write_str(effect.name())?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<'a> From<&'a Effect> for Effect {
     #[inline(always)]
     fn from(value: &'a Effect) -> Self {
@@ -886,7 +1745,7 @@ impl Iterator for EffectFlagsIter {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let data = NonZeroU16::new(self.data)?;
+        let data = NonZeroU32::new(self.data)?;
         let zeros = data.trailing_zeros();
         self.data ^= 1 << zeros;
         Some(Effect::decode(zeros as u8))
@@ -0,0 +1,202 @@
+//! Writing colorz styles through a [`termcolor`] sink
+//!
+//! [`termcolor::WriteColor`] sinks write colors with ANSI escapes or with
+//! the Windows console API (for legacy consoles which don't support escapes), so code which has to
+//! write through termcolor can't write colorz's escapes directly. Instead, convert the [`Style`] to a
+//! [`termcolor::ColorSpec`], or write the colored text through a [`TermcolorWriter`].
+//!
+//! ```rust
+//! use colorz::{Colorize, Style, ansi, termcolor::TermcolorWriter, mode::Stream};
+//! use termcolor::{Buffer, Color, ColorSpec};
+//!
+//! let spec = ColorSpec::from(Style::new().fg(ansi::Red).bold().into_runtime_style());
+//! assert_eq!(spec.fg(), Some(&Color::Red));
+//! assert!(spec.bold());
+//!
+//! let mut writer = TermcolorWriter::new(Buffer::ansi());
+//! write!(writer, "{} world", "hello".red().stream(Stream::AlwaysColor)).unwrap();
+//! # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+//! assert_eq!(writer.into_inner().into_inner(), b"\x1b[0m\x1b[31mhello\x1b[0m world");
+//! # }
+//! ```
+
+use core::fmt;
+use std::io;
+
+use ::termcolor::{Color as TermColor, ColorSpec, WriteColor};
+
+use crate::{ansi::AnsiColor, sgr::SgrSpans, Color, Effect, Style};
+
+/// Convert a color, using `intense` for bright ANSI colors if it matches the brightness of the color
+const fn to_termcolor(color: Color, intense: bool) -> Option<TermColor> {
+    const NORMAL: [TermColor; 8] = [
+        TermColor::Black,
+        TermColor::Red,
+        TermColor::Green,
+        TermColor::Yellow,
+        TermColor::Blue,
+        TermColor::Magenta,
+        TermColor::Cyan,
+        TermColor::White,
+    ];
+
+    Some(match color {
+        Color::Ansi(AnsiColor::Default) | Color::Default => return None,
+        Color::Ansi(color) if color.is_bright() != intense => TermColor::Ansi256(color as u8),
+        Color::Ansi(color) => NORMAL[color.to_normal() as usize],
+        Color::Xterm(color) => TermColor::Ansi256(color as u8),
+        Color::Css(color) => {
+            let rgb = color.rgb();
+            TermColor::Rgb(rgb.red, rgb.green, rgb.blue)
+        }
+        Color::Rgb(rgb) => TermColor::Rgb(rgb.red, rgb.green, rgb.blue),
+    })
+}
+
+/// termcolor only has one intense flag for both the foreground and the background, so it's used
+/// for whichever ANSI color is written first (the foreground). Other bright colors are written as
+/// 256-colors instead.
+///
+/// The underline color and any effects which termcolor doesn't support (like [`Effect::Blink`]) are ignored
+impl From<Style> for ColorSpec {
+    #[inline]
+    fn from(style: Style) -> Self {
+        let intense = match (style.foreground, style.background) {
+            (Some(Color::Ansi(color)), _) if color != AnsiColor::Default => color.is_bright(),
+            (_, Some(Color::Ansi(color))) => color.is_bright(),
+            _ => false,
+        };
+
+        let mut spec = ColorSpec::new();
+        spec.set_fg(
+            style
+                .foreground
+                .and_then(|color| to_termcolor(color, intense)),
+        )
+        .set_bg(
+            style
+                .background
+                .and_then(|color| to_termcolor(color, intense)),
+        )
+        .set_intense(intense)
+        .set_bold(style.effects.is(Effect::Bold))
+        .set_dimmed(style.effects.is(Effect::Dimmed))
+        .set_italic(style.effects.is(Effect::Italic))
+        .set_underline(
+            style.effects.is(Effect::Underline) || style.effects.is(Effect::DoubleUnderline),
+        )
+        .set_strikethrough(style.effects.is(Effect::Strikethrough));
+        spec
+    }
+}
+
+/// A writer which writes text containing colorz's escape sequences to a [`termcolor::WriteColor`]
+///
+/// The escape sequences are parsed (see [`sgr`](crate::sgr)) and each style is set on the sink via
+/// [`WriteColor::set_color`] instead, so styled values can be written to sinks which use the Windows console API.
+/// Use [`write!`] to write to it, or [`fmt::Write`].
+///
+/// Each escape sequence has to be written in a single call to [`fmt::Write::write_str`], which is the
+/// case for everything formatted by colorz.
+#[derive(Debug)]
+pub struct TermcolorWriter<W> {
+    writer: W,
+    /// The style parsed from the escape sequences written so far
+    style: Style,
+    /// The style which was last set on the sink
+    applied: Style,
+    error: Option<io::Error>,
+}
+
+impl<W: WriteColor> TermcolorWriter<W> {
+    /// Create a writer which writes to `writer`
+    #[inline]
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer,
+            style: Style::new().const_into_runtime_style(),
+            applied: Style::new().const_into_runtime_style(),
+            error: None,
+        }
+    }
+
+    /// Get the sink
+    #[inline]
+    pub const fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Get the sink mutably
+    ///
+    /// Writing to the sink directly may desync the current style
+    #[inline]
+    pub const fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Reset the style of the sink and get it
+    ///
+    /// # Errors
+    ///
+    /// If resetting the sink fails, then this returns the error and the sink
+    #[inline]
+    pub fn finish(mut self) -> Result<W, (io::Error, W)> {
+        match self.writer.reset() {
+            Ok(()) => Ok(self.writer),
+            Err(err) => Err((err, self.writer)),
+        }
+    }
+
+    /// Get the sink, without resetting it's style
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Write formatted text to the sink, this allows using [`write!`] with this writer
+    ///
+    /// # Errors
+    ///
+    /// If writing to the sink fails
+    #[inline]
+    pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> io::Result<()> {
+        match fmt::Write::write_fmt(self, args) {
+            Ok(()) => Ok(()),
+            Err(fmt::Error) => Err(self
+                .error
+                .take()
+                .unwrap_or_else(|| io::Error::other("formatter error"))),
+        }
+    }
+
+    fn write_spans(&mut self, s: &str) -> io::Result<()> {
+        let mut spans = SgrSpans::with_style(s, self.style);
+
+        for (style, text) in spans.by_ref() {
+            if style != self.applied {
+                if style.is_plain() {
+                    self.writer.reset()?;
+                } else {
+                    self.writer.set_color(&style.into())?;
+                }
+                self.applied = style;
+            }
+
+            self.writer.write_all(text.as_bytes())?;
+        }
+
+        // escapes at the end of the text are applied to the next text
+        self.style = spans.style();
+        Ok(())
+    }
+}
+
+impl<W: WriteColor> fmt::Write for TermcolorWriter<W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_spans(s).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
@@ -0,0 +1,326 @@
+use core::{
+    fmt::{self, Display, Write},
+    ops,
+};
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{mode::Stream, OptionalColor, RuntimeStyledValue, Style, StyledValue};
+
+/// An owned string with a runtime style
+///
+/// Unlike most [`StyledValue`]s, this doesn't borrow anything or carry any color types,
+/// so it can be built in one place, stored, and displayed later.
+///
+/// ```rust
+/// use colorz::{Colorize, StyledString};
+///
+/// let mut message: StyledString = "hello".red().to_styled_string();
+/// message += " world";
+///
+/// assert_eq!(message.value, "hello world");
+/// println!("{message}");
+/// ```
+pub type StyledString = RuntimeStyledValue<String>;
+
+impl StyledString {
+    /// Append a string to the value, keeping the style
+    #[inline]
+    pub fn push_str(&mut self, s: &str) {
+        self.value.push_str(s)
+    }
+
+    /// Append a character to the value, keeping the style
+    #[inline]
+    pub fn push(&mut self, c: char) {
+        self.value.push(c)
+    }
+
+    /// The length of the value in bytes (not including any escape sequences)
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    /// Is the value empty
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
+impl From<String> for StyledString {
+    #[inline]
+    fn from(value: String) -> Self {
+        StyledValue::new(value, Style::new().into_runtime_style(), None)
+    }
+}
+
+impl From<&str> for StyledString {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::from(String::from(value))
+    }
+}
+
+impl ops::Add<&str> for StyledString {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: &str) -> Self::Output {
+        self.push_str(rhs);
+        self
+    }
+}
+
+impl ops::AddAssign<&str> for StyledString {
+    #[inline]
+    fn add_assign(&mut self, rhs: &str) {
+        self.push_str(rhs)
+    }
+}
+
+impl ops::Add for StyledString {
+    type Output = StyledText;
+
+    /// Concatenate two styled strings, keeping both styles
+    #[inline]
+    fn add(self, rhs: StyledString) -> Self::Output {
+        let mut text = StyledText::from(self);
+        text.push_value(&rhs);
+        text
+    }
+}
+
+impl fmt::Write for StyledString {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display, F: OptionalColor, B: OptionalColor, U: OptionalColor>
+    StyledValue<T, F, B, U>
+{
+    /// Render the value to a [`String`], keeping the style and stream
+    ///
+    /// ```rust
+    /// use colorz::{Colorize, StyledString};
+    ///
+    /// let count: StyledString = 42.bold().to_styled_string();
+    /// assert_eq!(count.value, "42");
+    /// assert_eq!(count.style, colorz::Style::new().bold().into_runtime_style());
+    /// ```
+    #[inline]
+    pub fn to_styled_string(&self) -> StyledString {
+        StyledValue {
+            value: self.value.to_string(),
+            style: self.style.to_runtime(),
+            stream: self.stream,
+            options: self.options,
+        }
+    }
+}
+
+/// A sequence of segments, each of which has it's own style
+///
+/// This is useful for building up a single message (like a log line or a table cell)
+/// out of many differently styled parts, and then displaying it all at once.
+/// When displayed, only the escapes needed to switch between styles are emitted.
+///
+/// ```rust
+/// use colorz::{Colorize, Style, StyledText, ansi};
+///
+/// let mut line = StyledText::new();
+/// line.push_styled(Style::new().fg(ansi::Red).bold(), "error");
+/// line.push_plain(": file not found: ");
+/// line.push_value(&"config.toml".underline());
+///
+/// assert_eq!(line.as_str(), "error: file not found: config.toml");
+/// println!("{line}");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StyledText {
+    text: String,
+    // the style of each segment, and the end of that segment in `text`
+    segments: Vec<(Style, usize)>,
+    /// The stream to use
+    pub stream: Option<Stream>,
+}
+
+impl StyledText {
+    /// Create an empty styled text
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            text: String::new(),
+            segments: Vec::new(),
+            stream: None,
+        }
+    }
+
+    /// Sets the stream for the given text
+    #[inline]
+    pub const fn stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// The text without any styling
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The length of the text in bytes (not including any escape sequences)
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Is there no text
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Remove all segments
+    #[inline]
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.segments.clear();
+    }
+
+    fn end_segment(&mut self, style: Style) {
+        let end = self.text.len();
+
+        match self.segments.last_mut() {
+            // don't create empty segments
+            Some(&mut (_, last_end)) if last_end == end => (),
+            // merge adjacent segments with the same style
+            Some((last_style, last_end)) if *last_style == style => *last_end = end,
+            _ => self.segments.push((style, end)),
+        }
+    }
+
+    /// Append some text with the given style
+    #[inline]
+    pub fn push_styled<F, B, U>(&mut self, style: Style<F, B, U>, text: &str)
+    where
+        F: OptionalColor,
+        B: OptionalColor,
+        U: OptionalColor,
+    {
+        self.text.push_str(text);
+        self.end_segment(style.to_runtime());
+    }
+
+    /// Append some text without any style
+    #[inline]
+    pub fn push_plain(&mut self, text: &str) {
+        self.push_styled(Style::new(), text)
+    }
+
+    /// Append a styled value, using its style
+    ///
+    /// The stream of the value is ignored, the stream of the [`StyledText`] is used instead
+    #[inline]
+    pub fn push_value<T, F, B, U>(&mut self, value: &StyledValue<T, F, B, U>)
+    where
+        T: Display,
+        F: OptionalColor,
+        B: OptionalColor,
+        U: OptionalColor,
+    {
+        // writing to a `String` can't fail
+        let _ = write!(self.text, "{}", value.value);
+        self.end_segment(value.resolved_style());
+    }
+
+    /// Iterate over each segment of text, and it's style
+    ///
+    /// ```rust
+    /// use colorz::{Style, StyledText};
+    ///
+    /// let mut text = StyledText::new();
+    /// text.push_styled(Style::new().bold(), "a");
+    /// text.push_styled(Style::new().bold(), "b");
+    /// text.push_plain("c");
+    ///
+    /// let spans: Vec<_> = text.spans().collect();
+    /// assert_eq!(spans, [
+    ///     (Style::new().bold().into_runtime_style(), "ab"),
+    ///     (Style::new().into_runtime_style(), "c"),
+    /// ]);
+    /// ```
+    #[inline]
+    pub fn spans(&self) -> Spans<'_> {
+        Spans {
+            text: &self.text,
+            segments: self.segments.iter(),
+            start: 0,
+        }
+    }
+}
+
+impl From<StyledString> for StyledText {
+    #[inline]
+    fn from(value: StyledString) -> Self {
+        let mut text = Self::new();
+        text.push_value(&value);
+        text.stream = value.stream;
+        text
+    }
+}
+
+impl Display for StyledText {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plain = Style::new().into_runtime_style();
+        let mut active = plain;
+
+        for (style, text) in self.spans() {
+            let style = crate::render::resolve_runtime(style, self.stream);
+
+            if style != active {
+                style.transition_from(&active).fmt(f)?;
+                active = style;
+            }
+
+            f.write_str(text)?;
+        }
+
+        plain.transition_from(&active).fmt(f)
+    }
+}
+
+/// An iterator over the segments of a [`StyledText`], see [`StyledText::spans`]
+#[derive(Debug, Clone)]
+pub struct Spans<'a> {
+    text: &'a str,
+    segments: core::slice::Iter<'a, (Style, usize)>,
+    start: usize,
+}
+
+impl<'a> Iterator for Spans<'a> {
+    type Item = (Style, &'a str);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let &(style, end) = self.segments.next()?;
+        let text = &self.text[self.start..end];
+        self.start = end;
+        Some((style, text))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.segments.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Spans<'_> {}
@@ -0,0 +1,661 @@
+//! A global registry of named styles
+//!
+//! Applications can register styles under a name (like `error` or `warning`), and look them
+//! up when formatting. This gives applications a single place to let users re-theme all of
+//! their output, instead of hard-coding colors at every call site.
+//!
+//! The [builtin names](NAMES) are stored in a fixed table of atomics, so they are available
+//! without `std` and always have a default style. With the `std` feature, any other name can
+//! also be registered.
+//!
+//! ```rust
+//! use colorz::{Colorize, Style, ansi, mode::Stream, theme};
+//!
+//! // the builtin names have a default style
+//! assert_eq!(theme::get("error"), Some(Style::new().fg(ansi::Red).bold().into_runtime_style()));
+//!
+//! // `Colorize` has methods for the most common names, which look up the style when formatted
+//! let message = "something went wrong".error().stream(Stream::AlwaysColor);
+//!
+//! theme::set("error", Style::new().fg(ansi::Magenta).underline());
+//! assert_eq!(theme::get("error"), Some(Style::new().fg(ansi::Magenta).underline().into_runtime_style()));
+//! # if Style::new().bold().should_color(Stream::AlwaysColor) {
+//! assert_eq!(message.to_string(), "\x1b[4m\x1b[35msomething went wrong\x1b[24m\x1b[39m");
+//! # }
+//!
+//! theme::reset("error");
+//! assert_eq!(theme::get("error"), Some(Style::new().fg(ansi::Red).bold().into_runtime_style()));
+//! ```
+
+use core::sync::atomic::{fence, AtomicU32, Ordering};
+
+use crate::{
+    ansi::{self, AnsiColor},
+    css::CssColor,
+    rgb::RgbColor,
+    xterm::XtermColor,
+    Color, EffectFlags, OptionalColor, Style,
+};
+
+/// The names which always have a style, even without `std`
+///
+/// * `error` - bold red
+/// * `warning` - bold yellow
+/// * `success` - green
+/// * `info` - cyan
+/// * `hint` - dimmed
+/// * `debug` - blue
+/// * `trace` - magenta
+pub const NAMES: [&str; 7] = [
+    "error", "warning", "success", "info", "hint", "debug", "trace",
+];
+
+const DEFAULTS: [Style; 7] = [
+    Style::new().fg(ansi::Red).bold().const_into_runtime_style(),
+    Style::new()
+        .fg(ansi::Yellow)
+        .bold()
+        .const_into_runtime_style(),
+    Style::new().fg(ansi::Green).const_into_runtime_style(),
+    Style::new().fg(ansi::Cyan).const_into_runtime_style(),
+    Style::new().dimmed().const_into_runtime_style(),
+    Style::new().fg(ansi::Blue).const_into_runtime_style(),
+    Style::new().fg(ansi::Magenta).const_into_runtime_style(),
+];
+
+static BUILTIN: [Slot; 7] = [
+    Slot::new(DEFAULTS[0]),
+    Slot::new(DEFAULTS[1]),
+    Slot::new(DEFAULTS[2]),
+    Slot::new(DEFAULTS[3]),
+    Slot::new(DEFAULTS[4]),
+    Slot::new(DEFAULTS[5]),
+    Slot::new(DEFAULTS[6]),
+];
+
+#[cfg(feature = "std")]
+static CUSTOM: std::sync::RwLock<std::collections::BTreeMap<std::string::String, Style>> =
+    std::sync::RwLock::new(std::collections::BTreeMap::new());
+
+const NONE_TAG: u32 = 0;
+const ANSI_TAG: u32 = 1;
+const XTERM_TAG: u32 = 2;
+const CSS_TAG: u32 = 3;
+const RGB_TAG: u32 = 4;
+const DEFAULT_TAG: u32 = 5;
+
+const fn encode_color(color: Option<Color>) -> u32 {
+    match color {
+        None => NONE_TAG << 24,
+        Some(Color::Ansi(color)) => ANSI_TAG << 24 | color as u32,
+        Some(Color::Xterm(color)) => XTERM_TAG << 24 | color as u32,
+        Some(Color::Css(color)) => CSS_TAG << 24 | color as u32,
+        Some(Color::Rgb(RgbColor { red, green, blue })) => {
+            RGB_TAG << 24 | (red as u32) << 16 | (green as u32) << 8 | blue as u32
+        }
+        Some(Color::Default) => DEFAULT_TAG << 24,
+    }
+}
+
+const fn decode_color(data: u32) -> Option<Color> {
+    let [tag, red, green, blue] = data.to_be_bytes();
+
+    match tag as u32 {
+        ANSI_TAG => match AnsiColor::from_index(blue) {
+            Some(color) => Some(Color::Ansi(color)),
+            None => None,
+        },
+        XTERM_TAG => Some(Color::Xterm(XtermColor::from_code(blue))),
+        CSS_TAG => match CssColor::from_index(blue) {
+            Some(color) => Some(Color::Css(color)),
+            None => None,
+        },
+        RGB_TAG => Some(Color::Rgb(RgbColor { red, green, blue })),
+        DEFAULT_TAG => Some(Color::Default),
+        _ => None,
+    }
+}
+
+/// A style stored in atomics, guarded by a sequence lock so that
+/// readers never observe a partially written style
+struct Slot {
+    /// Odd while a write is in progress
+    seq: AtomicU32,
+    foreground: AtomicU32,
+    background: AtomicU32,
+    underline_color: AtomicU32,
+    effects: AtomicU32,
+}
+
+impl Slot {
+    const fn new(style: Style) -> Self {
+        Self {
+            seq: AtomicU32::new(0),
+            foreground: AtomicU32::new(encode_color(style.foreground)),
+            background: AtomicU32::new(encode_color(style.background)),
+            underline_color: AtomicU32::new(encode_color(style.underline_color)),
+            effects: AtomicU32::new(style.effects.bits()),
+        }
+    }
+
+    fn load(&self) -> Style {
+        loop {
+            let seq = self.seq.load(Ordering::Acquire);
+
+            if !seq.is_multiple_of(2) {
+                core::hint::spin_loop();
+                continue;
+            }
+
+            let foreground = self.foreground.load(Ordering::Relaxed);
+            let background = self.background.load(Ordering::Relaxed);
+            let underline_color = self.underline_color.load(Ordering::Relaxed);
+            let effects = self.effects.load(Ordering::Relaxed);
+
+            fence(Ordering::Acquire);
+
+            if self.seq.load(Ordering::Relaxed) == seq {
+                return Style {
+                    foreground: decode_color(foreground),
+                    background: decode_color(background),
+                    underline_color: decode_color(underline_color),
+                    effects: EffectFlags::from_bits_truncate(effects),
+                };
+            }
+        }
+    }
+
+    fn store(&self, style: Style) {
+        let seq = loop {
+            let seq = self.seq.load(Ordering::Relaxed);
+
+            if seq.is_multiple_of(2)
+                && self
+                    .seq
+                    .compare_exchange_weak(seq, seq + 1, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                break seq;
+            }
+
+            core::hint::spin_loop();
+        };
+
+        fence(Ordering::Release);
+
+        self.foreground
+            .store(encode_color(style.foreground), Ordering::Relaxed);
+        self.background
+            .store(encode_color(style.background), Ordering::Relaxed);
+        self.underline_color
+            .store(encode_color(style.underline_color), Ordering::Relaxed);
+        self.effects.store(style.effects.bits(), Ordering::Relaxed);
+
+        self.seq.store(seq.wrapping_add(2), Ordering::Release);
+    }
+}
+
+fn builtin(name: &str) -> Option<usize> {
+    NAMES.iter().position(|&builtin| builtin == name)
+}
+
+/// Get the style registered under `name`
+///
+/// Returns `None` if no style was registered under `name`, this never
+/// happens for the [builtin names](NAMES)
+///
+/// ```rust
+/// use colorz::theme;
+///
+/// assert!(theme::get("warning").is_some());
+/// assert_eq!(theme::get("not registered"), None);
+/// ```
+#[inline]
+pub fn get(name: &str) -> Option<Style> {
+    if let Some(index) = builtin(name) {
+        return Some(BUILTIN[index].load());
+    }
+
+    #[cfg(feature = "std")]
+    {
+        CUSTOM
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(name)
+            .copied()
+    }
+
+    #[cfg(not(feature = "std"))]
+    None
+}
+
+/// The style registered under `name`, or a plain style if there is none
+#[inline]
+pub(crate) fn get_or_plain(name: &str) -> Style {
+    get(name).unwrap_or(const { Style::new().const_into_runtime_style() })
+}
+
+/// Register `style` under `name`, replacing any previous style
+///
+/// Returns false if the style couldn't be registered, this only happens without
+/// the `std` feature for names other than the [builtin names](NAMES)
+///
+/// ```rust
+/// use colorz::{Style, theme, xterm};
+///
+/// if theme::set("path", Style::new().fg(xterm::Aqua).underline()) {
+///     assert_eq!(theme::get("path"), Some(Style::new().fg(xterm::Aqua).underline().into_runtime_style()));
+/// } else {
+///     // without `std` only the builtin names can be registered
+///     assert_eq!(theme::get("path"), None);
+/// }
+/// ```
+#[inline]
+pub fn set<F: OptionalColor, B: OptionalColor, U: OptionalColor>(
+    name: &str,
+    style: Style<F, B, U>,
+) -> bool {
+    let style = style.to_runtime();
+
+    if let Some(index) = builtin(name) {
+        BUILTIN[index].store(style);
+        return true;
+    }
+
+    #[cfg(feature = "std")]
+    {
+        CUSTOM
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(name.into(), style);
+        true
+    }
+
+    #[cfg(not(feature = "std"))]
+    false
+}
+
+/// Reset the style registered under `name`
+///
+/// The [builtin names](NAMES) go back to their default style, and any other name is unregistered
+///
+/// ```rust
+/// use colorz::{Style, theme};
+///
+/// let default = theme::get("info");
+///
+/// theme::set("info", Style::new().italics());
+/// theme::set("path", Style::new().italics());
+///
+/// theme::reset("info");
+/// theme::reset("path");
+///
+/// assert_eq!(theme::get("info"), default);
+/// assert_eq!(theme::get("path"), None);
+/// ```
+#[inline]
+pub fn reset(name: &str) {
+    if let Some(index) = builtin(name) {
+        BUILTIN[index].store(DEFAULTS[index]);
+    } else {
+        #[cfg(feature = "std")]
+        CUSTOM
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(name);
+    }
+}
+
+/// Reset every style, see [`reset`]
+#[inline]
+pub fn reset_all() {
+    for (slot, style) in BUILTIN.iter().zip(DEFAULTS) {
+        slot.store(style);
+    }
+
+    #[cfg(feature = "std")]
+    CUSTOM
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clear();
+}
+
+/// A collection of named styles, which can be [installed](Theme::install) into the registry
+///
+/// Themes can be parsed from a line based config format via [`FromStr`](core::str::FromStr) or [`Theme::from_lines_reader`].
+/// Each line is `name = style`, where the style may be quoted and uses the format described in
+/// [`Style`'s `FromStr` impl](Style#impl-FromStr-for-Style). Blank lines and lines starting with `#`
+/// are ignored. This format isn't TOML, it doesn't support inline comments, tables or escapes.
+/// To read TOML or JSON files, enable the `serde` feature and deserialize the theme from a map of
+/// names to styles with the format's serde crate (like `toml` or `serde_json`).
+///
+/// ```rust
+/// use colorz::{Style, ansi, theme::{self, Theme}};
+///
+/// let theme: Theme = r#"
+///     ## my theme
+///     error = "bold bright red"
+///     path = underline cyan
+/// "#.parse().unwrap();
+///
+/// theme.install();
+///
+/// assert_eq!(theme::get("error"), Some(Style::new().fg(ansi::BrightRed).bold().into_runtime_style()));
+/// assert_eq!(theme::get("path"), Some(Style::new().fg(ansi::Cyan).underline().into_runtime_style()));
+///
+/// let err = "warning = bold yelow".parse::<Theme>().unwrap_err();
+/// assert_eq!(err.key(), Some("warning"));
+/// assert_eq!(err.line(), 1);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Theme {
+    styles: std::collections::BTreeMap<std::string::String, Style>,
+}
+
+#[cfg(feature = "std")]
+impl Theme {
+    /// Create an empty theme
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            styles: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Add a style to the theme, returning the previous style with the same name
+    #[inline]
+    pub fn insert<F: OptionalColor, B: OptionalColor, U: OptionalColor>(
+        &mut self,
+        name: &str,
+        style: Style<F, B, U>,
+    ) -> Option<Style> {
+        self.styles.insert(name.into(), style.to_runtime())
+    }
+
+    /// Get the style with the given name
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<Style> {
+        self.styles.get(name).copied()
+    }
+
+    /// Iterate over the styles in the theme, ordered by name
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Style)> + '_ {
+        self.styles
+            .iter()
+            .map(|(name, &style)| (name.as_str(), style))
+    }
+
+    /// Register every style in the theme, see [`set`]
+    ///
+    /// Names which aren't in the theme are left unchanged
+    #[inline]
+    pub fn install(&self) {
+        for (name, style) in self.iter() {
+            set(name, style);
+        }
+    }
+
+    /// Read a theme in the line based format described in the [type docs](Theme)
+    ///
+    /// Parse errors are returned as [`InvalidData`](std::io::ErrorKind::InvalidData) errors
+    /// which contain a [`ParseThemeError`]
+    #[inline]
+    pub fn from_lines_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<Self> {
+        let mut text = std::string::String::new();
+        reader.read_to_string(&mut text)?;
+        text.parse()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::str::FromStr for Theme {
+    type Err = ParseThemeError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // comments are replaced by blank lines, so that the line numbers stay the same
+        Self::parse_entries(
+            s.lines()
+                .map(|line| match line.trim_start().starts_with('#') {
+                    true => "",
+                    false => line,
+                }),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Theme {
+    /// Parse a theme from a single line of comma separated `name=style` entries, like
+    /// `"error=bold red,path=cyan"`
+    ///
+    /// This is a compact format for environment variables (like `MYAPP_STYLES`), see [`Theme::from_env`].
+    /// Each style uses the format described in [`Style`'s `FromStr` impl](Style#impl-FromStr-for-Style),
+    /// and empty entries are ignored. For errors, [`ParseThemeError::line`] is the number of the entry.
+    ///
+    /// ```rust
+    /// use colorz::{Style, ansi, theme::Theme};
+    ///
+    /// let theme = Theme::from_env_str("error=bold red, path=cyan,").unwrap();
+    ///
+    /// assert_eq!(theme.get("error"), Some(Style::new().fg(ansi::Red).bold().into_runtime_style()));
+    /// assert_eq!(theme.get("path"), Some(Style::new().fg(ansi::Cyan).into_runtime_style()));
+    ///
+    /// let err = Theme::from_env_str("error=red,path").unwrap_err();
+    /// assert_eq!(err.line(), 2);
+    /// ```
+    #[inline]
+    pub fn from_env_str(s: &str) -> Result<Self, ParseThemeError> {
+        Self::parse_entries(s.split(','))
+    }
+
+    /// Parse a theme from the environment variable `var`, in the format described in [`Theme::from_env_str`]
+    ///
+    /// This returns `Ok(None)` if the variable isn't set (or isn't valid unicode)
+    ///
+    /// ```rust
+    /// use colorz::theme::Theme;
+    ///
+    /// if let Some(theme) = Theme::from_env("MYAPP_STYLES").unwrap_or_default() {
+    ///     theme.install();
+    /// }
+    /// ```
+    #[inline]
+    pub fn from_env(var: &str) -> Result<Option<Self>, ParseThemeError> {
+        match std::env::var(var) {
+            Ok(value) => Self::from_env_str(&value).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Parse `name = style` entries, blank entries are ignored
+    fn parse_entries<'a>(entries: impl Iterator<Item = &'a str>) -> Result<Self, ParseThemeError> {
+        let mut theme = Theme::new();
+
+        for (index, line) in entries.enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(ParseThemeError {
+                    line: index + 1,
+                    key: None,
+                    error: None,
+                });
+            };
+
+            let key = unquote(key.trim());
+            let value = unquote(value.trim());
+
+            let style = value.parse::<Style>().map_err(|error| ParseThemeError {
+                line: index + 1,
+                key: Some(key.into()),
+                error: Some(error),
+            })?;
+
+            theme.styles.insert(key.into(), style);
+        }
+
+        Ok(theme)
+    }
+}
+
+#[cfg(feature = "std")]
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(s)
+}
+
+#[cfg(feature = "std")]
+impl<'a> IntoIterator for &'a Theme {
+    type Item = (&'a str, Style);
+    type IntoIter = std::iter::Map<
+        std::collections::btree_map::Iter<'a, std::string::String, Style>,
+        fn((&'a std::string::String, &'a Style)) -> (&'a str, Style),
+    >;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.styles
+            .iter()
+            .map(|(name, &style)| (name.as_str(), style))
+    }
+}
+
+/// An error from parsing a [`Theme`]
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseThemeError {
+    line: usize,
+    key: Option<std::string::String>,
+    error: Option<crate::ParseStyleError>,
+}
+
+#[cfg(feature = "std")]
+impl ParseThemeError {
+    /// The line with the error, starting at 1
+    #[inline]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The name of the style which couldn't be parsed
+    ///
+    /// This is `None` if the line wasn't in the `name = style` format
+    #[inline]
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Why the style couldn't be parsed
+    ///
+    /// This is `None` if the line wasn't in the `name = style` format
+    #[inline]
+    pub const fn style_error(&self) -> Option<&crate::ParseStyleError> {
+        self.error.as_ref()
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for ParseThemeError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (&self.key, &self.error) {
+            (Some(key), Some(error)) => write!(
+                f,
+                "Invalid theme: line {}: invalid style for `{key}`: {error}",
+                self.line
+            ),
+            _ => write!(
+                f,
+                "Invalid theme: line {}: expected `name = style`",
+                self.line
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseThemeError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error
+            .as_ref()
+            .map(|error| error as &(dyn std::error::Error + 'static))
+    }
+}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "serde"))))]
+impl<'de> serde::Deserialize<'de> for Theme {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Theme;
+
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                f.write_str("a map of names to styles")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut theme = Theme::new();
+
+                while let Some((key, value)) =
+                    map.next_entry::<std::string::String, std::string::String>()?
+                {
+                    let style = value.parse::<Style>().map_err(|err| {
+                        serde::de::Error::custom(format_args!("invalid style for `{key}`: {err}"))
+                    })?;
+
+                    theme.styles.insert(key, style);
+                }
+
+                Ok(theme)
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{css, xterm};
+
+    #[test]
+    fn color_encoding() {
+        let colors = [
+            None,
+            Some(Color::Ansi(AnsiColor::Default)),
+            Some(Color::Default),
+            Some(Color::Ansi(AnsiColor::BrightWhite)),
+            Some(Color::Xterm(xterm::Aqua.into())),
+            Some(Color::Css(css::RebeccaPurple.into())),
+            Some(Color::Rgb(RgbColor {
+                red: 1,
+                green: 2,
+                blue: 3,
+            })),
+        ];
+
+        for color in colors {
+            assert_eq!(decode_color(encode_color(color)), color);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Colored formatting for [`tracing-subscriber`](::tracing_subscriber)'s `fmt` subscriber
+//!
+//! [`TracingFormat`] formats events as `LEVEL span:span: target: message field=value`, with the level
+//! styled using the [`theme`](crate::theme) (like [`log`](crate::log) records), the span names in bold,
+//! the target with the `hint` style, and the field names in italics.
+//!
+//! Whether colors are written follows colorz's [coloring mode](crate::mode) (including `NO_COLOR`),
+//! not `tracing-subscriber`'s `with_ansi` setting, so tracing output and the rest of an application's output
+//! are colored consistently.
+//!
+//! ```rust
+//! use colorz::{mode::Stream, tracing::TracingFormat};
+//!
+//! tracing_subscriber::fmt()
+//!     .event_format(TracingFormat::new().stream(Stream::Stdout))
+//!     .fmt_fields(TracingFormat::new().stream(Stream::Stdout))
+//!     .init();
+//!
+//! let span = tracing::info_span!("request", id = 3);
+//! let _guard = span.enter();
+//! tracing::warn!(retries = 2, "connection was reset");
+//! ```
+
+use core::fmt;
+
+use ::tracing_core::{
+    field::{Field, Visit},
+    Event, Level, Subscriber,
+};
+use ::tracing_subscriber::{
+    field::RecordFields,
+    fmt::{format::Writer, FmtContext, FormatEvent, FormatFields},
+    registry::LookupSpan,
+};
+
+use crate::{mode::Stream, Colorize, Style};
+
+/// The [`theme`](crate::theme) style used for the level
+///
+/// ```rust
+/// use colorz::{tracing::level_style, theme};
+///
+/// assert_eq!(Some(level_style(&tracing::Level::ERROR)), theme::get("error"));
+/// ```
+#[inline]
+pub fn level_style(level: &Level) -> Style {
+    let name = match *level {
+        Level::ERROR => "error",
+        Level::WARN => "warning",
+        Level::INFO => "info",
+        Level::DEBUG => "debug",
+        _ => "trace",
+    };
+
+    crate::theme::get_or_plain(name)
+}
+
+/// An event and field formatter for `tracing-subscriber`, see the [module docs](self)
+///
+/// Use it with both [`SubscriberBuilder::event_format`](::tracing_subscriber::fmt::SubscriberBuilder::event_format)
+/// and [`SubscriberBuilder::fmt_fields`](::tracing_subscriber::fmt::SubscriberBuilder::fmt_fields)
+/// to style the fields of events and spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TracingFormat {
+    stream: Option<Stream>,
+    target: bool,
+}
+
+impl Default for TracingFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TracingFormat {
+    /// Create a formatter which writes the target, and uses the default stream
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            stream: None,
+            target: true,
+        }
+    }
+
+    /// Sets the stream used to detect if colors should be written
+    ///
+    /// This should be the stream that the subscriber writes to, `tracing-subscriber` writes to stdout by default
+    #[inline]
+    pub const fn stream(mut self, stream: Stream) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Sets if the target of the event should be written
+    #[inline]
+    pub const fn with_target(mut self, target: bool) -> Self {
+        self.target = target;
+        self
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for TracingFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    #[inline]
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let level = metadata.level();
+
+        write!(
+            writer,
+            "{:<5} ",
+            level
+                .as_str()
+                .style_with(level_style(level))
+                .stream_opt(self.stream)
+        )?;
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                write!(writer, "{}:", span.name().bold().stream_opt(self.stream))?;
+            }
+            writer.write_char(' ')?;
+        }
+
+        if self.target {
+            write!(
+                writer,
+                "{}: ",
+                metadata
+                    .target()
+                    .style_with(crate::theme::get_or_plain("hint"))
+                    .stream_opt(self.stream)
+            )?;
+        }
+
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+impl<'writer> FormatFields<'writer> for TracingFormat {
+    #[inline]
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = FieldVisitor {
+            writer,
+            stream: self.stream,
+            first: true,
+            result: Ok(()),
+        };
+
+        fields.record(&mut visitor);
+        visitor.result
+    }
+}
+
+/// Writes the message as is, and the other fields as `name=value`
+struct FieldVisitor<'writer> {
+    writer: Writer<'writer>,
+    stream: Option<Stream>,
+    first: bool,
+    result: fmt::Result,
+}
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.result.is_err() {
+            return;
+        }
+
+        let separator = if self.first { "" } else { " " };
+        self.first = false;
+
+        self.result = if field.name() == "message" {
+            write!(self.writer, "{separator}{value:?}")
+        } else {
+            write!(
+                self.writer,
+                "{separator}{}={value:?}",
+                field.name().italics().stream_opt(self.stream)
+            )
+        };
+    }
+}
@@ -1,6 +1,15 @@
-use core::fmt::{self, Display};
+use core::fmt::{self, Display, Write};
 
-use crate::{ansi, mode::Stream, Effect, OptionalColor, Style, StyledValue};
+use crate::{
+    ansi,
+    mode::{ColorKind, Stream},
+    render::Render,
+    rgb::RgbColor,
+    strip::StripAnsi,
+    style::EscapeBuffer,
+    xterm::XtermColor,
+    Color, Effect, Hyperlink, OptionalColor, RuntimeStyledValue, Style, StyleEscapes, StyledValue,
+};
 
 impl<T, F, B, U> StyledValue<T, F, B, U> {
     /// Create a new styled value
@@ -10,14 +19,194 @@ impl<T, F, B, U> StyledValue<T, F, B, U> {
             value,
             style,
             stream,
+            options: Options::DEFAULT,
         }
     }
+
+    /// Unwrap the styled value, discarding the style and stream
+    ///
+    /// ```rust
+    /// use colorz::Colorize;
+    ///
+    /// assert_eq!("hello".red().into_inner(), "hello");
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Get a reference to the value
+    #[inline]
+    pub const fn value_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Display the value without any styling, regardless of the coloring mode
+    ///
+    /// This allows the same styled value to be written plainly (e.g. to a log file)
+    /// and styled (e.g. to the terminal). Unlike [`value_ref`](Self::value_ref), any escape
+    /// sequences written by the value itself (like nested styled values) are removed too, see [`StripAnsi`].
+    ///
+    /// ```rust
+    /// use colorz::{Colorize, mode::Stream};
+    ///
+    /// let status = "ok".green();
+    /// assert_eq!(format!("status: {}", status.plain()), "status: ok");
+    /// println!("status: {status}");
+    ///
+    /// let message = format!("{} done", "build".blue().stream(Stream::AlwaysColor));
+    /// assert_eq!(message.bold().plain().to_string(), "build done");
+    /// ```
+    #[inline]
+    pub const fn plain(&self) -> StripAnsi<&T> {
+        StripAnsi(&self.value)
+    }
+
+    /// Get a mutable reference to the value, the style and stream are left unchanged
+    ///
+    /// ```rust
+    /// use colorz::Colorize;
+    ///
+    /// let mut count = 1.into_red();
+    /// *count.value_mut() += 1;
+    /// assert_eq!(count.value, 2);
+    /// ```
+    #[inline]
+    pub const fn value_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Get a mutable reference to the style, the value and stream are left unchanged
+    #[inline]
+    pub const fn style_mut(&mut self) -> &mut Style<F, B, U> {
+        &mut self.style
+    }
+
+    /// Get a mutable reference to the stream, the value and style are left unchanged
+    #[inline]
+    pub const fn stream_mut(&mut self) -> &mut Option<Stream> {
+        &mut self.stream
+    }
+
+    /// Transform the value, keeping the style, stream and any other settings
+    ///
+    /// ```rust
+    /// use colorz::{Colorize, Style, ansi};
+    ///
+    /// let len = "hello".red().map(str::len);
+    /// assert_eq!(len.value, 5);
+    /// assert_eq!(len.style, Style::new().fg(ansi::Red));
+    /// ```
+    #[inline]
+    pub fn map<V>(self, f: impl FnOnce(T) -> V) -> StyledValue<V, F, B, U> {
+        StyledValue {
+            value: f(self.value),
+            style: self.style,
+            stream: self.stream,
+            options: self.options,
+        }
+    }
+
+    /// Borrow the value, keeping the style, stream and any other settings
+    ///
+    /// ```rust
+    /// use colorz::Colorize;
+    ///
+    /// let name = String::from("colorz").into_bold();
+    /// let borrowed = name.as_ref();
+    /// assert_eq!(borrowed.value, "colorz");
+    /// ```
+    #[inline]
+    pub const fn as_ref(&self) -> StyledValue<&T, F, B, U>
+    where
+        F: Copy,
+        B: Copy,
+        U: Copy,
+    {
+        StyledValue {
+            value: &self.value,
+            style: self.style,
+            stream: self.stream,
+            options: self.options,
+        }
+    }
+
+    /// Replace the style, keeping the value, stream and any other settings
+    ///
+    /// ```rust
+    /// use colorz::{Colorize, Style, ansi};
+    ///
+    /// let warning = "warning".red().with_style(Style::new().fg(ansi::Yellow).bold());
+    /// assert_eq!(warning.style, Style::new().fg(ansi::Yellow).bold());
+    /// ```
+    #[inline]
+    pub fn with_style<F2, B2, U2>(self, style: Style<F2, B2, U2>) -> StyledValue<T, F2, B2, U2> {
+        StyledValue {
+            value: self.value,
+            style,
+            stream: self.stream,
+            options: self.options,
+        }
+    }
+
+    /// Adjust the style, keeping the value, stream and any other settings
+    ///
+    /// ```rust
+    /// use colorz::{Colorize, Effect, Style, ansi};
+    ///
+    /// let active = false;
+    /// let item = "item".blue().map_style(|style| if active { style } else { style.dimmed() });
+    /// assert!(item.style.is(Effect::Dimmed));
+    /// assert_eq!(item.style, Style::new().fg(ansi::Blue).dimmed());
+    /// ```
+    #[inline]
+    pub fn map_style<F2, B2, U2>(
+        self,
+        f: impl FnOnce(Style<F, B, U>) -> Style<F2, B2, U2>,
+    ) -> StyledValue<T, F2, B2, U2> {
+        StyledValue {
+            value: self.value,
+            style: f(self.style),
+            stream: self.stream,
+            options: self.options,
+        }
+    }
+}
+
+/// The settings of a [`StyledValue`] which aren't public fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Options {
+    /// See [`StyledValue::per_line`]
+    pub(crate) per_line: bool,
+    /// See [`StyledValue::max_color`]
+    pub(crate) max_color: ColorKind,
+    /// See [`StyledValue::nested`]
+    #[cfg(feature = "std")]
+    pub(crate) nested: bool,
+    /// The name the style is looked up under in the theme when formatting, see [`Colorize::error`](crate::Colorize::error)
+    pub(crate) theme: Option<&'static str>,
+    /// See [`StyledValue::serialize_style`]
+    #[cfg(feature = "serde")]
+    pub(crate) serialize_style: bool,
+}
+
+impl Options {
+    pub(crate) const DEFAULT: Self = Self {
+        per_line: false,
+        max_color: ColorKind::Rgb,
+        #[cfg(feature = "std")]
+        nested: false,
+        theme: None,
+        #[cfg(feature = "serde")]
+        serialize_style: false,
+    };
 }
 
 macro_rules! AnsiColorMethods {
     (
         ($(#[$fg:meta] #[$bg:meta] $color:ident $fun:ident $into_fun:ident $on_fun:ident $into_on_fun:ident)*)
         ($(#[$doc:meta] $effect:ident $effect_fun:ident $into_effect_fun:ident)*)
+        ($(#[$theme_doc:meta] $theme_fun:ident $into_theme_fun:ident)*)
     ) => {
         /// An extension trait for all values which adds convenience formatting functions
         ///
@@ -45,6 +234,7 @@ macro_rules! AnsiColorMethods {
                     value: self,
                     style: Style::new(),
                     stream: None,
+                    options: Options::DEFAULT,
                 }
             }
 
@@ -63,7 +253,8 @@ macro_rules! AnsiColorMethods {
                 StyledValue {
                     value: self,
                     style: Style::new(),
-                    stream: None
+                    stream: None,
+                    options: Options::DEFAULT,
                 }
             }
 
@@ -87,6 +278,7 @@ macro_rules! AnsiColorMethods {
                     value: self,
                     style,
                     stream: None,
+                    options: Options::DEFAULT,
                 }
             }
 
@@ -108,9 +300,33 @@ macro_rules! AnsiColorMethods {
                     value: self,
                     style,
                     stream: None,
+                    options: Options::DEFAULT,
                 }
             }
 
+            /// Write the value as a terminal hyperlink to `url`, see [`Hyperlink`]
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            ///
+            /// ```rust
+            /// use colorz::Colorize;
+            ///
+            /// println!("see {}", "the docs".underline().hyperlink("https://docs.rs/colorz"));
+            /// ```
+            #[inline]
+            fn hyperlink<'a>(&self, url: &'a str) -> Hyperlink<'a, &Self> {
+                Hyperlink::new(self, url)
+            }
+
+            /// Write the value as a terminal hyperlink to `url`, see [`Hyperlink`]
+            #[inline]
+            fn into_hyperlink(self, url: &str) -> Hyperlink<'_, Self>
+            where
+                Self: Sized,
+            {
+                Hyperlink::new(self, url)
+            }
+
             /// Changes the foreground color
             ///
             /// This borrows the source value, so it cannot outlive the source
@@ -213,6 +429,129 @@ macro_rules! AnsiColorMethods {
                 self.into_style().underline_color(color)
             }
 
+            /// Changes the foreground to an RGB color
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            ///
+            /// ```rust
+            /// use colorz::{Colorize, rgb::RgbColor};
+            ///
+            /// let hello = "Hello ".fg_rgb(255, 128, 0);
+            /// assert_eq!(hello.style.foreground, RgbColor { red: 255, green: 128, blue: 0 });
+            /// ```
+            #[inline]
+            fn fg_rgb(&self, red: u8, green: u8, blue: u8) -> StyledValue<&Self, RgbColor> {
+                self.style().fg_rgb(red, green, blue)
+            }
+
+            /// Changes the foreground to an RGB color
+            #[inline]
+            fn into_fg_rgb(self, red: u8, green: u8, blue: u8) -> StyledValue<Self, RgbColor> where Self: Sized {
+                self.into_style().fg_rgb(red, green, blue)
+            }
+
+            /// Changes the background to an RGB color
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            #[inline]
+            fn bg_rgb(&self, red: u8, green: u8, blue: u8) -> StyledValue<&Self, crate::NoColor, RgbColor> {
+                self.style().bg_rgb(red, green, blue)
+            }
+
+            /// Changes the background to an RGB color
+            #[inline]
+            fn into_bg_rgb(self, red: u8, green: u8, blue: u8) -> StyledValue<Self, crate::NoColor, RgbColor> where Self: Sized {
+                self.into_style().bg_rgb(red, green, blue)
+            }
+
+            /// Changes the underline color to an RGB color
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            #[inline]
+            fn underline_rgb(&self, red: u8, green: u8, blue: u8) -> StyledValue<&Self, crate::NoColor, crate::NoColor, RgbColor> {
+                self.style().underline_rgb(red, green, blue)
+            }
+
+            /// Changes the underline color to an RGB color
+            #[inline]
+            fn into_underline_rgb(self, red: u8, green: u8, blue: u8) -> StyledValue<Self, crate::NoColor, crate::NoColor, RgbColor> where Self: Sized {
+                self.into_style().underline_rgb(red, green, blue)
+            }
+
+            /// Changes the foreground to the Xterm color with the given 256-color code
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            ///
+            /// ```rust
+            /// use colorz::{Colorize, xterm::XtermColor};
+            ///
+            /// let hello = "Hello ".fg_xterm(208);
+            /// assert_eq!(hello.style.foreground, XtermColor::from_code(208));
+            /// ```
+            #[inline]
+            fn fg_xterm(&self, code: u8) -> StyledValue<&Self, XtermColor> {
+                self.style().fg_xterm(code)
+            }
+
+            /// Changes the foreground to the Xterm color with the given 256-color code
+            #[inline]
+            fn into_fg_xterm(self, code: u8) -> StyledValue<Self, XtermColor> where Self: Sized {
+                self.into_style().fg_xterm(code)
+            }
+
+            /// Changes the background to the Xterm color with the given 256-color code
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            #[inline]
+            fn bg_xterm(&self, code: u8) -> StyledValue<&Self, crate::NoColor, XtermColor> {
+                self.style().bg_xterm(code)
+            }
+
+            /// Changes the background to the Xterm color with the given 256-color code
+            #[inline]
+            fn into_bg_xterm(self, code: u8) -> StyledValue<Self, crate::NoColor, XtermColor> where Self: Sized {
+                self.into_style().bg_xterm(code)
+            }
+
+            /// Changes the underline color to the Xterm color with the given 256-color code
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            #[inline]
+            fn underline_xterm(&self, code: u8) -> StyledValue<&Self, crate::NoColor, crate::NoColor, XtermColor> {
+                self.style().underline_xterm(code)
+            }
+
+            /// Changes the underline color to the Xterm color with the given 256-color code
+            #[inline]
+            fn into_underline_xterm(self, code: u8) -> StyledValue<Self, crate::NoColor, crate::NoColor, XtermColor> where Self: Sized {
+                self.into_style().underline_xterm(code)
+            }
+
+            /// Changes the foreground color, an alias for [`fg`](Colorize::fg) which converts the color to a [`Color`]
+            ///
+            /// This borrows the source value, so it cannot outlive the source.
+            /// This and [`on`](Colorize::on) are provided to ease migrating from the `colored` crate.
+            ///
+            /// ```rust
+            /// use colorz::{Colorize, Color, ansi};
+            ///
+            /// let hello = "Hello ".color(ansi::Red).on(ansi::Blue);
+            /// assert_eq!(hello.style.foreground, Color::Ansi(ansi::AnsiColor::Red));
+            /// assert_eq!(hello.style.background, Color::Ansi(ansi::AnsiColor::Blue));
+            /// ```
+            #[inline]
+            fn color(&self, color: impl Into<Color>) -> StyledValue<&Self, Color> {
+                self.style().color(color)
+            }
+
+            /// Changes the background color, an alias for [`bg`](Colorize::bg) which converts the color to a [`Color`]
+            ///
+            /// This borrows the source value, so it cannot outlive the source
+            #[inline]
+            fn on(&self, color: impl Into<Color>) -> StyledValue<&Self, crate::NoColor, Color> {
+                self.style().on(color)
+            }
+
             $(#[$fg] #[inline] fn $fun(&self) -> StyledValue<&Self, ansi::$color> {
                 self.style().$fun()
             })*
@@ -236,6 +575,46 @@ macro_rules! AnsiColorMethods {
             $(#[$doc] #[inline] fn $into_effect_fun(self) -> StyledValue<Self> where Self: Sized {
                 self.into_style().$effect_fun()
             })*
+
+            $(
+                #[$theme_doc]
+                ///
+                /// The style is looked up in the [`theme`](crate::theme) each time the value is formatted,
+                /// so changes to the theme also affect existing values. Any styles added to the returned
+                /// value are layered on top of the theme's style.
+                #[inline]
+                fn $theme_fun(&self) -> RuntimeStyledValue<&Self> {
+                    StyledValue {
+                        value: self,
+                        style: Style::new().const_into_runtime_style(),
+                        stream: None,
+                        options: Options {
+                            theme: Some(stringify!($theme_fun)),
+                            ..Options::DEFAULT
+                        },
+                    }
+                }
+            )*
+
+            $(
+                #[$theme_doc]
+                ///
+                /// The style is looked up in the [`theme`](crate::theme) each time the value is formatted,
+                /// so changes to the theme also affect existing values. Any styles added to the returned
+                /// value are layered on top of the theme's style.
+                #[inline]
+                fn $into_theme_fun(self) -> RuntimeStyledValue<Self> where Self: Sized {
+                    StyledValue {
+                        value: self,
+                        style: Style::new().const_into_runtime_style(),
+                        stream: None,
+                        options: Options {
+                            theme: Some(stringify!($theme_fun)),
+                            ..Options::DEFAULT
+                        },
+                    }
+                }
+            )*
         }
 
         impl<T, F: OptionalColor, B: OptionalColor, U: OptionalColor> StyledValue<T, F, B, U> {
@@ -246,6 +625,7 @@ macro_rules! AnsiColorMethods {
                     value: self,
                     style: Style::new(),
                     stream: None,
+                    options: Options::DEFAULT,
                 }
             }
 
@@ -256,6 +636,7 @@ macro_rules! AnsiColorMethods {
                     value: self,
                     style: Style::new(),
                     stream: None,
+                    options: Options::DEFAULT,
                 }
             }
 
@@ -266,6 +647,7 @@ macro_rules! AnsiColorMethods {
                     value: self.value,
                     style: self.style.fg(color),
                     stream: self.stream,
+                    options: self.options,
                 }
             }
 
@@ -276,6 +658,7 @@ macro_rules! AnsiColorMethods {
                     value: self.value,
                     style: self.style.bg(color),
                     stream: self.stream,
+                    options: self.options,
                 }
             }
 
@@ -286,9 +669,80 @@ macro_rules! AnsiColorMethods {
                     value: self.value,
                     style: self.style.underline_color(color),
                     stream: self.stream,
+                    options: self.options,
                 }
             }
 
+            /// Change the foreground to an RGB color
+            ///
+            /// ```rust
+            /// use colorz::{Colorize, mode::Stream};
+            ///
+            /// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+            /// assert_eq!(
+            ///     "hello".bold().fg_rgb(255, 128, 0).stream(Stream::AlwaysColor).to_string(),
+            ///     "\x1b[1m\x1b[38;2;255;128;0mhello\x1b[22m\x1b[39m",
+            /// );
+            /// # }
+            /// ```
+            #[inline]
+            pub fn fg_rgb(self, red: u8, green: u8, blue: u8) -> StyledValue<T, RgbColor, B, U> {
+                self.fg(RgbColor { red, green, blue })
+            }
+
+            /// Change the background to an RGB color
+            #[inline]
+            pub fn bg_rgb(self, red: u8, green: u8, blue: u8) -> StyledValue<T, F, RgbColor, U> {
+                self.bg(RgbColor { red, green, blue })
+            }
+
+            /// Change the underline color to an RGB color
+            #[inline]
+            pub fn underline_rgb(self, red: u8, green: u8, blue: u8) -> StyledValue<T, F, B, RgbColor> {
+                self.underline_color(RgbColor { red, green, blue })
+            }
+
+            /// Change the foreground to the Xterm color with the given 256-color code
+            ///
+            /// ```rust
+            /// use colorz::{Colorize, mode::Stream};
+            ///
+            /// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+            /// assert_eq!(
+            ///     "hello".into_style().fg_xterm(208).stream(Stream::AlwaysColor).to_string(),
+            ///     "\x1b[38;5;208mhello\x1b[39m",
+            /// );
+            /// # }
+            /// ```
+            #[inline]
+            pub fn fg_xterm(self, code: u8) -> StyledValue<T, XtermColor, B, U> {
+                self.fg(XtermColor::from_code(code))
+            }
+
+            /// Change the background to the Xterm color with the given 256-color code
+            #[inline]
+            pub fn bg_xterm(self, code: u8) -> StyledValue<T, F, XtermColor, U> {
+                self.bg(XtermColor::from_code(code))
+            }
+
+            /// Change the underline color to the Xterm color with the given 256-color code
+            #[inline]
+            pub fn underline_xterm(self, code: u8) -> StyledValue<T, F, B, XtermColor> {
+                self.underline_color(XtermColor::from_code(code))
+            }
+
+            /// Change the foreground color, an alias for [`fg`](Self::fg) which converts the color to a [`Color`]
+            #[inline]
+            pub fn color(self, color: impl Into<Color>) -> StyledValue<T, Color, B, U> {
+                self.fg(color.into())
+            }
+
+            /// Change the background color, an alias for [`bg`](Self::bg) which converts the color to a [`Color`]
+            #[inline]
+            pub fn on(self, color: impl Into<Color>) -> StyledValue<T, F, Color, U> {
+                self.bg(color.into())
+            }
+
             $(#[inline] #[$fg] pub fn $fun(self) -> StyledValue<T, ansi::$color, B, U> {
                 self.fg(ansi::$color)
             })*
@@ -302,6 +756,7 @@ macro_rules! AnsiColorMethods {
                     value: self.value,
                     style: self.style.with(Effect::$effect),
                     stream: self.stream,
+                    options: self.options,
                 }
             })*
 
@@ -318,6 +773,106 @@ macro_rules! AnsiColorMethods {
                 self.stream = stream;
                 self
             }
+
+            /// Clear the style before every newline in the value, and apply it again after
+            ///
+            /// Some pagers (like `less -R`) and log viewers reset the style at the end of each line,
+            /// so multi-line values would otherwise lose their style after the first line.
+            ///
+            /// ```rust
+            /// use colorz::{Colorize, mode::Stream};
+            ///
+            /// let value = "hello\nworld".red().per_line().stream(Stream::AlwaysColor);
+            /// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+            /// assert_eq!(value.to_string(), "\x1b[31mhello\x1b[39m\n\x1b[31mworld\x1b[39m");
+            /// # }
+            /// ```
+            #[inline]
+            pub const fn per_line(mut self) -> Self {
+                self.options.per_line = true;
+                self
+            }
+
+            /// Restore the style of the enclosing styled value after this value, instead of clearing it
+            ///
+            /// When this value is written while the value of another styled value is being formatted
+            /// (like from its `Display` impl), it's styled with the outer style layered below its own
+            /// (see [`Style::merge`]). Afterwards the outer style is restored, so the rest of the outer
+            /// value keeps its style. Values which aren't nested are cleared as usual, even if they're
+            /// written while another styled value is being formatted (like with `to_string`).
+            ///
+            /// ```rust
+            /// use core::fmt;
+            /// use colorz::{Colorize, mode::Stream};
+            ///
+            /// struct Greeting;
+            ///
+            /// impl fmt::Display for Greeting {
+            ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            ///         write!(f, "hello {}!", "world".red().nested().stream(Stream::AlwaysColor))
+            ///     }
+            /// }
+            ///
+            /// // the `!` is still green, even though it's written after the red `world`
+            /// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+            /// assert_eq!(
+            ///     Greeting.green().stream(Stream::AlwaysColor).to_string(),
+            ///     "\x1b[32mhello \x1b[31mworld\x1b[32m!\x1b[39m",
+            /// );
+            /// # }
+            /// ```
+            #[inline]
+            #[cfg(feature = "std")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+            pub const fn nested(mut self) -> Self {
+                self.options.nested = true;
+                self
+            }
+
+            /// Limit the colors used when formatting this value to the given kind
+            ///
+            /// Any higher-fidelity colors are converted to the nearest color of the given kind
+            /// when formatting (see [`Color::downgrade`](crate::Color::downgrade)), even if the stream supports them.
+            /// This is useful when the output is passed to tools which don't handle RGB colors.
+            /// [`ColorKind::NoColor`] removes all colors, but keeps the effects.
+            ///
+            /// ```rust
+            /// use colorz::{Colorize, mode::{ColorKind, Stream}, rgb::RgbColor};
+            ///
+            /// let value = "hello".fg(RgbColor { red: 200, green: 10, blue: 20 }).stream(Stream::AlwaysColor);
+            /// # if colorz::Style::new().bold().should_color(Stream::AlwaysColor) {
+            /// assert_eq!(value.max_color(ColorKind::Xterm).to_string(), "\x1b[38;5;160mhello\x1b[39m");
+            /// assert_eq!(value.max_color(ColorKind::Ansi).to_string(), "\x1b[31mhello\x1b[39m");
+            /// # }
+            /// ```
+            #[inline]
+            pub const fn max_color(mut self, kind: ColorKind) -> Self {
+                self.options.max_color = kind;
+                self
+            }
+
+            /// Serialize this value as a `{ value, style }` struct, instead of only the plain value
+            ///
+            /// The style is serialized as a string which can be parsed back into a [`Style`] (like `bold red on blue`),
+            /// and no escape sequences are ever serialized. See the `Serialize` impl on [`StyledValue`] for details.
+            ///
+            /// ```rust
+            /// use colorz::Colorize;
+            ///
+            /// let value = "disk full".red().bold();
+            /// assert_eq!(serde_json::to_string(&value).unwrap(), r#""disk full""#);
+            /// assert_eq!(
+            ///     serde_json::to_string(&value.serialize_style()).unwrap(),
+            ///     r#"{"value":"disk full","style":"bold red"}"#,
+            /// );
+            /// ```
+            #[inline]
+            #[cfg(feature = "serde")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+            pub const fn serialize_style(mut self) -> Self {
+                self.options.serialize_style = true;
+                self
+            }
         }
 
         const fn _all_effects_accounted_for(e: Effect) {
@@ -407,30 +962,574 @@ AnsiColorMethods! {
         SuperScript superscript into_superscript
         /// Applies the subscript effect
         SubScript subscript into_subscript
+        /// Applies the framed effect
+        Framed framed into_framed
+        /// Applies the encircled effect
+        Encircled encircled into_encircled
+    )
+    (
+        /// Applies the theme's `error` style
+        error into_error
+        /// Applies the theme's `warning` style
+        warning into_warning
+        /// Applies the theme's `success` style
+        success into_success
+        /// Applies the theme's `info` style
+        info into_info
+        /// Applies the theme's `hint` style
+        hint into_hint
     )
 }
 
 impl<T, F: OptionalColor, B: OptionalColor, U: OptionalColor> StyledValue<T, F, B, U> {
+    /// Convert to a styled value with a runtime style, so that it has the same type
+    /// regardless of which colors are used
+    ///
+    /// ```rust
+    /// use colorz::{Colorize, RuntimeStyledValue, Style, ansi};
+    ///
+    /// let value: RuntimeStyledValue<&str> = "hello".red().on_blue().into_runtime_style();
+    /// assert_eq!(value.style, Style::new().fg(ansi::Red).bg(ansi::Blue).into_runtime_style());
+    /// ```
+    #[inline]
+    pub fn into_runtime_style(self) -> RuntimeStyledValue<T> {
+        StyledValue {
+            value: self.value,
+            style: self.style.to_runtime(),
+            stream: self.stream,
+            options: self.options,
+        }
+    }
+
+    /// Erase both the style and value types, borrowing the value
+    ///
+    /// This is useful to store styled values of different types in the same collection
+    ///
+    /// ```rust
+    /// use colorz::{Colorize, xterm};
+    ///
+    /// let count = 10.bold();
+    /// let name = "colorz".fg(xterm::Aqua);
+    ///
+    /// let parts = [count.as_dyn_display(), name.as_dyn_display()];
+    /// for part in parts {
+    ///     println!("{part}");
+    /// }
+    /// ```
+    #[inline]
+    pub fn as_dyn_display(&self) -> RuntimeStyledValue<&dyn fmt::Display>
+    where
+        T: fmt::Display,
+    {
+        StyledValue {
+            value: &self.value,
+            style: self.style.to_runtime(),
+            stream: self.stream,
+            options: self.options,
+        }
+    }
+
     /// Writes a styled value with the given value formatter
+    ///
+    /// Unlike the formatting impls (like `Display`), this doesn't pad the value outside of the escapes,
+    /// any width is passed on to `f` as is, since `f` can only be called once.
+    ///
+    /// Like the formatting impls, this restores the enclosing style after values marked with
+    /// [`nested`](Self::nested) which are written by `f`.
+    ///
+    /// ```rust
+    /// use core::fmt;
+    /// use colorz::{Colorize, StyledValue, ansi};
+    ///
+    /// struct Quoted<'a>(StyledValue<&'a str, ansi::Green>);
+    ///
+    /// impl fmt::Display for Quoted<'_> {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         self.0.fmt_with(f, |value, f| write!(f, "{value:?}"))
+    ///     }
+    /// }
+    ///
+    /// println!("{}", Quoted("hello".into_green()));
+    /// ```
     #[inline]
     pub fn fmt_with(
         &self,
         fmt: &mut fmt::Formatter<'_>,
         f: impl FnOnce(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
     ) -> fmt::Result {
-        let use_colors = self.style.should_color(self.stream);
+        if let Some(themed) = self.resolve_theme() {
+            return themed.fmt_styled(fmt, None, false, |value, fmt| f(value, fmt));
+        }
+
+        self.fmt_styled(fmt, None, false, f)
+    }
+
+    /// The style the value is written with, including the theme's style if the value has one
+    pub(crate) fn resolved_style(&self) -> Style {
+        match self.options.theme {
+            Some(name) => crate::theme::get_or_plain(name).merge(self.style.to_runtime()),
+            None => self.style.to_runtime(),
+        }
+    }
+
+    /// If the style comes from the theme, look it up and layer this value's style on top of it
+    pub(crate) fn resolve_theme(&self) -> Option<RuntimeStyledValue<&T>> {
+        self.options.theme.map(|_| StyledValue {
+            value: &self.value,
+            style: self.resolved_style(),
+            stream: self.stream,
+            options: Options {
+                theme: None,
+                ..self.options
+            },
+        })
+    }
+
+    /// Write the styled value, padding it outside of the escapes if a width was given
+    ///
+    /// `f` is called up to 3 times if the value needs to be padded, to measure the value.
+    /// This doesn't look up the theme, see [`resolve_theme`](Self::resolve_theme).
+    ///
+    /// If given, `escapes` are the precomputed escapes of the style (see [`CachedStyle`](crate::CachedStyle)),
+    /// which are written instead of the style when the global settings don't change it
+    pub(crate) fn fmt_padded(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        escapes: Option<&StyleEscapes>,
+        f: impl Fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        let width = match fmt.width() {
+            Some(width) if !fmt.sign_aware_zero_pad() && self.style.should_color(self.stream) => {
+                width
+            }
+            _ => return self.fmt_styled(fmt, escapes, false, f),
+        };
+
+        struct Value<'a, T, G>(&'a T, &'a G);
+
+        impl<T, G: Fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result> Display for Value<'_, T, G> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                (self.1)(self.0, f)
+            }
+        }
+
+        let value = Value(&self.value, &f);
+        let flags = Flags::new(fmt);
+
+        let mut unpadded = VisibleChars::default();
+        flags.write(&mut unpadded, &value)?;
+
+        let padding = width.saturating_sub(unpadded.chars);
+
+        let (fill, align) = match fmt.align() {
+            _ if padding == 0 => return self.fmt_styled(fmt, escapes, true, &f),
+            Some(align) => (fmt.fill(), align),
+            None => {
+                // the alignment wasn't specified, so use the value's default alignment,
+                // this is found by checking where the value puts it's own padding
+                let mut padded = VisibleChars::default();
+                let probe = Flags {
+                    width: Some(unpadded.chars + 2),
+                    ..flags
+                };
+                probe.write(&mut padded, &value)?;
 
-        if use_colors {
-            self.style.apply().fmt(fmt)?;
+                let align = match padded.leading_spaces.checked_sub(unpadded.leading_spaces) {
+                    // the value ignores the width, so it shouldn't be padded
+                    _ if padded.chars == unpadded.chars => {
+                        return self.fmt_styled(fmt, escapes, true, &f)
+                    }
+                    Some(0) => fmt::Alignment::Left,
+                    Some(1) => fmt::Alignment::Center,
+                    Some(_) => fmt::Alignment::Right,
+                    // the value's output changed between calls, so it can't be measured
+                    None => return self.fmt_styled(fmt, escapes, true, &f),
+                };
+
+                (' ', align)
+            }
+        };
+
+        let (before, after) = match align {
+            fmt::Alignment::Left => (0, padding),
+            fmt::Alignment::Right => (padding, 0),
+            fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+        };
+
+        for _ in 0..before {
+            fmt.write_char(fill)?;
         }
-        f(&self.value, fmt)?;
-        if use_colors {
-            self.style.clear().fmt(fmt)?;
+        self.fmt_styled(fmt, escapes, true, &f)?;
+        for _ in 0..after {
+            fmt.write_char(fill)?;
         }
+
+        Ok(())
+    }
+
+    /// Write the styled value, if `padded` is true then the width is not passed to the value
+    fn fmt_styled(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        escapes: Option<&StyleEscapes>,
+        padded: bool,
+        f: impl FnOnce(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        // styles changed by the global settings are written via the runtime style
+        let runtime = match crate::render::resolve(&self.style, self.stream, self.options.max_color)
+        {
+            Render::Plain => return f(&self.value, fmt),
+            Render::AsIs => None,
+            Render::Runtime(style) => Some(style),
+        };
+
+        #[cfg(feature = "std")]
+        {
+            struct Restore(Option<Style>);
+
+            impl Drop for Restore {
+                fn drop(&mut self) {
+                    ENCLOSING_STYLE.set(self.0);
+                }
+            }
+
+            let style = runtime.unwrap_or_else(|| self.style.to_runtime());
+            let enclosing = ENCLOSING_STYLE.get();
+
+            match enclosing.filter(|_| self.options.nested) {
+                None if !padded && !self.options.per_line => {
+                    let _restore = Restore(enclosing);
+                    ENCLOSING_STYLE.set(Some(style));
+                    self.fmt_batched(fmt, escapes, runtime, f)
+                }
+                None => {
+                    match runtime {
+                        Some(style) => style.apply().fmt(fmt)?,
+                        None => self.fmt_apply(fmt, escapes)?,
+                    }
+                    let cleared = {
+                        let _restore = Restore(enclosing);
+                        ENCLOSING_STYLE.set(Some(style));
+                        self.fmt_value(fmt, style, padded, f)?
+                    };
+                    if !cleared {
+                        match runtime {
+                            Some(style) => style.clear().fmt(fmt)?,
+                            None => self.fmt_clear(fmt, escapes)?,
+                        }
+                    }
+                    Ok(())
+                }
+                Some(outer) => {
+                    let effective = outer.merge(style);
+
+                    effective.transition_from(&outer).fmt(fmt)?;
+                    let cleared = {
+                        let _restore = Restore(Some(outer));
+                        ENCLOSING_STYLE.set(Some(effective));
+                        self.fmt_value(fmt, effective, padded, f)?
+                    };
+                    if cleared {
+                        outer.apply().fmt(fmt)
+                    } else {
+                        outer.transition_from(&effective).fmt(fmt)
+                    }
+                }
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            if !padded && !self.options.per_line {
+                return self.fmt_batched(fmt, escapes, runtime, f);
+            }
+
+            match runtime {
+                Some(style) => {
+                    style.apply().fmt(fmt)?;
+                    if !self.fmt_value(fmt, style, padded, f)? {
+                        style.clear().fmt(fmt)?;
+                    }
+                }
+                None => {
+                    self.fmt_apply(fmt, escapes)?;
+                    if !self.fmt_value(fmt, self.style.to_runtime(), padded, f)? {
+                        self.fmt_clear(fmt, escapes)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Write the escapes and the value, collecting them in a stack buffer first
+    ///
+    /// Calls to the formatter are comparatively expensive, so short values (like most `&str`s)
+    /// are written with a single call. This can't be used with [`per_line`](Self::per_line),
+    /// or if the value is padded, since the value is written with a new formatter.
+    fn fmt_batched(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        escapes: Option<&StyleEscapes>,
+        runtime: Option<Style>,
+        f: impl FnOnce(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        let value = OnceValue {
+            value: &self.value,
+            f: core::cell::Cell::new(Some(f)),
+        };
+
+        let flags = Flags::new(fmt);
+        let mut out = Batched {
+            fmt,
+            buffer: EscapeBuffer::new(),
+        };
+
+        match (runtime, escapes) {
+            (Some(style), _) => style.write_apply_to(&mut out)?,
+            (None, Some(escapes)) => out.write_str(escapes.prefix())?,
+            (None, None) => self.style.write_apply_to(&mut out)?,
+        }
+        flags.write(&mut out, &value)?;
+        match (runtime, escapes) {
+            (Some(style), _) => style.write_clear_to(&mut out)?,
+            (None, Some(escapes)) => out.write_str(escapes.suffix())?,
+            (None, None) => self.style.write_clear_to(&mut out)?,
+        }
+
+        out.flush()
+    }
+
+    /// Write the escapes which apply the style, or the precomputed `escapes` if given
+    fn fmt_apply(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        escapes: Option<&StyleEscapes>,
+    ) -> fmt::Result {
+        match escapes {
+            Some(escapes) => fmt.write_str(escapes.prefix()),
+            None => self.style.apply().fmt(fmt),
+        }
+    }
+
+    /// Write the escapes which clear the style, or the precomputed `escapes` if given
+    fn fmt_clear(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        escapes: Option<&StyleEscapes>,
+    ) -> fmt::Result {
+        match escapes {
+            Some(escapes) => fmt.write_str(escapes.suffix()),
+            None => self.style.clear().fmt(fmt),
+        }
+    }
+
+    /// Write the value, handling [`per_line`](Self::per_line) if needed
+    ///
+    /// Returns true if the style was already cleared
+    fn fmt_value(
+        &self,
+        fmt: &mut fmt::Formatter<'_>,
+        style: Style,
+        padded: bool,
+        f: impl FnOnce(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> Result<bool, fmt::Error> {
+        if !self.options.per_line && !padded {
+            f(&self.value, fmt)?;
+            return Ok(false);
+        }
+
+        let value = OnceValue {
+            value: &self.value,
+            f: core::cell::Cell::new(Some(f)),
+        };
+
+        let flags = Flags::new(fmt);
+
+        if !self.options.per_line {
+            flags.write(fmt, &value)?;
+            return Ok(false);
+        }
+
+        let mut lines = PerLine {
+            fmt,
+            style,
+            cleared: false,
+        };
+
+        flags.write(&mut lines, &value)?;
+
+        Ok(lines.cleared)
+    }
+}
+
+/// A value which is written by calling `f` once, later writes are empty
+struct OnceValue<'a, T, G> {
+    value: &'a T,
+    f: core::cell::Cell<Option<G>>,
+}
+
+impl<T, G: FnOnce(&T, &mut fmt::Formatter<'_>) -> fmt::Result> Display for OnceValue<'_, T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.f.take() {
+            Some(fmt_value) => fmt_value(self.value, f),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A writer which collects small writes in a stack buffer, and writes them to the formatter at once
+struct Batched<'a, 'b> {
+    fmt: &'a mut fmt::Formatter<'b>,
+    buffer: EscapeBuffer,
+}
+
+impl Batched<'_, '_> {
+    fn flush(&mut self) -> fmt::Result {
+        if !self.buffer.as_str().is_empty() {
+            self.fmt.write_str(self.buffer.as_str())?;
+            self.buffer = EscapeBuffer::new();
+        }
+
         Ok(())
     }
 }
 
+impl fmt::Write for Batched<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // the buffer is left as is if `s` doesn't fit
+        if self.buffer.write_str(s).is_ok() {
+            return Ok(());
+        }
+
+        self.flush()?;
+
+        if self.buffer.write_str(s).is_err() {
+            self.fmt.write_str(s)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A writer which clears the style before each newline, and applies it again before the next line
+struct PerLine<'a, 'b> {
+    fmt: &'a mut fmt::Formatter<'b>,
+    style: Style,
+    cleared: bool,
+}
+
+impl fmt::Write for PerLine<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        let mut line = lines.next().unwrap_or_default();
+
+        loop {
+            if !line.is_empty() {
+                if self.cleared {
+                    self.style.apply().fmt(self.fmt)?;
+                    self.cleared = false;
+                }
+                self.fmt.write_str(line)?;
+            }
+
+            let Some(next) = lines.next() else {
+                return Ok(());
+            };
+
+            if !self.cleared {
+                self.style.clear().fmt(self.fmt)?;
+                self.cleared = true;
+            }
+            self.fmt.write_str("\n")?;
+            line = next;
+        }
+    }
+}
+
+/// The formatting flags which are forwarded when a value is written to another writer
+///
+/// The width is only kept for zero padding, and the fill and alignment are dropped
+#[derive(Clone, Copy)]
+pub(crate) struct Flags {
+    alternate: bool,
+    sign_plus: bool,
+    zero_pad: bool,
+    width: Option<usize>,
+    precision: Option<usize>,
+}
+
+impl Flags {
+    pub(crate) fn new(fmt: &fmt::Formatter<'_>) -> Self {
+        Self {
+            alternate: fmt.alternate(),
+            sign_plus: fmt.sign_plus(),
+            zero_pad: fmt.sign_aware_zero_pad(),
+            width: fmt.width().filter(|_| fmt.sign_aware_zero_pad()),
+            precision: fmt.precision(),
+        }
+    }
+
+    /// Write `value` using these flags
+    pub(crate) fn write(self, out: &mut dyn fmt::Write, value: &dyn Display) -> fmt::Result {
+        let width = self.width.map(|width| (self.zero_pad, width));
+
+        match (self.alternate, self.sign_plus, width, self.precision) {
+            (false, false, None, None) => write!(out, "{value}"),
+            (false, false, None, Some(p)) => write!(out, "{value:.p$}"),
+            (false, false, Some((false, w)), None) => write!(out, "{value:w$}"),
+            (false, false, Some((false, w)), Some(p)) => write!(out, "{value:w$.p$}"),
+            (false, false, Some((true, w)), None) => write!(out, "{value:0w$}"),
+            (false, false, Some((true, w)), Some(p)) => write!(out, "{value:0w$.p$}"),
+            (false, true, None, None) => write!(out, "{value:+}"),
+            (false, true, None, Some(p)) => write!(out, "{value:+.p$}"),
+            (false, true, Some((false, w)), None) => write!(out, "{value:+w$}"),
+            (false, true, Some((false, w)), Some(p)) => write!(out, "{value:+w$.p$}"),
+            (false, true, Some((true, w)), None) => write!(out, "{value:+0w$}"),
+            (false, true, Some((true, w)), Some(p)) => write!(out, "{value:+0w$.p$}"),
+            (true, false, None, None) => write!(out, "{value:#}"),
+            (true, false, None, Some(p)) => write!(out, "{value:#.p$}"),
+            (true, false, Some((false, w)), None) => write!(out, "{value:#w$}"),
+            (true, false, Some((false, w)), Some(p)) => write!(out, "{value:#w$.p$}"),
+            (true, false, Some((true, w)), None) => write!(out, "{value:#0w$}"),
+            (true, false, Some((true, w)), Some(p)) => write!(out, "{value:#0w$.p$}"),
+            (true, true, None, None) => write!(out, "{value:+#}"),
+            (true, true, None, Some(p)) => write!(out, "{value:+#.p$}"),
+            (true, true, Some((false, w)), None) => write!(out, "{value:+#w$}"),
+            (true, true, Some((false, w)), Some(p)) => write!(out, "{value:+#w$.p$}"),
+            (true, true, Some((true, w)), None) => write!(out, "{value:+#0w$}"),
+            (true, true, Some((true, w)), Some(p)) => write!(out, "{value:+#0w$.p$}"),
+        }
+    }
+}
+
+/// A writer which counts the visible characters written to it, skipping any escape sequences
+#[derive(Default)]
+struct VisibleChars {
+    chars: usize,
+    leading_spaces: usize,
+    scanner: crate::strip::EscapeScanner,
+}
+
+impl fmt::Write for VisibleChars {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars().filter(|&c| self.scanner.next(c)) {
+            if c == ' ' && self.chars == self.leading_spaces {
+                self.leading_spaces += 1;
+            }
+            self.chars += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    // The effective style of the styled value currently being written on this thread, if any
+    static ENCLOSING_STYLE: core::cell::Cell<Option<Style>> = const { core::cell::Cell::new(None) };
+}
+
 macro_rules! fmt_impl {
     ($name:ident) => {
         impl<T: fmt::$name, F: OptionalColor, B: OptionalColor, U: OptionalColor> fmt::$name
@@ -438,7 +1537,12 @@ macro_rules! fmt_impl {
         {
             #[inline]
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                self.fmt_with(f, fmt::$name::fmt)
+                match self.resolve_theme() {
+                    Some(themed) => {
+                        themed.fmt_padded(f, None, |value, f| fmt::$name::fmt(*value, f))
+                    }
+                    None => self.fmt_padded(f, None, fmt::$name::fmt),
+                }
             }
         }
     };
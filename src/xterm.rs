@@ -48,6 +48,27 @@ macro_rules! XTerm {
             }};
         }
 
+        /// Convert a color name to the compile time Xterm color type
+        ///
+        /// This is like [`xterm_from_code!`], but the color is selected by it's name,
+        /// which is useful in macros that generate styles.
+        ///
+        /// ```rust
+        /// use colorz::{xterm_color, xterm, Colorize};
+        ///
+        /// let pink: xterm_color!(DeepPink3) = xterm_color!(DeepPink3);
+        /// assert_eq!(xterm::XtermColor::from(pink), xterm::XtermColor::DeepPink3);
+        ///
+        /// println!("{}", "hello".fg(xterm_color!(DeepPink3)));
+        /// ```
+        #[macro_export]
+        macro_rules! xterm_color {
+            $(($name) => { $crate::xterm::$name };)*
+            ($d t:tt) => {{
+                compile_error! { concat!("Unknown Xterm color name: ", stringify!($d t)) }
+            }};
+        }
+
         impl From<u8> for XtermColor {
             #[inline(always)]
             fn from(args: u8) -> Self {
@@ -69,6 +90,13 @@ macro_rules! XTerm {
             }
         }
 
+        impl From<XtermColor> for crate::rgb::RgbColor {
+            #[inline(always)]
+            fn from(color: XtermColor) -> Self {
+                color.rgb()
+            }
+        }
+
         $(
             impl From<$name> for XtermColor {
                 #[inline(always)]
@@ -105,6 +133,24 @@ macro_rules! XTerm {
                 }
             }
 
+            /// All Xterm colors, ordered by their color args
+            const ALL: &'static [XtermColor; 256] = &[
+                $(XtermColor::$name,)*
+            ];
+
+            /// Iterate over every Xterm color, ordered by their color args
+            ///
+            /// ```rust
+            /// use colorz::xterm::XtermColor;
+            ///
+            /// assert_eq!(XtermColor::variants().len(), 256);
+            /// assert!(XtermColor::variants().enumerate().all(|(i, color)| color as usize == i));
+            /// ```
+            #[inline]
+            pub fn variants() -> core::iter::Copied<core::slice::Iter<'static, Self>> {
+                Self::ALL.iter().copied()
+            }
+
             /// The color args of this Xterm color
             #[inline]
             pub const fn args(self) -> &'static str {
@@ -178,42 +224,14 @@ macro_rules! XTerm {
         impl ColorSpec for XtermColor {
             type Dynamic = Self;
 
-            const KIND: crate::mode::ColorKind = crate::mode::ColorKind::Xterm;
+            const KIND: crate::mode::ColorKind = crate::cap::kind(crate::mode::ColorKind::Xterm);
 
             #[inline]
             fn into_dynamic(self) -> Self::Dynamic {
                 self
             }
 
-            #[inline]
-            fn foreground_args(self) -> &'static str {
-                self.foreground_args()
-            }
-
-            #[inline]
-            fn background_args(self) -> &'static str {
-                self.background_args()
-            }
-
-            #[inline]
-            fn underline_args(self) -> &'static str {
-                self.underline_args()
-            }
-
-            #[inline]
-            fn foreground_escape(self) -> &'static str {
-                self.foreground_escape()
-            }
-
-            #[inline]
-            fn background_escape(self) -> &'static str {
-                self.background_escape()
-            }
-
-            #[inline]
-            fn underline_escape(self) -> &'static str {
-                self.underline_escape()
-            }
+            runtime_capped_methods!(self => crate::Color::Xterm(self));
         }
 
 
@@ -244,47 +262,166 @@ macro_rules! XTerm {
             impl ColorSpec for $name {
                 type Dynamic = XtermColor;
 
-                const KIND: crate::mode::ColorKind = crate::mode::ColorKind::Xterm;
+                const KIND: crate::mode::ColorKind = crate::cap::kind(crate::mode::ColorKind::Xterm);
 
                 #[inline]
                 fn into_dynamic(self) -> Self::Dynamic {
                     Self::DYNAMIC
                 }
 
-                #[inline]
-                fn foreground_args(self) -> &'static str {
-                    Self::FOREGROUND_ARGS
-                }
-
-                #[inline]
-                fn background_args(self) -> &'static str {
-                    Self::BACKGROUND_ARGS
-                }
-
-                #[inline]
-                fn underline_args(self) -> &'static str {
-                    Self::UNDERLINE_ARGS
-                }
-
-                #[inline]
-                fn foreground_escape(self) -> &'static str {
-                    Self::FOREGROUND_ESCAPE
-                }
-
-                #[inline]
-                fn background_escape(self) -> &'static str {
-                    Self::BACKGROUND_ESCAPE
-                }
-
-                #[inline]
-                fn underline_escape(self) -> &'static str {
-                    Self::UNDERLINE_ESCAPE
-                }
+                comptime_capped_methods!(crate::Color::Xterm(Self::DYNAMIC));
             }
         )*
     };
 }
 
+impl XtermColor {
+    /// The rgb value of this color in the default xterm palette
+    ///
+    /// Terminals may be configured to use a different palette, especially for the first 16 colors,
+    /// so this is only an approximation of the color which is shown.
+    ///
+    /// ```rust
+    /// use colorz::{rgb::RgbColor, xterm::{self, XtermColor}};
+    ///
+    /// assert_eq!(XtermColor::from(xterm::Red).rgb(), RgbColor { red: 205, green: 0, blue: 0 });
+    /// assert_eq!(XtermColor::from_code(67).rgb(), RgbColor { red: 95, green: 135, blue: 175 });
+    /// assert_eq!(XtermColor::from_code(244).rgb(), RgbColor { red: 128, green: 128, blue: 128 });
+    /// ```
+    #[inline]
+    pub const fn rgb(self) -> crate::rgb::RgbColor {
+        const ANSI: [(u8, u8, u8); 16] = [
+            (0x00, 0x00, 0x00),
+            (0xcd, 0x00, 0x00),
+            (0x00, 0xcd, 0x00),
+            (0xcd, 0xcd, 0x00),
+            (0x00, 0x00, 0xee),
+            (0xcd, 0x00, 0xcd),
+            (0x00, 0xcd, 0xcd),
+            (0xe5, 0xe5, 0xe5),
+            (0x7f, 0x7f, 0x7f),
+            (0xff, 0x00, 0x00),
+            (0x00, 0xff, 0x00),
+            (0xff, 0xff, 0x00),
+            (0x5c, 0x5c, 0xff),
+            (0xff, 0x00, 0xff),
+            (0x00, 0xff, 0xff),
+            (0xff, 0xff, 0xff),
+        ];
+
+        const fn cube_level(x: u8) -> u8 {
+            if x == 0 {
+                0
+            } else {
+                55 + 40 * x
+            }
+        }
+
+        let code = self as u8;
+        let (red, green, blue) = match code {
+            0..=15 => ANSI[code as usize],
+            16..=231 => {
+                let code = code - 16;
+                (
+                    cube_level(code / 36),
+                    cube_level(code / 6 % 6),
+                    cube_level(code % 6),
+                )
+            }
+            232..=255 => {
+                let level = 8 + 10 * (code - 232);
+                (level, level, level)
+            }
+        };
+
+        crate::rgb::RgbColor { red, green, blue }
+    }
+
+    /// The color in the 6x6x6 color cube (codes 16 to 231) with the given levels
+    ///
+    /// Each level is between 0 (none) and 5 (full intensity)
+    ///
+    /// # Panics
+    ///
+    /// If any level is greater than 5
+    ///
+    /// ```rust
+    /// use colorz::xterm::{self, XtermColor};
+    ///
+    /// assert_eq!(XtermColor::from_cube(0, 0, 0), xterm::Gray0.into());
+    /// assert_eq!(XtermColor::from_cube(5, 2, 0), xterm::DarkOrange.into());
+    /// assert_eq!(XtermColor::from_cube(5, 5, 5), XtermColor::from_code(231));
+    /// ```
+    #[inline]
+    pub const fn from_cube(red: u8, green: u8, blue: u8) -> Self {
+        assert!(
+            red < 6 && green < 6 && blue < 6,
+            "color cube levels must be between 0 and 5"
+        );
+        Self::from_code(16 + 36 * red + 6 * green + blue)
+    }
+
+    /// The color in the grayscale ramp (codes 232 to 255) with the given level
+    ///
+    /// The level is between 0 (almost black) and 23 (almost white)
+    ///
+    /// # Panics
+    ///
+    /// If the level is greater than 23
+    ///
+    /// ```rust
+    /// use colorz::xterm::XtermColor;
+    ///
+    /// assert_eq!(XtermColor::gray(0), XtermColor::from_code(232));
+    /// assert_eq!(XtermColor::gray(23), XtermColor::from_code(255));
+    /// ```
+    #[inline]
+    pub const fn gray(level: u8) -> Self {
+        assert!(level < 24, "grayscale levels must be between 0 and 23");
+        Self::from_code(232 + level)
+    }
+
+    /// The perceptually nearest Xterm color to an rgb color, using the default xterm palette
+    ///
+    /// Both the nearest color in the 6x6x6 color cube and the nearest color in the grayscale ramp are
+    /// considered, and whichever looks closer is picked. The first 16 Xterm colors are never picked,
+    /// since they are the ANSI colors, which are often customized by the terminal's theme.
+    ///
+    /// ```rust
+    /// use colorz::{rgb::RgbColor, xterm::{self, XtermColor}};
+    ///
+    /// const ORANGE: XtermColor = XtermColor::from_rgb(RgbColor { red: 255, green: 128, blue: 0 });
+    ///
+    /// assert_eq!(ORANGE, xterm::DarkOrange.into());
+    /// assert_eq!(XtermColor::from_rgb(RgbColor { red: 95, green: 135, blue: 175 }), XtermColor::from_code(67));
+    /// assert_eq!(XtermColor::from_rgb(RgbColor { red: 120, green: 122, blue: 121 }), XtermColor::from_code(243));
+    /// ```
+    #[inline]
+    pub const fn from_rgb(color: crate::rgb::RgbColor) -> Self {
+        color.to_xterm()
+    }
+
+    /// The nearest ANSI color
+    ///
+    /// The first 16 Xterm colors are the ANSI colors, so they are converted exactly.
+    /// The other colors are converted to the ANSI color nearest to their rgb value in the default xterm palette.
+    /// To match against the terminal's actual colors, see `mode::TerminalPalette` (with the `std` feature).
+    ///
+    /// ```rust
+    /// use colorz::{ansi::AnsiColor, xterm::{self, XtermColor}};
+    ///
+    /// assert_eq!(XtermColor::from_code(9).to_ansi_approx(), AnsiColor::BrightRed);
+    /// assert_eq!(XtermColor::from(xterm::DarkBlue).to_ansi_approx(), AnsiColor::Blue);
+    /// ```
+    #[inline]
+    pub const fn to_ansi_approx(self) -> crate::ansi::AnsiColor {
+        match crate::ansi::AnsiColor::from_index(self as u8) {
+            Some(color) if (self as u8) < 16 => color,
+            _ => self.rgb().to_ansi(),
+        }
+    }
+}
+
 XTerm! {
     $
     0 Black
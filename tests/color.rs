@@ -0,0 +1,278 @@
+use colorz::{ansi::AnsiColor, rgb::RgbColor, xterm::XtermColor, Color};
+
+const ANSI_COLORS: [AnsiColor; 17] = [
+    AnsiColor::Black,
+    AnsiColor::Red,
+    AnsiColor::Green,
+    AnsiColor::Yellow,
+    AnsiColor::Blue,
+    AnsiColor::Magenta,
+    AnsiColor::Cyan,
+    AnsiColor::White,
+    AnsiColor::BrightBlack,
+    AnsiColor::BrightRed,
+    AnsiColor::BrightGreen,
+    AnsiColor::BrightYellow,
+    AnsiColor::BrightBlue,
+    AnsiColor::BrightMagenta,
+    AnsiColor::BrightCyan,
+    AnsiColor::BrightWhite,
+    AnsiColor::Default,
+];
+
+#[test]
+fn test_display_round_trip() {
+    for code in 0..=255 {
+        let color = Color::Xterm(XtermColor::from_code(code));
+        assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+    }
+
+    for color in ANSI_COLORS {
+        let color = Color::Ansi(color);
+        let expected = match color {
+            // `default` parses to the dedicated `Color::Default` variant
+            Color::Ansi(AnsiColor::Default) => Color::Default,
+            _ => color,
+        };
+        assert_eq!(color.to_string().parse::<Color>(), Ok(expected));
+    }
+
+    assert_eq!(Color::Default.to_string(), "default");
+    assert_eq!("default".parse::<Color>(), Ok(Color::Default));
+
+    let color = Color::Rgb(RgbColor {
+        red: 0,
+        green: 0x0f,
+        blue: 0xf0,
+    });
+    assert_eq!(color.to_string(), "#000ff0");
+    assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+}
+
+#[test]
+fn test_css_round_trip() {
+    use colorz::css::CssColor;
+
+    for name in [
+        "AliceBlue",
+        "Red",
+        "DodgerBlue",
+        "YellowGreen",
+        "RebeccaPurple",
+    ] {
+        let color = CssColor::from_name(name).unwrap();
+        assert_eq!(color.name(), name);
+
+        let color = Color::Css(color);
+        assert_eq!(color.to_string().parse::<Color>(), Ok(color));
+    }
+
+    assert_eq!(CssColor::from_name("not a color"), None);
+
+    // names which are also ansi colors need a prefix to parse as the css color
+    assert_eq!(Color::Css(CssColor::Red).to_string(), "css:Red");
+    assert_eq!(Color::Css(CssColor::Purple).to_string(), "css:Purple");
+    assert_eq!(
+        "css:dodger-blue".parse::<Color>(),
+        Ok(Color::Css(CssColor::DodgerBlue))
+    );
+    assert_eq!(
+        "css:bright red".parse::<Color>(),
+        Err(colorz::ParseColorError::UnknownColor)
+    );
+}
+
+#[test]
+fn test_parse_ansi_names_ignoring_case() {
+    use colorz::css::CssColor;
+
+    for color in ANSI_COLORS {
+        let name = color.name();
+        if name == "default" {
+            continue;
+        }
+
+        assert_eq!(name.to_uppercase().parse::<Color>(), Ok(Color::Ansi(color)));
+    }
+
+    assert_eq!("Red".parse::<Color>(), Ok(Color::Ansi(AnsiColor::Red)));
+    assert_eq!(
+        "Bright Blue".parse::<Color>(),
+        Ok(Color::Ansi(AnsiColor::BrightBlue))
+    );
+    assert_eq!(
+        "PURPLE".parse::<Color>(),
+        Ok(Color::Ansi(AnsiColor::Magenta))
+    );
+    assert_eq!("DEFAULT".parse::<Color>(), Ok(Color::Default));
+
+    // css only names still fall back to the css colors
+    assert_eq!(
+        "RebeccaPurple".parse::<Color>(),
+        Ok(Color::Css(CssColor::RebeccaPurple))
+    );
+}
+
+#[test]
+fn test_parse_rgb_shorthand() {
+    assert_eq!(
+        "#abc".parse::<Color>(),
+        Ok(Color::Rgb(RgbColor {
+            red: 0xaa,
+            green: 0xbb,
+            blue: 0xcc
+        }))
+    );
+    assert_eq!(
+        "#F0a".parse::<Color>(),
+        Ok(Color::Rgb(RgbColor {
+            red: 0xff,
+            green: 0x00,
+            blue: 0xaa
+        }))
+    );
+    assert_eq!(
+        "#abg".parse::<Color>(),
+        Err(colorz::ParseColorError::InvalidHexDigit)
+    );
+}
+
+#[test]
+fn test_parse_xterm_decimal() {
+    for code in 0..=255u8 {
+        assert_eq!(
+            code.to_string().parse::<Color>(),
+            Ok(Color::Xterm(XtermColor::from_code(code)))
+        );
+    }
+}
+
+#[test]
+fn test_parse_color_functions() {
+    use colorz::ParseColorError;
+
+    let rgb = |red, green, blue| Ok(Color::Rgb(RgbColor { red, green, blue }));
+
+    assert_eq!("rgb(255, 0, 10)".parse::<Color>(), rgb(255, 0, 10));
+    assert_eq!("rgb(1,2,3)".parse::<Color>(), rgb(1, 2, 3));
+    assert_eq!("RGB( 1 2 3 )".parse::<Color>(), rgb(1, 2, 3));
+    assert_eq!("hsl(0, 100%, 50%)".parse::<Color>(), rgb(255, 0, 0));
+    assert_eq!("hsl(120deg 50% 50%)".parse::<Color>(), rgb(64, 191, 64));
+
+    assert_eq!(
+        "rgb(256, 0, 0)".parse::<Color>(),
+        Err(ParseColorError::U8Overflow)
+    );
+    assert_eq!(
+        "rgb(1, 2)".parse::<Color>(),
+        Err(ParseColorError::InvalidArguments)
+    );
+    assert_eq!(
+        "rgb(red, 0, 0)".parse::<Color>(),
+        Err(ParseColorError::InvalidArguments)
+    );
+    assert_eq!(
+        "hsl(0, 101%, 50%)".parse::<Color>(),
+        Err(ParseColorError::InvalidArguments)
+    );
+}
+
+#[test]
+fn test_downgrade_round_trip() {
+    let level = |x: u8| if x == 0 { 0 } else { 55 + 40 * x };
+
+    for code in 16..=255u8 {
+        let rgb = if code < 232 {
+            let index = code - 16;
+            RgbColor {
+                red: level(index / 36),
+                green: level(index / 6 % 6),
+                blue: level(index % 6),
+            }
+        } else {
+            let gray = 8 + 10 * (code - 232);
+            RgbColor {
+                red: gray,
+                green: gray,
+                blue: gray,
+            }
+        };
+
+        assert_eq!(rgb.to_xterm(), XtermColor::from_code(code));
+    }
+
+    for code in 0..16 {
+        assert_eq!(XtermColor::from_code(code).to_ansi_approx() as u8, code);
+    }
+}
+
+#[test]
+fn test_hsl_hsv_round_trip() {
+    let close = |a: RgbColor, b: RgbColor| {
+        a.red.abs_diff(b.red) <= 3 && a.green.abs_diff(b.green) <= 3 && a.blue.abs_diff(b.blue) <= 3
+    };
+
+    for red in (0..=255).step_by(15) {
+        for green in (0..=255).step_by(15) {
+            for blue in (0..=255).step_by(15) {
+                let color = RgbColor { red, green, blue };
+
+                let (hue, saturation, lightness) = color.to_hsl();
+                assert!(close(RgbColor::from_hsl(hue, saturation, lightness), color));
+
+                let (hue, saturation, value) = color.to_hsv();
+                assert!(close(RgbColor::from_hsv(hue, saturation, value), color));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_oklab_round_trip() {
+    for red in (0..=255).step_by(15) {
+        for green in (0..=255).step_by(15) {
+            for blue in (0..=255).step_by(15) {
+                let color = RgbColor { red, green, blue };
+
+                assert_eq!(RgbColor::from_oklab(color.to_oklab()), color);
+                assert_eq!(RgbColor::from_oklch(color.to_oklch()), color);
+            }
+        }
+    }
+}
+
+#[cfg(not(any(
+    feature = "strip-colors",
+    feature = "max-colors-256",
+    feature = "max-colors-16"
+)))]
+#[test]
+fn test_gradient() {
+    use colorz::{gradient::Gradient, mode::Stream};
+
+    let rgb = |red, green, blue| RgbColor { red, green, blue };
+    let colors = [rgb(255, 0, 0), rgb(0, 255, 0), rgb(0, 0, 255)];
+
+    assert_eq!(
+        Gradient::new("a b c", &colors)
+            .stream(Stream::AlwaysColor)
+            .to_string(),
+        "\x1b[38;2;255;0;0ma \x1b[38;2;0;255;0mb \x1b[38;2;0;0;255mc\x1b[39m"
+    );
+
+    // repeated colors are only written once
+    assert_eq!(
+        Gradient::new("ab", &[rgb(1, 2, 3)])
+            .stream(Stream::AlwaysColor)
+            .to_string(),
+        "\x1b[38;2;1;2;3mab\x1b[39m"
+    );
+
+    assert_eq!(
+        Gradient::new("abc", &[])
+            .stream(Stream::AlwaysColor)
+            .to_string(),
+        "abc"
+    );
+}
@@ -25,6 +25,27 @@ fn test_background() {
     assert_eq!(format!("{}", style.apply()), "\x1b[2m\x1b[44m");
 }
 
+#[test]
+fn test_default_color() {
+    let style = Style::new()
+        .fg(colorz::Color::Default)
+        .bg(colorz::Color::Default)
+        .underline_color(colorz::Color::Default)
+        .underline();
+
+    assert_eq!(
+        format!("{}", style.apply()),
+        "\x1b[59m\x1b[4m\x1b[39m\x1b[49m"
+    );
+
+    let style = Style::new_runtime()
+        .fg_const(colorz::Color::Default)
+        .bg_const(colorz::Color::Default)
+        .underline_color_const(colorz::Color::Default)
+        .underline();
+    assert_eq!(style.escapes().prefix(), "\x1b[59;39;49;4m");
+}
+
 #[test]
 fn test_partial() {
     let background = Style::new().bg(colorz::Color::Ansi(colorz::ansi::AnsiColor::Red));
@@ -41,6 +62,7 @@ fn test_partial() {
     assert_eq!(x, "\x1b[41mhello \x1b[34mmy\x1b[39m world\x1b[49m")
 }
 
+#[cfg(not(any(feature = "max-colors-256", feature = "max-colors-16")))]
 #[test]
 fn test_rgb() {
     let style = Style::new().bg(colorz::Color::Rgb(colorz::rgb::RgbColor {
@@ -52,6 +74,7 @@ fn test_rgb() {
     assert_eq!(format!("{}", style.apply()), "\x1b[48;2;255;128;0m");
 }
 
+#[cfg(not(any(feature = "max-colors-256", feature = "max-colors-16")))]
 #[test]
 fn test_rgb_const() {
     let style = Style::new().bg(colorz::rgb::Rgb::<255, 0, 18>);
@@ -64,6 +87,7 @@ fn test_rgb_const() {
     );
 }
 
+#[cfg(not(any(feature = "max-colors-256", feature = "max-colors-16")))]
 #[test]
 fn test_rgb_to_runtime() {
     let style = Style::new()
@@ -72,3 +96,460 @@ fn test_rgb_to_runtime() {
 
     assert_eq!(format!("{}", style.apply()), "\x1b[48;2;255;128;0m");
 }
+
+#[cfg(not(any(feature = "max-colors-256", feature = "max-colors-16")))]
+#[test]
+fn test_rgb_runtime_foreground() {
+    let style = Style::new()
+        .fg(colorz::Color::Rgb(colorz::rgb::RgbColor {
+            red: 1,
+            green: 2,
+            blue: 3,
+        }))
+        .bg(colorz::Color::Ansi(colorz::ansi::AnsiColor::Red))
+        .bold()
+        .italics();
+
+    assert_eq!(format!("{}", style.apply()), "\x1b[38;2;1;2;3;41;1;3m");
+}
+
+#[cfg(feature = "max-colors-16")]
+#[test]
+fn test_max_colors() {
+    let style = Style::new()
+        .fg(colorz::rgb::Rgb::<255, 0, 0>)
+        .bg(colorz::xterm::Aqua)
+        .into_runtime_style();
+
+    assert_eq!(format!("{}", style.apply()), "\x1b[91m\x1b[106m");
+    assert_eq!(
+        colorz::ColorSpec::foreground_args(colorz::rgb::Rgb::<255, 0, 0>),
+        "91"
+    );
+}
+
+#[test]
+fn test_erased_style() {
+    use colorz::{Colorize, RuntimeStyledValue};
+
+    let values: [RuntimeStyledValue<&str>; 2] = [
+        "a".red().into_runtime_style(),
+        "b".fg(Some(colorz::xterm::Aqua))
+            .bold()
+            .into_runtime_style(),
+    ];
+
+    assert_eq!(format!("{}", values[0]), format!("{}", "a".red()));
+    assert_eq!(
+        format!("{}", values[1]),
+        format!("{}", "b".fg(colorz::xterm::Aqua).bold())
+    );
+}
+
+#[cfg(all(feature = "alloc", not(feature = "strip-colors")))]
+#[test]
+fn test_styled_text() {
+    use colorz::{ansi, StyledText};
+
+    let mut text = StyledText::new();
+    text.push_styled(Style::new().fg(ansi::Red), "a");
+    text.push_styled(Style::new().fg(ansi::Red), "b");
+    text.push_plain("c");
+    text.push_styled(Style::new().bold(), "d");
+
+    assert_eq!(text.spans().len(), 3);
+    assert_eq!(format!("{text}"), "\x1b[31mab\x1b[39mc\x1b[1md\x1b[22m");
+}
+
+#[test]
+fn test_style_from_str() {
+    use colorz::{ansi, ParseColorError, ParseStyleError};
+
+    assert_eq!(
+        "  italic  on   #102030 ".parse::<Style>(),
+        Ok(Style::new()
+            .bg(colorz::Color::Rgb(colorz::rgb::RgbColor {
+                red: 0x10,
+                green: 0x20,
+                blue: 0x30
+            }))
+            .italics()
+            .into_runtime_style())
+    );
+    assert_eq!(
+        "Blink-Fast UNDERLINE bright green".parse::<Style>(),
+        Ok(Style::new()
+            .fg(ansi::BrightGreen)
+            .blink_fast()
+            .underline()
+            .into_runtime_style())
+    );
+    assert_eq!("".parse::<Style>(), Ok(Style::new().into_runtime_style()));
+    assert_eq!("on".parse::<Style>(), Err(ParseStyleError::MissingColor));
+    assert_eq!(
+        "underline:".parse::<Style>(),
+        Err(ParseStyleError::MissingColor)
+    );
+    assert_eq!(
+        "red blue".parse::<Style>(),
+        Err(ParseStyleError::DuplicateColor)
+    );
+    assert_eq!("shiny".parse::<Style>(), Err(ParseStyleError::UnknownWord));
+    assert_eq!(
+        "RED on BLUE".parse::<Style>(),
+        Ok(Style::new()
+            .fg(ansi::Red)
+            .bg(ansi::Blue)
+            .into_runtime_style())
+    );
+    assert_eq!(
+        "Bright Red on css:blue".parse::<Style>(),
+        Ok(Style::new()
+            .fg(ansi::BrightRed)
+            .bg(colorz::Color::Css(colorz::css::CssColor::Blue))
+            .into_runtime_style())
+    );
+
+    // the arguments of color functions may contain spaces
+    assert_eq!(
+        "rgb(1, 2, 3) on hsl(120, 50%, 50%)".parse::<Style>(),
+        Ok(Style::new()
+            .fg(colorz::rgb::RgbColor {
+                red: 1,
+                green: 2,
+                blue: 3
+            })
+            .bg(colorz::rgb::RgbColor {
+                red: 64,
+                green: 191,
+                blue: 64
+            })
+            .into_runtime_style())
+    );
+    assert_eq!(
+        "bold underline:rgb( 255 0 0 ) on rgb(0,0,255)".parse::<Style>(),
+        Ok(Style::new()
+            .bg(colorz::rgb::RgbColor {
+                red: 0,
+                green: 0,
+                blue: 255
+            })
+            .underline_color(colorz::rgb::RgbColor {
+                red: 255,
+                green: 0,
+                blue: 0
+            })
+            .bold()
+            .underline()
+            .into_runtime_style())
+    );
+    assert_eq!(
+        "rgb(1, 2) bold".parse::<Style>(),
+        Err(ParseStyleError::InvalidColor(
+            ParseColorError::InvalidArguments
+        ))
+    );
+    assert_eq!(
+        "on rgb(1, 2, 3".parse::<Style>(),
+        Err(ParseStyleError::InvalidColor(ParseColorError::UnknownColor))
+    );
+    assert_eq!(
+        "#12345g".parse::<Style>(),
+        Err(ParseStyleError::InvalidColor(
+            ParseColorError::InvalidHexDigit
+        ))
+    );
+}
+
+#[cfg(not(any(feature = "max-colors-256", feature = "max-colors-16")))]
+#[test]
+fn test_style_transition() {
+    use colorz::{ansi, css, Color};
+
+    let plain = Style::new().into_runtime_style();
+    let bold_dim = Style::new().bold().dimmed().into_runtime_style();
+    let dim = Style::new().dimmed().into_runtime_style();
+
+    // clearing bold also clears dimmed, so it must be applied again
+    assert_eq!(dim.transition_from(&bold_dim).to_string(), "\x1b[22;2m");
+    assert_eq!(bold_dim.transition_from(&dim).to_string(), "\x1b[1m");
+
+    let underlined = Style::new()
+        .underline_color(Color::Css(css::CssColor::Red))
+        .bg(ansi::Blue)
+        .underline()
+        .into_runtime_style();
+    let double = Style::new()
+        .underline_color(Color::Css(css::CssColor::Red))
+        .double_underline()
+        .into_runtime_style();
+
+    assert_eq!(
+        underlined.transition_from(&plain).to_string(),
+        "\x1b[44;58;2;255;0;0;4m"
+    );
+    assert_eq!(
+        double.transition_from(&underlined).to_string(),
+        "\x1b[24;49;21m"
+    );
+    assert_eq!(plain.transition_from(&double).to_string(), "\x1b[24;59m");
+
+    // the underline color isn't visible without underlining the text
+    let hidden_underline = Style::new()
+        .underline_color(Color::Css(css::CssColor::Red))
+        .into_runtime_style();
+    assert_eq!(hidden_underline.transition_from(&plain).to_string(), "");
+}
+
+#[test]
+#[cfg(all(feature = "std", not(feature = "strip-colors")))]
+fn test_nested_styled_values() {
+    use colorz::{mode::Stream, Colorize};
+    use core::fmt;
+
+    struct Greeting;
+
+    impl fmt::Display for Greeting {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let world = "world".red().bold().nested().stream(Stream::AlwaysColor);
+            write!(f, "hello {world}!")
+        }
+    }
+
+    let greeting = Greeting.green().on_blue().stream(Stream::AlwaysColor);
+
+    assert_eq!(
+        greeting.to_string(),
+        "\x1b[32m\x1b[44mhello \x1b[31;1mworld\x1b[22;32m!\x1b[39m\x1b[49m"
+    );
+
+    // outside of another styled value, the inner value is cleared as usual
+    assert_eq!(
+        Greeting.to_string(),
+        "hello \x1b[1m\x1b[31mworld\x1b[22m\x1b[39m!"
+    );
+}
+
+#[test]
+#[cfg(all(feature = "std", not(feature = "strip-colors")))]
+fn test_unrelated_values_inside_styled_value() {
+    use colorz::{mode::Stream, Colorize};
+    use core::fmt;
+    use std::cell::RefCell;
+
+    struct Inner<'a>(&'a RefCell<String>);
+
+    impl fmt::Display for Inner<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // formatted separately, so it must not pick up the outer style
+            *self.0.borrow_mut() = "inner".red().stream(Stream::AlwaysColor).to_string();
+            f.write_str("outer")
+        }
+    }
+
+    let inner = RefCell::new(String::new());
+    let value = Inner(&inner).into_green().stream(Stream::AlwaysColor);
+
+    assert_eq!(value.to_string(), "\x1b[32mouter\x1b[39m");
+    assert_eq!(*inner.borrow(), "\x1b[31minner\x1b[39m");
+}
+
+#[test]
+#[cfg(not(feature = "strip-colors"))]
+fn test_per_line() {
+    use colorz::{mode::Stream, Colorize};
+
+    let value = "\nhello\n\nworld\n"
+        .red()
+        .per_line()
+        .stream(Stream::AlwaysColor);
+    assert_eq!(
+        value.to_string(),
+        "\x1b[31m\x1b[39m\n\x1b[31mhello\x1b[39m\n\n\x1b[31mworld\x1b[39m\n"
+    );
+
+    // formatting flags are still passed to the value
+    let value = 1.5.red().per_line().stream(Stream::AlwaysColor);
+    assert_eq!(format!("{value:+.2}"), "\x1b[31m+1.50\x1b[39m");
+}
+
+#[test]
+#[cfg(not(feature = "strip-colors"))]
+fn test_padding() {
+    use colorz::{mode::Stream, Colorize};
+
+    let hi = "hi".red().stream(Stream::AlwaysColor);
+    assert_eq!(format!("{hi:>5}"), "   \x1b[31mhi\x1b[39m");
+    assert_eq!(format!("{hi:*<5}"), "\x1b[31mhi\x1b[39m***");
+    assert_eq!(format!("{hi:^5}"), " \x1b[31mhi\x1b[39m  ");
+    // strings are left aligned by default
+    assert_eq!(format!("{hi:5}"), "\x1b[31mhi\x1b[39m   ");
+    assert_eq!(format!("{hi:1}"), "\x1b[31mhi\x1b[39m");
+    // precision is still passed to the value
+    assert_eq!(format!("{hi:>5.1}"), "    \x1b[31mh\x1b[39m");
+
+    // numbers are right aligned by default
+    let num = 42.red().stream(Stream::AlwaysColor);
+    assert_eq!(format!("{num:5}"), "   \x1b[31m42\x1b[39m");
+    assert_eq!(format!("{num:+5}"), "  \x1b[31m+42\x1b[39m");
+    // zero padding is done by the value
+    assert_eq!(format!("{num:05}"), "\x1b[31m00042\x1b[39m");
+
+    // values which ignore the width aren't padded
+    #[derive(Debug)]
+    struct Unit;
+    let unit = Unit.red().stream(Stream::AlwaysColor);
+    assert_eq!(format!("{unit:10?}"), "\x1b[31mUnit\x1b[39m");
+
+    // values which write different text each time aren't padded
+    struct Shrinking(core::cell::Cell<usize>);
+    impl core::fmt::Display for Shrinking {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let spaces = self.0.get();
+            self.0.set(spaces.saturating_sub(1));
+            write!(f, "{:spaces$}x", "")
+        }
+    }
+    let shrinking = Shrinking(core::cell::Cell::new(2));
+    let shrinking = shrinking.red().stream(Stream::AlwaysColor);
+    assert_eq!(format!("{shrinking:10}"), "\x1b[31mx\x1b[39m");
+}
+
+#[test]
+#[cfg(not(feature = "strip-colors"))]
+fn test_cached_style() {
+    use colorz::{ansi, mode::Stream, Colorize};
+
+    let cached = Style::new().fg(ansi::Red).into_runtime_style().precompute();
+
+    // cached values are formatted like other styled values
+    assert_eq!(
+        format!("{:>8}", cached.value("x").stream(Stream::AlwaysColor)),
+        format!("{:>8}", "x".red().stream(Stream::AlwaysColor)),
+    );
+    assert_eq!(
+        format!("{:^5}|", cached.value(1).stream(Stream::AlwaysColor)),
+        "  \x1b[31m1\x1b[39m  |"
+    );
+    assert_eq!(
+        format!("{:+.1}", cached.value(1.0).stream(Stream::AlwaysColor)),
+        "\x1b[31m+1.0\x1b[39m"
+    );
+    assert_eq!(
+        cached
+            .value("a\nb")
+            .per_line()
+            .stream(Stream::AlwaysColor)
+            .to_string(),
+        "a\nb"
+            .red()
+            .per_line()
+            .stream(Stream::AlwaysColor)
+            .to_string(),
+    );
+    assert_eq!(
+        format!("{:>3}", cached.value("x").stream(Stream::NeverColor)),
+        "  x"
+    );
+}
+
+#[test]
+#[cfg(not(feature = "strip-colors"))]
+#[cfg(not(any(feature = "max-colors-256", feature = "max-colors-16")))]
+fn test_batched_escapes() {
+    use colorz::{mode::Stream, rgb::RgbColor, Colorize};
+    use core::fmt::{self, Write};
+
+    struct Counter {
+        out: String,
+        calls: usize,
+    }
+
+    impl Write for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.calls += 1;
+            self.out.push_str(s);
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter {
+        out: String::new(),
+        calls: 0,
+    };
+
+    write!(
+        counter,
+        "{}",
+        "hello".red().bold().stream(Stream::AlwaysColor)
+    )
+    .unwrap();
+    assert_eq!(counter.out, "\x1b[1m\x1b[31mhello\x1b[22m\x1b[39m");
+    // the escapes and the value are written at once
+    assert_eq!(counter.calls, 1);
+
+    counter.out.clear();
+    counter.calls = 0;
+
+    // values which don't fit in the buffer are still written in full
+    let long = "x".repeat(200);
+    write!(counter, "{}", long.red().stream(Stream::AlwaysColor)).unwrap();
+    assert_eq!(counter.out, format!("\x1b[31m{long}\x1b[39m"));
+    assert_eq!(counter.calls, 3);
+
+    counter.out.clear();
+    counter.calls = 0;
+
+    let color = RgbColor {
+        red: 255,
+        green: 255,
+        blue: 255,
+    };
+    let style = Style::new()
+        .fg(color)
+        .bg(color)
+        .underline_color(color)
+        .bold()
+        .italics()
+        .underline()
+        .strikethrough();
+
+    write!(counter, "{}", style.apply()).unwrap();
+    assert_eq!(
+        counter.out,
+        "\x1b[58;2;255;255;255m\x1b[38;2;255;255;255;48;2;255;255;255;1;3;4;9m"
+    );
+    assert_eq!(counter.calls, 1);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_serialize_style_round_trip() {
+    use colorz::{ansi, css, rgb::RgbColor, xterm, Color};
+
+    let styles = [
+        Style::new().into_runtime_style(),
+        Style::new().bold().double_underline().into_runtime_style(),
+        Style::new()
+            .fg(ansi::BrightBlue)
+            .bg(xterm::Aqua)
+            .into_runtime_style(),
+        Style::new()
+            .fg(RgbColor {
+                red: 1,
+                green: 2,
+                blue: 3,
+            })
+            .underline_color(css::DodgerBlue)
+            .underline()
+            .into_runtime_style(),
+        Style::new_runtime()
+            .fg_const(Color::Default)
+            .bg_const(Color::Ansi(ansi::AnsiColor::BrightWhite)),
+    ];
+
+    for style in styles {
+        let spec: String = serde_json::from_str(&serde_json::to_string(&style).unwrap()).unwrap();
+        assert_eq!(spec.parse::<Style>(), Ok(style), "{spec}");
+    }
+}
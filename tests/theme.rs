@@ -0,0 +1,28 @@
+#![cfg(all(feature = "std", feature = "serde"))]
+
+use colorz::{ansi, theme::Theme, Style};
+
+#[test]
+fn test_deserialize_theme() {
+    let theme: Theme =
+        serde_json::from_str(r#"{ "error": "bold red", "path": "underline:blue"}"#).unwrap();
+
+    assert_eq!(
+        theme.get("error"),
+        Some(Style::new().fg(ansi::Red).bold().into_runtime_style())
+    );
+    assert_eq!(
+        theme.get("path"),
+        Some(
+            Style::new()
+                .underline_color(ansi::Blue)
+                .underline()
+                .into_runtime_style()
+        )
+    );
+
+    let err = serde_json::from_str::<Theme>(r#"{ "error": "bold red", "path": "undreline" }"#)
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("invalid style for `path`"), "{err}");
+}